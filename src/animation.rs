@@ -0,0 +1,59 @@
+use std::io::BufRead;
+
+use crate::{day10, day11, day5, day9, Result};
+
+// A puzzle being worked through one step at a time, so it can be watched. Everything's drawn the
+// way the puzzle text draws it.
+pub trait Simulation {
+    // Moves on by one step, or returns false once there's nothing left to do.
+    fn step(&mut self) -> bool;
+    // The current state, as lines of plain text.
+    fn draw(&self) -> Vec<String>;
+    // Where it's up to, like "Move 3 of 4: move 2 from 2 to 1".
+    fn status(&self) -> String;
+}
+
+// Reads a day's puzzle input and sets up a simulation of one of its parts.
+pub type Animator = fn(&mut dyn BufRead, usize) -> Result<Box<dyn Simulation>>;
+
+// Every day that can be animated, as (day, what a step is, animator). Days with two parts simulate
+// part 1 unless asked for part 2.
+pub const ANIMATORS: &[(usize, &str, Animator)] = &[
+    (
+        5,
+        "a move of crates, by the CrateMover 9000 or 9001 for part 2",
+        day5::animation::animate,
+    ),
+    (
+        9,
+        "the head moving one square, with 2 knots or 10 for part 2",
+        day9::animation::animate,
+    ),
+    (
+        10,
+        "a clock cycle, with the CRT drawing one pixel",
+        day10::animation::animate,
+    ),
+    (
+        11,
+        "a monkey throwing an item, for 20 rounds or 10000 for part 2",
+        day11::animation::animate,
+    ),
+];
+
+pub fn animator(day: usize) -> Option<Animator> {
+    ANIMATORS
+        .iter()
+        .find(|(animator_day, _, _)| *animator_day == day)
+        .map(|(_, _, animator)| *animator)
+}
+
+// Steps through to the end, keeping every frame drawn along the way, including the first.
+pub fn frames(simulation: &mut dyn Simulation) -> Vec<Vec<String>> {
+    let mut frames = vec![simulation.draw()];
+    while simulation.step() {
+        frames.push(simulation.draw());
+    }
+
+    frames
+}
@@ -0,0 +1,172 @@
+use std::collections::BTreeMap;
+use std::error;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::path::{Component, Path, PathBuf};
+
+use crate::input::Input;
+use crate::solver::Answer;
+use crate::BoxedResult;
+
+// The known answers for each day, part and input. The file looks like this:
+//
+//     # Comments and blank lines are ignored.
+//     1.1 inputs/day1.txt = 24000
+//     10.2 inputs/day10.txt =
+//       ##..##..##..##..##..##..##..##..##..##..
+//       ###...###...###...###...###...###...###.
+//
+// Multiline answers like day 10's CRT picture start on the line after the '=', with every row
+// indented by two spaces.
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    entries: BTreeMap<(usize, usize, String), String>,
+}
+
+#[derive(Debug)]
+pub enum AnswersParseError {
+    MissingSeparator(usize),
+    BadKey(usize),
+    UnexpectedIndent(usize),
+}
+
+impl fmt::Display for AnswersParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingSeparator(line) => write!(f, "Line {} is missing its '='.", line),
+            Self::BadKey(line) => {
+                write!(
+                    f,
+                    "Line {} doesn't start with '<day>.<part> <input>'.",
+                    line
+                )
+            }
+            Self::UnexpectedIndent(line) => {
+                write!(
+                    f,
+                    "Line {} is indented but doesn't continue an answer.",
+                    line
+                )
+            }
+        }
+    }
+}
+
+impl error::Error for AnswersParseError {}
+
+const INDENT: &str = "  ";
+
+impl Answers {
+    pub fn from_reader<R: BufRead>(reader: R) -> BoxedResult<Self> {
+        let mut answers = Self::default();
+        // The key of the multiline answer currently being read, if there is one.
+        let mut open_key: Option<(usize, usize, String)> = None;
+
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line_number = index + 1;
+
+            if let Some(row) = line.strip_prefix(INDENT) {
+                let key = open_key
+                    .as_ref()
+                    .ok_or(AnswersParseError::UnexpectedIndent(line_number))?;
+                let answer = answers.entries.entry(key.clone()).or_default();
+                if !answer.is_empty() {
+                    answer.push('\n');
+                }
+                answer.push_str(row);
+                continue;
+            }
+
+            open_key = None;
+
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, answer) = line
+                .split_once('=')
+                .ok_or(AnswersParseError::MissingSeparator(line_number))?;
+            let key = parse_key(key.trim()).ok_or(AnswersParseError::BadKey(line_number))?;
+            let answer = answer.trim();
+
+            if answer.is_empty() {
+                open_key = Some(key.clone());
+            }
+
+            answers.entries.insert(key, String::from(answer));
+        }
+
+        Ok(answers)
+    }
+
+    pub fn write_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        for ((day, part, input), answer) in &self.entries {
+            if answer.contains('\n') {
+                writeln!(writer, "{}.{} {} =", day, part, input)?;
+                for row in answer.lines() {
+                    writeln!(writer, "{}{}", INDENT, row)?;
+                }
+            } else {
+                writeln!(writer, "{}.{} {} = {}", day, part, input, answer)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn expected(&self, day: usize, part: usize, input: &Input) -> Option<&str> {
+        self.entries
+            .get(&(day, part, input_key(input)))
+            .map(String::as_str)
+    }
+
+    pub fn insert(&mut self, day: usize, part: usize, input: &Input, answer: &Answer) {
+        self.entries
+            .insert((day, part, input_key(input)), answer.to_string());
+    }
+
+    pub fn check(&self, day: usize, part: usize, input: &Input, answer: &Answer) -> Verdict {
+        match self.expected(day, part, input) {
+            Some(expected) if expected == answer.to_string() => Verdict::Pass,
+            Some(expected) => Verdict::Mismatch(String::from(expected)),
+            None => Verdict::Missing,
+        }
+    }
+}
+
+fn parse_key(key: &str) -> Option<(usize, usize, String)> {
+    let (selection, input) = key.split_once(char::is_whitespace)?;
+    let (day, part) = selection.split_once('.')?;
+
+    Some((
+        day.parse().ok()?,
+        part.parse().ok()?,
+        path_key(Path::new(input.trim())),
+    ))
+}
+
+fn input_key(input: &Input) -> String {
+    match input {
+        Input::Path(path) => path_key(path),
+        input => input.to_string(),
+    }
+}
+
+// Paths are compared component by component, on both sides, so `inputs/day1.txt` and
+// `./inputs/day1.txt` find the same answer.
+fn path_key(path: &Path) -> String {
+    path.components()
+        .filter(|component| *component != Component::CurDir)
+        .collect::<PathBuf>()
+        .display()
+        .to_string()
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Mismatch(String),
+    Missing,
+}
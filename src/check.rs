@@ -0,0 +1,146 @@
+use std::fmt;
+
+use advent_of_code_2022::answers::{Answers, Verdict};
+use advent_of_code_2022::input::Input;
+use advent_of_code_2022::solver::Answer;
+use advent_of_code_2022::BoxedResult;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail,
+    Missing,
+    Error,
+}
+
+impl Status {
+    // A part with no answer to check against hasn't been checked at all, so that fails too unless
+    // it's allowed.
+    pub fn is_failure(&self, allow_missing: bool) -> bool {
+        match self {
+            Self::Pass => false,
+            Self::Fail | Self::Error => true,
+            Self::Missing => !allow_missing,
+        }
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pass => write!(f, "pass"),
+            Self::Fail => write!(f, "FAIL"),
+            Self::Missing => write!(f, "missing"),
+            Self::Error => write!(f, "ERROR"),
+        }
+    }
+}
+
+pub struct Row {
+    pub day: usize,
+    pub part: usize,
+    pub status: Status,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl Row {
+    pub fn new(
+        day: usize,
+        part: usize,
+        input: &Input,
+        answers: &Answers,
+        result: BoxedResult<Answer>,
+    ) -> Self {
+        let expected = String::from(answers.expected(day, part, input).unwrap_or(""));
+
+        let (status, actual) = match result {
+            Ok(answer) => {
+                let status = match answers.check(day, part, input, &answer) {
+                    Verdict::Pass => Status::Pass,
+                    Verdict::Mismatch(_) => Status::Fail,
+                    Verdict::Missing => Status::Missing,
+                };
+                (status, answer.to_string())
+            }
+            Err(error) => (Status::Error, error.to_string()),
+        };
+
+        Self {
+            day,
+            part,
+            status,
+            expected,
+            actual,
+        }
+    }
+}
+
+const HEADINGS: [&str; 5] = ["Day", "Part", "Result", "Expected", "Actual"];
+
+// Lays the rows out as a plain-text table. Pictures don't fit in a cell, so they're summarized in the
+// table and any that failed are printed in full underneath it.
+pub fn render_table(rows: &[Row]) -> String {
+    let cells = rows
+        .iter()
+        .map(|row| {
+            [
+                row.day.to_string(),
+                row.part.to_string(),
+                row.status.to_string(),
+                summarize(&row.expected),
+                summarize(&row.actual),
+            ]
+        })
+        .collect::<Vec<[String; 5]>>();
+
+    let mut widths = HEADINGS.map(str::len);
+    for row in &cells {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let mut lines = vec![format_line(&HEADINGS.map(String::from), &widths)];
+    lines.extend(cells.iter().map(|row| format_line(row, &widths)));
+
+    for row in rows {
+        if row.status == Status::Fail && (row.expected.contains('\n') || row.actual.contains('\n'))
+        {
+            lines.push(format!(
+                "\nDay {}, part {} expected:\n{}\nbut got:\n{}",
+                row.day, row.part, row.expected, row.actual
+            ));
+        }
+    }
+
+    let count = |status: Status| rows.iter().filter(|row| row.status == status).count();
+    lines.push(format!(
+        "\n{} passed, {} failed, {} errored, {} missing.",
+        count(Status::Pass),
+        count(Status::Fail),
+        count(Status::Error),
+        count(Status::Missing)
+    ));
+
+    lines.join("\n")
+}
+
+fn format_line(cells: &[String; 5], widths: &[usize; 5]) -> String {
+    cells
+        .iter()
+        .zip(widths)
+        .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+        .collect::<Vec<String>>()
+        .join("  ")
+        .trim_end()
+        .to_string()
+}
+
+fn summarize(value: &str) -> String {
+    if value.contains('\n') {
+        format!("<{} lines>", value.lines().count())
+    } else {
+        String::from(value)
+    }
+}
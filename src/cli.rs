@@ -0,0 +1,749 @@
+use std::error::Error;
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use advent_of_code_2022::image;
+use advent_of_code_2022::input::Input;
+use advent_of_code_2022::report::Format;
+use advent_of_code_2022::trace::Level;
+
+use crate::player;
+
+pub const USAGE: &str = "\
+Usage: advent-of-code-2022 [OPTIONS] [SELECTION...]
+       advent-of-code-2022 generate <DAY> [--size <N>] [--seed <SEED>]
+       advent-of-code-2022 diffcheck [--runs <N>] [--size <N>] [--seed <SEED>] [SELECTION...]
+       advent-of-code-2022 fuzz [--runs <N>] [--seed <SEED>] [--timeout <MS>] [--output <DIR>]
+                                [SELECTION...]
+       advent-of-code-2022 image <DAY> <PATH> [--input <PATH>] [--scale <N>]
+       advent-of-code-2022 animate <DAY>[.<PART>] [--input <PATH>] [--speed <N>]
+
+Selections:
+  all        Every day and part (the default)
+  7          Both parts of day 7
+  7.2        Only part 2 of day 7
+  3..9       Both parts of days 3 through 9
+
+Options:
+  --input <PATH>        Read the puzzle input from PATH instead of inputs/dayN.txt ('-' for stdin)
+  --input-dir <DIR>     Read each day's puzzle input from DIR/dayN.txt
+  --list                List the available solutions instead of running them
+  --bench               Benchmark each solution instead of timing a single run
+  --warmup <N>          Untimed runs before benchmarking (default: 10)
+  --iterations <N>      Timed runs per benchmark (default: 100)
+  --format <FORMAT>     Print the results as json, csv or markdown instead of text (with --bench,
+                        the time is the median of the timed runs)
+  --jobs <N>            Run up to N solutions at once (default: 1)
+  --memory              Also count each solution's allocations, bytes allocated and peak live bytes
+  --stream              Use the streaming solutions for days 6, 8 and 13, which read the input in
+                        one pass and only keep a bounded part of it (--list shows each one's limit)
+  --check               Compare every answer against the answers file and print a pass/fail table
+  --allow-missing       Don't fail --check for parts that have no answer in the answers file
+  --record              Save every answer to the answers file, replacing any already there
+  --answers <PATH>      The answers file used by --check and --record (default: answers.txt)
+  --watch               Keep running, and re-run a solution whenever its input file changes
+  --repl                Start a prompt for loading inputs, running parts and inspecting parsed input
+  --serve <ADDRESS>     Serve the solutions over HTTP on ADDRESS, like 127.0.0.1:8022
+  -v, --verbose         Trace each solver's steps to stderr, like day 5's moves or day 11's throws
+                        (-vv also traces how each line of the input is parsed)
+  --trace-file <PATH>   Write the trace to PATH instead of stderr (implies -v)
+  --help                Show this message
+
+Tracing can also be turned on with AOC_TRACE=debug or AOC_TRACE=trace, and sent to a file with
+AOC_TRACE_FILE=PATH. The command line takes priority over both, which work with every command.
+
+Generating inputs:
+  generate <DAY>        Print a random puzzle input for DAY that both of its parts can solve
+  --size <N>            How big to make it, in elves for day 1, moves for day 5 and so on
+                        (default: 1000)
+  --seed <SEED>         Make the same input again from SEED (default: a new seed, printed to
+                        stderr)
+
+Checking against the reference solutions:
+  diffcheck             Run each selected part and its naive reference on generated inputs, and
+                        show the first input they disagree on
+  --runs <N>            Inputs to try for each part, from size 1 up to --size (default: 100)
+  --size <N>            The size of the biggest input (default: 30)
+  --seed <SEED>         The first run's seed, counting up from there (default: a new seed)
+  --jobs <N>            Check up to N parts at once (default: 1)
+
+Fuzzing the parsers:
+  fuzz                  Feed each selected part's parser mutated examples and generated inputs,
+                        and save the smallest input found for every panic, hang or stack overflow
+  --runs <N>            Mutated inputs to try for each part (default: 1000)
+  --seed <SEED>         Mutate the same way again from SEED (default: a new seed)
+  --timeout <MS>        How long a parser can take before it counts as hung (default: 1000)
+  --output <DIR>        Where to save the crashing inputs (default: fuzz)
+  --jobs <N>            Fuzz up to N parts at once (default: 1)
+
+Drawing pictures:
+  image <DAY> <PATH>    Solve DAY's puzzle input and draw it to PATH, as a .ppm, .pgm or .svg image
+  --input <PATH>        Read the puzzle input from PATH instead of inputs/dayN.txt ('-' for stdin)
+  --scale <N>           How many pixels across each square of the puzzle is (default: 8, at most
+                        64)
+
+Watching simulations:
+  animate <DAY>         Play DAY's puzzle input step by step in the terminal, drawn the way the
+                        puzzle draws it (DAY.2 plays part 2)
+  --input <PATH>        Read the puzzle input from PATH instead of inputs/dayN.txt
+  --speed <N>           Steps a second to start at (default: 8, at most 65536)
+
+While it plays, space pauses, n steps, + and - change the speed, r restarts and q quits. Without a
+terminal to draw on, every frame is printed one after the other.";
+
+// The hidden subcommand that `fuzz` runs each part's parser in.
+pub const FUZZ_WORKER: &str = "fuzz-worker";
+
+#[derive(Debug)]
+pub enum UsageError {
+    UnknownOption(String),
+    MissingValue(String),
+    MissingArgument(&'static str),
+    InvalidValue(String, String),
+    ConflictingOptions(&'static str, &'static str),
+    InvalidSelection(String),
+}
+
+impl fmt::Display for UsageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownOption(option) => write!(f, "Unknown option '{}'.", option),
+            Self::MissingValue(option) => write!(f, "Option '{}' needs a value.", option),
+            Self::MissingArgument(argument) => write!(f, "Missing argument '{}'.", argument),
+            Self::InvalidValue(option, value) => {
+                write!(f, "Invalid value '{}' for option '{}'.", value, option)
+            }
+            Self::ConflictingOptions(first, second) => {
+                write!(
+                    f,
+                    "Options '{}' and '{}' can't be used together.",
+                    first, second
+                )
+            }
+            Self::InvalidSelection(selection) => write!(f, "Invalid selection '{}'.", selection),
+        }
+    }
+}
+
+impl Error for UsageError {}
+
+#[derive(Debug, PartialEq)]
+pub enum Selection {
+    All,
+    Day(usize),
+    Part(usize, usize),
+    Days(usize, usize),
+}
+
+impl Selection {
+    pub fn matches(&self, day: usize, part: usize) -> bool {
+        match *self {
+            Self::All => true,
+            Self::Day(selected_day) => day == selected_day,
+            Self::Part(selected_day, selected_part) => day == selected_day && part == selected_part,
+            Self::Days(first, last) => (first..=last).contains(&day),
+        }
+    }
+}
+
+impl fmt::Display for Selection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::All => write!(f, "all"),
+            Self::Day(day) => write!(f, "{}", day),
+            Self::Part(day, part) => write!(f, "{}.{}", day, part),
+            Self::Days(first, last) => write!(f, "{}..{}", first, last),
+        }
+    }
+}
+
+impl FromStr for Selection {
+    type Err = UsageError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || UsageError::InvalidSelection(String::from(value));
+        let parse_number = |number: &str| number.parse::<usize>().map_err(|_| invalid());
+
+        if value == "all" {
+            Ok(Self::All)
+        } else if let Some((first, last)) = value.split_once("..") {
+            let first = parse_number(first)?;
+            let last = parse_number(last)?;
+
+            if first > last {
+                return Err(invalid());
+            }
+
+            Ok(Self::Days(first, last))
+        } else if let Some((day, part)) = value.split_once('.') {
+            Ok(Self::Part(parse_number(day)?, parse_number(part)?))
+        } else {
+            Ok(Self::Day(parse_number(value)?))
+        }
+    }
+}
+
+// The program either runs solutions, or with a subcommand first, does one of the other jobs.
+#[derive(Debug)]
+pub enum Command {
+    Run(Options),
+    Generate(Generate),
+    DiffCheck(DiffCheck),
+    Fuzz(Fuzz),
+    Image(Image),
+    Animate(Animate),
+    // Not in the usage, since it's only for `fuzz` to start as a child process.
+    FuzzWorker(usize, usize),
+}
+
+impl Command {
+    pub fn parse<I: Iterator<Item = String>>(args: I) -> Result<Self, UsageError> {
+        let mut args = args.peekable();
+
+        if args.peek().map(String::as_str) == Some("generate") {
+            args.next();
+            Ok(Self::Generate(Generate::parse(args)?))
+        } else if args.peek().map(String::as_str) == Some("diffcheck") {
+            args.next();
+            Ok(Self::DiffCheck(DiffCheck::parse(args)?))
+        } else if args.peek().map(String::as_str) == Some("fuzz") {
+            args.next();
+            Ok(Self::Fuzz(Fuzz::parse(args)?))
+        } else if args.peek().map(String::as_str) == Some("image") {
+            args.next();
+            Ok(Self::Image(Image::parse(args)?))
+        } else if args.peek().map(String::as_str) == Some("animate") {
+            args.next();
+            Ok(Self::Animate(Animate::parse(args)?))
+        } else if args.peek().map(String::as_str) == Some(FUZZ_WORKER) {
+            args.next();
+            match args.next().map(|arg| arg.parse()).transpose()? {
+                Some(Selection::Part(day, part)) => Ok(Self::FuzzWorker(day, part)),
+                Some(selection) => Err(UsageError::InvalidSelection(selection.to_string())),
+                None => Err(UsageError::MissingArgument("DAY.PART")),
+            }
+        } else {
+            Ok(Self::Run(Options::parse(args)?))
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Generate {
+    pub day: usize,
+    pub size: usize,
+    pub seed: Option<u64>,
+    pub help: bool,
+}
+
+impl Generate {
+    pub fn parse<I: Iterator<Item = String>>(mut args: I) -> Result<Self, UsageError> {
+        let mut day = None;
+        let mut size = 1000;
+        let mut seed = None;
+        let mut help = false;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--size" => {
+                    size = parse_count(&arg, args.next())?;
+                    if size == 0 {
+                        return Err(UsageError::InvalidValue(arg, String::from("0")));
+                    }
+                }
+                "--seed" => seed = Some(parse_seed(&arg, args.next())?),
+                "-h" | "--help" => help = true,
+                _ if arg.starts_with('-') => return Err(UsageError::UnknownOption(arg)),
+                _ if day.is_none() => {
+                    day = Some(
+                        arg.parse()
+                            .map_err(|_| UsageError::InvalidValue(String::from("DAY"), arg))?,
+                    );
+                }
+                _ => return Err(UsageError::UnknownOption(arg)),
+            }
+        }
+
+        let day = match day {
+            Some(day) => day,
+            // The day doesn't matter when all that's wanted is the usage.
+            None if help => 0,
+            None => return Err(UsageError::MissingArgument("DAY")),
+        };
+
+        Ok(Self {
+            day,
+            size,
+            seed,
+            help,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct DiffCheck {
+    pub selections: Vec<Selection>,
+    pub runs: usize,
+    pub size: usize,
+    pub seed: Option<u64>,
+    pub jobs: usize,
+    pub help: bool,
+}
+
+impl DiffCheck {
+    pub fn parse<I: Iterator<Item = String>>(mut args: I) -> Result<Self, UsageError> {
+        let mut options = Self {
+            selections: Vec::new(),
+            runs: 100,
+            size: 30,
+            seed: None,
+            jobs: 1,
+            help: false,
+        };
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--runs" | "--size" | "--jobs" => {
+                    let count = parse_count(&arg, args.next())?;
+                    if count == 0 {
+                        return Err(UsageError::InvalidValue(arg, String::from("0")));
+                    }
+                    match arg.as_str() {
+                        "--runs" => options.runs = count,
+                        "--size" => options.size = count,
+                        _ => options.jobs = count,
+                    }
+                }
+                "--seed" => options.seed = Some(parse_seed(&arg, args.next())?),
+                "-h" | "--help" => options.help = true,
+                _ if arg.starts_with('-') => return Err(UsageError::UnknownOption(arg)),
+                _ => options.selections.push(arg.parse()?),
+            }
+        }
+
+        if options.selections.is_empty() {
+            options.selections.push(Selection::All);
+        }
+
+        Ok(options)
+    }
+
+    pub fn is_selected(&self, day: usize, part: usize) -> bool {
+        self.selections
+            .iter()
+            .any(|selection| selection.matches(day, part))
+    }
+}
+
+#[derive(Debug)]
+pub struct Fuzz {
+    pub selections: Vec<Selection>,
+    pub runs: usize,
+    pub seed: Option<u64>,
+    pub timeout: Duration,
+    pub output: PathBuf,
+    pub jobs: usize,
+    pub help: bool,
+}
+
+impl Fuzz {
+    pub fn parse<I: Iterator<Item = String>>(mut args: I) -> Result<Self, UsageError> {
+        let mut options = Self {
+            selections: Vec::new(),
+            runs: 1000,
+            seed: None,
+            timeout: Duration::from_millis(1000),
+            output: PathBuf::from("fuzz"),
+            jobs: 1,
+            help: false,
+        };
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--runs" => options.runs = parse_count(&arg, args.next())?,
+                "--timeout" | "--jobs" => {
+                    let count = parse_count(&arg, args.next())?;
+                    if count == 0 {
+                        return Err(UsageError::InvalidValue(arg, String::from("0")));
+                    }
+                    match arg.as_str() {
+                        "--timeout" => options.timeout = Duration::from_millis(count as u64),
+                        _ => options.jobs = count,
+                    }
+                }
+                "--seed" => options.seed = Some(parse_seed(&arg, args.next())?),
+                "--output" => {
+                    let value = args.next().ok_or(UsageError::MissingValue(arg))?;
+                    options.output = PathBuf::from(value);
+                }
+                "-h" | "--help" => options.help = true,
+                _ if arg.starts_with('-') => return Err(UsageError::UnknownOption(arg)),
+                _ => options.selections.push(arg.parse()?),
+            }
+        }
+
+        if options.selections.is_empty() {
+            options.selections.push(Selection::All);
+        }
+
+        Ok(options)
+    }
+
+    pub fn is_selected(&self, day: usize, part: usize) -> bool {
+        self.selections
+            .iter()
+            .any(|selection| selection.matches(day, part))
+    }
+}
+
+// The biggest squares pictures can be drawn with, which keeps even the largest allowed picture to
+// a file that can be opened.
+pub const MAX_SCALE: usize = 64;
+
+#[derive(Debug)]
+pub struct Image {
+    pub day: usize,
+    pub output: PathBuf,
+    pub format: image::Format,
+    pub input: Option<Input>,
+    pub scale: usize,
+    pub help: bool,
+}
+
+impl Image {
+    pub fn parse<I: Iterator<Item = String>>(mut args: I) -> Result<Self, UsageError> {
+        let mut day = None;
+        let mut output = None;
+        let mut input = None;
+        let mut scale = 8;
+        let mut help = false;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--input" => {
+                    let path = args.next().ok_or(UsageError::MissingValue(arg))?;
+                    input = Some(if path == "-" {
+                        Input::Stdin
+                    } else {
+                        Input::Path(PathBuf::from(path))
+                    });
+                }
+                "--scale" => {
+                    scale = parse_count(&arg, args.next())?;
+                    if scale == 0 || scale > MAX_SCALE {
+                        return Err(UsageError::InvalidValue(arg, scale.to_string()));
+                    }
+                }
+                "-h" | "--help" => help = true,
+                _ if arg.starts_with('-') => return Err(UsageError::UnknownOption(arg)),
+                _ if day.is_none() => {
+                    day = Some(
+                        arg.parse()
+                            .map_err(|_| UsageError::InvalidValue(String::from("DAY"), arg))?,
+                    );
+                }
+                _ if output.is_none() => output = Some(PathBuf::from(arg)),
+                _ => return Err(UsageError::UnknownOption(arg)),
+            }
+        }
+
+        // Neither matters when all that's wanted is the usage.
+        let (day, output) = match (day, output) {
+            (Some(day), Some(output)) => (day, output),
+            _ if help => (0, PathBuf::new()),
+            (None, _) => return Err(UsageError::MissingArgument("DAY")),
+            (_, None) => return Err(UsageError::MissingArgument("PATH")),
+        };
+
+        let format = match image::Format::from_path(&output) {
+            Some(format) => format,
+            None if help => image::Format::Ppm,
+            None => {
+                return Err(UsageError::InvalidValue(
+                    String::from("PATH"),
+                    output.display().to_string(),
+                ))
+            }
+        };
+
+        Ok(Self {
+            day,
+            output,
+            format,
+            input,
+            scale,
+            help,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Animate {
+    pub day: usize,
+    pub part: usize,
+    pub input: Option<Input>,
+    pub speed: usize,
+    pub help: bool,
+}
+
+impl Animate {
+    pub fn parse<I: Iterator<Item = String>>(mut args: I) -> Result<Self, UsageError> {
+        let mut selection = None;
+        let mut input = None;
+        let mut speed = player::DEFAULT_SPEED;
+        let mut help = false;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--input" => {
+                    let path = args.next().ok_or(UsageError::MissingValue(arg))?;
+                    input = Some(Input::Path(PathBuf::from(path)));
+                }
+                "--speed" => {
+                    speed = parse_count(&arg, args.next())?;
+                    if speed == 0 || speed > player::MAX_SPEED {
+                        return Err(UsageError::InvalidValue(arg, speed.to_string()));
+                    }
+                }
+                "-h" | "--help" => help = true,
+                _ if arg.starts_with('-') => return Err(UsageError::UnknownOption(arg)),
+                _ if selection.is_none() => selection = Some(arg.parse()?),
+                _ => return Err(UsageError::UnknownOption(arg)),
+            }
+        }
+
+        let (day, part) = match selection {
+            Some(Selection::Day(day)) => (day, 1),
+            Some(Selection::Part(day, part)) if part == 1 || part == 2 => (day, part),
+            Some(selection) => return Err(UsageError::InvalidSelection(selection.to_string())),
+            None if help => (0, 1),
+            None => return Err(UsageError::MissingArgument("DAY")),
+        };
+
+        Ok(Self {
+            day,
+            part,
+            input,
+            speed,
+            help,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Options {
+    pub selections: Vec<Selection>,
+    pub input: Option<Input>,
+    pub input_directory: Option<PathBuf>,
+    pub list: bool,
+    pub bench: bool,
+    pub warmup: usize,
+    pub iterations: usize,
+    pub format: Option<Format>,
+    pub jobs: usize,
+    pub memory: bool,
+    pub stream: bool,
+    pub check: bool,
+    pub allow_missing: bool,
+    pub record: bool,
+    pub answers: PathBuf,
+    pub watch: bool,
+    pub repl: bool,
+    pub serve: Option<String>,
+    pub verbosity: usize,
+    pub trace_file: Option<PathBuf>,
+    pub help: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            selections: Vec::new(),
+            input: None,
+            input_directory: None,
+            list: false,
+            bench: false,
+            warmup: 10,
+            iterations: 100,
+            format: None,
+            jobs: 1,
+            memory: false,
+            stream: false,
+            check: false,
+            allow_missing: false,
+            record: false,
+            answers: PathBuf::from("answers.txt"),
+            watch: false,
+            repl: false,
+            serve: None,
+            verbosity: 0,
+            trace_file: None,
+            help: false,
+        }
+    }
+}
+
+impl Options {
+    pub fn parse<I: Iterator<Item = String>>(mut args: I) -> Result<Self, UsageError> {
+        let mut options = Self::default();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--input" => {
+                    let path = args.next().ok_or(UsageError::MissingValue(arg))?;
+                    options.input = Some(if path == "-" {
+                        Input::Stdin
+                    } else {
+                        Input::Path(PathBuf::from(path))
+                    });
+                }
+                "--input-dir" => {
+                    let path = args.next().ok_or(UsageError::MissingValue(arg))?;
+                    options.input_directory = Some(PathBuf::from(path));
+                }
+                "--list" => options.list = true,
+                "--bench" => options.bench = true,
+                "--warmup" => options.warmup = parse_count(&arg, args.next())?,
+                "--iterations" => {
+                    options.iterations = parse_count(&arg, args.next())?;
+                    if options.iterations == 0 {
+                        return Err(UsageError::InvalidValue(arg, String::from("0")));
+                    }
+                }
+                "--format" => {
+                    let format = args.next().ok_or(UsageError::MissingValue(arg.clone()))?;
+                    options.format = Some(
+                        format
+                            .parse()
+                            .map_err(|_| UsageError::InvalidValue(arg, format))?,
+                    );
+                }
+                "--jobs" => {
+                    options.jobs = parse_count(&arg, args.next())?;
+                    if options.jobs == 0 {
+                        return Err(UsageError::InvalidValue(arg, String::from("0")));
+                    }
+                }
+                "--memory" => options.memory = true,
+                "--stream" => options.stream = true,
+                "--check" => options.check = true,
+                "--allow-missing" => options.allow_missing = true,
+                "--record" => options.record = true,
+                "--answers" => {
+                    let path = args.next().ok_or(UsageError::MissingValue(arg))?;
+                    options.answers = PathBuf::from(path);
+                }
+                "--watch" => options.watch = true,
+                "--repl" => options.repl = true,
+                "--serve" => {
+                    options.serve = Some(args.next().ok_or(UsageError::MissingValue(arg))?);
+                }
+                "-v" | "--verbose" => options.verbosity += 1,
+                "-vv" => options.verbosity += 2,
+                "--trace-file" => {
+                    let path = args.next().ok_or(UsageError::MissingValue(arg))?;
+                    options.trace_file = Some(PathBuf::from(path));
+                }
+                "-h" | "--help" => options.help = true,
+                _ if arg.starts_with('-') => return Err(UsageError::UnknownOption(arg)),
+                _ => options.selections.push(arg.parse()?),
+            }
+        }
+
+        if options.check {
+            if options.bench {
+                return Err(UsageError::ConflictingOptions("--check", "--bench"));
+            } else if options.format.is_some() {
+                return Err(UsageError::ConflictingOptions("--check", "--format"));
+            } else if options.record {
+                return Err(UsageError::ConflictingOptions("--check", "--record"));
+            }
+        }
+
+        // Watching re-runs forever, so it only makes sense for output that's read as it goes.
+        if options.watch {
+            if options.check {
+                return Err(UsageError::ConflictingOptions("--watch", "--check"));
+            } else if options.record {
+                return Err(UsageError::ConflictingOptions("--watch", "--record"));
+            } else if options.format.is_some() {
+                return Err(UsageError::ConflictingOptions("--watch", "--format"));
+            } else if options.input == Some(Input::Stdin) {
+                return Err(UsageError::ConflictingOptions("--watch", "--input -"));
+            }
+        }
+
+        // The REPL picks its own days and inputs as it goes.
+        if options.repl {
+            if options.watch {
+                return Err(UsageError::ConflictingOptions("--repl", "--watch"));
+            } else if options.check {
+                return Err(UsageError::ConflictingOptions("--repl", "--check"));
+            } else if options.record {
+                return Err(UsageError::ConflictingOptions("--repl", "--record"));
+            } else if options.format.is_some() {
+                return Err(UsageError::ConflictingOptions("--repl", "--format"));
+            } else if options.stream {
+                return Err(UsageError::ConflictingOptions("--repl", "--stream"));
+            } else if options.input.is_some() {
+                return Err(UsageError::ConflictingOptions("--repl", "--input"));
+            } else if !options.selections.is_empty() {
+                return Err(UsageError::ConflictingOptions("--repl", "SELECTION"));
+            }
+        }
+
+        // The server takes its days, parts and inputs from each request.
+        if options.serve.is_some() {
+            if options.repl {
+                return Err(UsageError::ConflictingOptions("--serve", "--repl"));
+            } else if options.watch {
+                return Err(UsageError::ConflictingOptions("--serve", "--watch"));
+            } else if options.check {
+                return Err(UsageError::ConflictingOptions("--serve", "--check"));
+            } else if options.record {
+                return Err(UsageError::ConflictingOptions("--serve", "--record"));
+            } else if options.stream {
+                return Err(UsageError::ConflictingOptions("--serve", "--stream"));
+            } else if options.input.is_some() {
+                return Err(UsageError::ConflictingOptions("--serve", "--input"));
+            } else if !options.selections.is_empty() {
+                return Err(UsageError::ConflictingOptions("--serve", "SELECTION"));
+            }
+        }
+
+        if options.selections.is_empty() {
+            options.selections.push(Selection::All);
+        }
+
+        Ok(options)
+    }
+
+    pub fn is_selected(&self, day: usize, part: usize) -> bool {
+        self.selections
+            .iter()
+            .any(|selection| selection.matches(day, part))
+    }
+
+    // Without any `-v`s, the level's left to the environment.
+    pub fn trace_level(&self) -> Option<Level> {
+        (self.verbosity > 0).then(|| Level::from_verbosity(self.verbosity))
+    }
+}
+
+fn parse_count(option: &str, value: Option<String>) -> Result<usize, UsageError> {
+    let value = value.ok_or_else(|| UsageError::MissingValue(String::from(option)))?;
+
+    value
+        .parse::<usize>()
+        .map_err(|_| UsageError::InvalidValue(String::from(option), value))
+}
+
+fn parse_seed(option: &str, value: Option<String>) -> Result<u64, UsageError> {
+    let value = value.ok_or_else(|| UsageError::MissingValue(String::from(option)))?;
+
+    value
+        .parse::<u64>()
+        .map_err(|_| UsageError::InvalidValue(String::from(option), value))
+}
@@ -1,2 +1,36 @@
 pub mod part1;
 pub mod part2;
+pub mod reference;
+
+use std::io::BufRead;
+
+use crate::parse;
+use crate::solver::{Reference, Solver};
+use crate::{Problem, Result};
+
+// Every snack an elf is carrying, in the order they were listed.
+pub type Elf = Vec<usize>;
+
+// Each elf's snacks are a group of lines, with a blank line before the next elf's.
+pub fn parse_elves<R: BufRead>(reader: R) -> Result<Vec<Elf>> {
+    // If every snack together fits, then so does any group of them we add up later.
+    let mut total: usize = 0;
+
+    parse::records(reader, 1)
+        .map(|record| {
+            record?
+                .lines()
+                .map(|line| {
+                    let value = line.number::<usize>(line.text)?;
+                    total = total
+                        .checked_add(value)
+                        .ok_or_else(|| line.error(Problem::Overflow("add up")))?;
+                    Ok(value)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+pub const SOLVERS: [&dyn Solver; 2] = [&part1::Solution, &part2::Solution];
+pub const REFERENCES: [Reference; 2] = [reference::part1, reference::part2];
@@ -1,34 +1,48 @@
-use std::fs;
-use std::io;
 use std::io::BufRead;
 
-// The basic idea of the algorithm is to just run through the file one line at
-// a time, keeping track of the biggest value we've seen. At the end, return it!
-pub fn find_elf_with_most_calories() -> usize {
-    let file = fs::File::open("inputs/day1.txt").unwrap();
-    let buf_reader = io::BufReader::new(file);
+use super::*;
+use crate::solver::{downcast, Answer, Parsed, Solver};
 
-    let mut biggest: usize = 0;
-    let mut sum: usize = 0;
+pub fn find_elf_with_most_calories<R: BufRead>(reader: R) -> Result<usize> {
+    Ok(find_most_calories(&parse_elves(reader)?))
+}
 
-    for line_result in buf_reader.lines() {
-        let line = line_result.unwrap();
+// The basic idea of the algorithm is to just run through the elves one at a
+// time, keeping track of the biggest total we've seen. At the end, return it!
+pub fn find_most_calories(elves: &[Elf]) -> usize {
+    let mut biggest: usize = 0;
 
-        if line.len() == 0 {
-            if sum > biggest {
-                biggest = sum;
-            }
+    for elf in elves {
+        let sum: usize = elf.iter().sum();
 
-            sum = 0;
-        } else {
-            let value = line.parse::<usize>().unwrap();
-            sum += value;
+        if sum > biggest {
+            biggest = sum;
         }
     }
 
-    if sum > biggest {
-        sum
-    } else {
-        biggest
+    biggest
+}
+
+pub struct Solution;
+
+impl Solver for Solution {
+    fn day(&self) -> usize {
+        1
+    }
+
+    fn part(&self) -> usize {
+        1
+    }
+
+    fn name(&self) -> &'static str {
+        "day1::part1::find_elf_with_most_calories"
+    }
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Parsed> {
+        Ok(Box::new(parse_elves(input)?))
+    }
+
+    fn solve_parsed(&self, parsed: Parsed) -> Result<Answer> {
+        Ok(find_most_calories(&downcast::<Vec<Elf>>(parsed)?).into())
     }
 }
@@ -1,43 +1,54 @@
-use std::fs;
-use std::io;
 use std::io::BufRead;
 
+use super::*;
+use crate::solver::{downcast, Answer, Parsed, Solver};
+
+pub fn find_three_elves_with_most_calories<R: BufRead>(reader: R) -> Result<usize> {
+    Ok(find_three_most_calories(&parse_elves(reader)?))
+}
+
 // This one is similar, but we use an array of the three biggest values instead
 // of just the single biggest.
-pub fn find_three_elves_with_most_calories() -> usize {
-    let file = fs::File::open("inputs/day1.txt").unwrap();
-    let buf_reader = io::BufReader::new(file);
-
+pub fn find_three_most_calories(elves: &[Elf]) -> usize {
     let mut biggest_three: [usize; 3] = [0, 0, 0];
-    let mut sum: usize = 0;
-
-    for line_result in buf_reader.lines() {
-        let line = line_result.unwrap();
-
-        if line.len() == 0 {
-            // Since this for loop is an early exit loop, sorting makes sure
-            // we're always replacing the lowest value.
-            biggest_three.sort();
-            for biggest in &mut biggest_three {
-                if sum > *biggest {
-                    *biggest = sum;
-                    break;
-                }
-            }
 
-            sum = 0;
-        } else {
-            let value = line.parse::<usize>().unwrap();
-            sum += value;
-        }
-    }
+    for elf in elves {
+        let sum: usize = elf.iter().sum();
 
-    for biggest in &mut biggest_three {
-        if sum > *biggest {
-            *biggest = sum;
-            break;
+        // Since this for loop is an early exit loop, sorting makes sure
+        // we're always replacing the lowest value.
+        biggest_three.sort();
+        for biggest in &mut biggest_three {
+            if sum > *biggest {
+                *biggest = sum;
+                break;
+            }
         }
     }
 
     biggest_three.iter().sum()
 }
+
+pub struct Solution;
+
+impl Solver for Solution {
+    fn day(&self) -> usize {
+        1
+    }
+
+    fn part(&self) -> usize {
+        2
+    }
+
+    fn name(&self) -> &'static str {
+        "day1::part2::find_three_elves_with_most_calories"
+    }
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Parsed> {
+        Ok(Box::new(parse_elves(input)?))
+    }
+
+    fn solve_parsed(&self, parsed: Parsed) -> Result<Answer> {
+        Ok(find_three_most_calories(&downcast::<Vec<Elf>>(parsed)?).into())
+    }
+}
@@ -0,0 +1,22 @@
+use crate::solver::Answer;
+
+// Every elf's total, worked out straight from the text.
+fn totals(input: &str) -> Option<Vec<usize>> {
+    input
+        .trim_end()
+        .split("\n\n")
+        .map(|elf| elf.lines().map(|line| line.parse::<usize>().ok()).sum())
+        .collect()
+}
+
+pub fn part1(input: &str) -> Option<Answer> {
+    Some(totals(input)?.into_iter().max()?.into())
+}
+
+pub fn part2(input: &str) -> Option<Answer> {
+    let mut totals = totals(input)?;
+    totals.sort();
+    totals.reverse();
+
+    Some(totals.iter().take(3).sum::<usize>().into())
+}
@@ -0,0 +1,105 @@
+use crate::animation::Simulation;
+
+use super::part1::SignalStrength;
+use super::part2::CRT;
+use super::*;
+
+// Keeps what X was during every cycle, so the other peripherals can be replayed one cycle at a
+// time afterwards.
+struct Recording {
+    register_x: Vec<isize>,
+}
+
+impl Peripheral for Recording {
+    fn new() -> Self {
+        Self {
+            register_x: Vec::new(),
+        }
+    }
+
+    fn update(&mut self, register_x: isize, _: usize) {
+        self.register_x.push(register_x);
+    }
+}
+
+// Both parts watch the same program, with one clock cycle per step.
+pub struct CrtAnimation {
+    register_x: Vec<isize>,
+    cycle: usize,
+    crt: CRT,
+    signal_strength: SignalStrength,
+}
+
+pub fn animate(input: &mut dyn BufRead, _: usize) -> Result<Box<dyn Simulation>> {
+    let mut cpu = CPU::<Recording>::new();
+    cpu.execute_reader(input)?;
+
+    Ok(Box::new(CrtAnimation {
+        register_x: cpu.peripheral.register_x,
+        cycle: 0,
+        crt: CRT::new(),
+        signal_strength: SignalStrength::new(),
+    }))
+}
+
+impl Simulation for CrtAnimation {
+    fn step(&mut self) -> bool {
+        let Some(register_x) = self.register_x.get(self.cycle).copied() else {
+            return false;
+        };
+
+        self.cycle += 1;
+        self.crt.update(register_x, self.cycle);
+        self.signal_strength.update(register_x, self.cycle);
+        true
+    }
+
+    // Where the sprite is for the next cycle, then the CRT so far, like in the puzzle.
+    fn draw(&self) -> Vec<String> {
+        let sprite = self
+            .register_x
+            .get(self.cycle)
+            .or(self.register_x.last())
+            .copied()
+            .unwrap_or(1);
+        let sprite_position = (0..40)
+            .map(|pixel: isize| {
+                if (pixel - sprite).abs() <= 1 {
+                    '#'
+                } else {
+                    '.'
+                }
+            })
+            .collect::<String>();
+
+        let mut lines = vec![
+            format!("Sprite position: {}", sprite_position),
+            String::new(),
+        ];
+        lines.extend(self.crt.rows.iter().filter(|row| !row.is_empty()).cloned());
+
+        lines
+    }
+
+    fn status(&self) -> String {
+        let sum = match self.signal_strength.signal_strength {
+            Some(sum) => sum.to_string(),
+            None => String::from("too big to hold"),
+        };
+
+        match self
+            .cycle
+            .checked_sub(1)
+            .map(|cycle| self.register_x[cycle])
+        {
+            Some(register_x) => format!(
+                "Cycle {} of {}: X is {}. The signal strengths add up to {}.",
+                self.cycle,
+                self.register_x.len(),
+                register_x,
+                sum
+            ),
+            None => format!("{} cycles to run.", self.register_x.len()),
+        }
+    }
+}
@@ -1,6 +1,3 @@
-use std::error::Error;
-use std::fmt;
-
 use super::*;
 
 pub trait Peripheral {
@@ -8,12 +5,19 @@ pub trait Peripheral {
     fn update(&mut self, register_x: isize, clock_cycle: usize);
 }
 
+#[allow(clippy::upper_case_acronyms)]
 pub struct CPU<T: Peripheral> {
     register_x: isize,
     clock_cycle: usize,
     pub peripheral: T,
 }
 
+impl<T: Peripheral> Default for CPU<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T: Peripheral> CPU<T> {
     pub fn new() -> Self {
         Self {
@@ -23,12 +27,20 @@ impl<T: Peripheral> CPU<T> {
         }
     }
 
+    pub fn register_x(&self) -> isize {
+        self.register_x
+    }
+
+    pub fn clock_cycle(&self) -> usize {
+        self.clock_cycle
+    }
+
     fn increment_clock(&mut self) {
         self.clock_cycle += 1;
         self.peripheral.update(self.register_x, self.clock_cycle);
     }
 
-    fn execute_instruction(&mut self, instruction: Instruction) {
+    pub fn execute_instruction(&mut self, instruction: Instruction) {
         match instruction {
             Instruction::AddX(amount) => {
                 self.increment_clock();
@@ -39,66 +51,42 @@ impl<T: Peripheral> CPU<T> {
         }
     }
 
-    pub fn execute_file(&mut self, path: &str) -> BoxedResult<()> {
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
-
-        for line in reader.lines() {
-            let instruction = Instruction::try_from(line?)?;
-            self.execute_instruction(instruction);
-        }
+    pub fn execute_reader<R: BufRead>(&mut self, reader: R) -> Result<()> {
+        self.execute_program(&parse_program(reader)?);
 
         Ok(())
     }
-}
 
-#[derive(Debug)]
-enum ParseInstructionError {
-    MissingCommand,
-    InvalidCommand(String),
-    MissingArgument(String, usize),
-}
-
-impl fmt::Display for ParseInstructionError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::MissingCommand => write!(f, "Missing command!"),
-            Self::InvalidCommand(command) => write!(f, "Invalid command: {}", command),
-            Self::MissingArgument(command, index) => {
-                write!(f, "Missing argument {} for '{}'", index, command)
-            }
+    pub fn execute_program(&mut self, program: &[Instruction]) {
+        for instruction in program {
+            self.execute_instruction(*instruction);
         }
     }
 }
 
-impl Error for ParseInstructionError {}
+pub fn parse_program<R: BufRead>(reader: R) -> Result<Vec<Instruction>> {
+    parse::lines(reader, 10, |line| Instruction::parse(&line))
+}
 
+#[derive(Clone, Copy)]
 pub enum Instruction {
     AddX(isize),
     NoOp,
 }
 
-impl TryFrom<String> for Instruction {
-    type Error = Box<dyn Error>;
-
-    fn try_from(value: String) -> Result<Self, Self::Error> {
-        let mut tokens = value.split_ascii_whitespace();
-        let command = tokens.next().ok_or(ParseInstructionError::MissingCommand)?;
+impl Instruction {
+    pub fn parse(line: &Line) -> Result<Self> {
+        let mut fields = Fields::new(line);
 
-        match command {
-            "addx" => {
-                let amount = tokens
-                    .next()
-                    .ok_or(ParseInstructionError::MissingArgument(
-                        String::from(command),
-                        0,
-                    ))?
-                    .parse::<isize>()?;
+        let instruction = match fields.next("command")? {
+            // Keeping each amount to 32 bits means the register can't overflow without billions of
+            // additions.
+            "addx" => Instruction::AddX(fields.number::<i32>("amount for 'addx'")? as isize),
+            "noop" => Instruction::NoOp,
+            command => return Err(line.error_at(command, Problem::Unrecognized("command"))),
+        };
+        fields.end()?;
 
-                Ok(Instruction::AddX(amount))
-            }
-            "noop" => Ok(Instruction::NoOp),
-            _ => Err(ParseInstructionError::InvalidCommand(String::from(command)).into()),
-        }
+        Ok(instruction)
     }
 }
@@ -1,10 +1,33 @@
-mod cpu;
+pub mod animation;
+pub mod cpu;
 pub mod part1;
 pub mod part2;
+pub mod reference;
 
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::time::Instant;
+use std::io::BufRead;
 
-use crate::{BoxedResult, TimedResult};
-use cpu::*;
+use crate::image::{self, Color, Picture};
+use crate::parse::{self, Fields};
+use crate::solver::{Reference, Solver};
+use crate::{Error, Line, Problem, Result};
+pub use cpu::*;
+
+// Lit pixels are white, and a last row the program didn't finish stays dark where it stopped.
+pub fn paint(input: &mut dyn BufRead) -> Result<Picture> {
+    let rows = part2::render_program(&parse_program(input)?);
+    let width = rows.iter().map(String::len).max().unwrap_or(0);
+    let mut picture = image::canvas(10, width, rows.len())?;
+
+    for (y, row) in rows.iter().enumerate() {
+        for (x, pixel) in row.bytes().enumerate() {
+            if pixel == b'#' {
+                picture[(x, y)] = Color::WHITE;
+            }
+        }
+    }
+
+    Ok(picture)
+}
+
+pub const SOLVERS: [&dyn Solver; 2] = [&part1::Solution, &part2::Solution];
+pub const REFERENCES: [Reference; 2] = [reference::part1, reference::part2];
@@ -1,26 +1,62 @@
+use crate::solver::{downcast, Answer, Parsed, Solver};
+
 use super::*;
 
-struct SignalStrength {
-    signal_strength: isize,
+// The sum becomes `None` once it's too big to hold.
+pub struct SignalStrength {
+    pub signal_strength: Option<isize>,
 }
 
 impl Peripheral for SignalStrength {
     fn new() -> Self {
-        Self { signal_strength: 0 }
+        Self {
+            signal_strength: Some(0),
+        }
     }
 
     fn update(&mut self, register_x: isize, clock_cycle: usize) {
-        if (clock_cycle + 20) % 40 == 0 {
-            self.signal_strength += register_x * clock_cycle as isize;
+        if (clock_cycle + 20).is_multiple_of(40) {
+            self.signal_strength = self
+                .signal_strength
+                .and_then(|sum| sum.checked_add(register_x.checked_mul(clock_cycle as isize)?));
         }
     }
 }
 
-pub fn calculate_sum_of_signal_strengths() -> TimedResult<isize> {
-    let start_time = Instant::now();
+pub fn calculate_sum_of_signal_strengths<R: BufRead>(reader: R) -> Result<isize> {
+    sum_signal_strengths(&parse_program(reader)?)
+}
 
+pub fn sum_signal_strengths(program: &[Instruction]) -> Result<isize> {
     let mut cpu = CPU::<SignalStrength>::new();
-    cpu.execute_file("inputs/day10.txt")?;
+    cpu.execute_program(program);
+
+    cpu.peripheral.signal_strength.ok_or(Error::puzzle(
+        10,
+        Problem::Overflow("work out the signal strength"),
+    ))
+}
+
+pub struct Solution;
+
+impl Solver for Solution {
+    fn day(&self) -> usize {
+        10
+    }
 
-    Ok((cpu.peripheral.signal_strength, start_time.elapsed()))
+    fn part(&self) -> usize {
+        1
+    }
+
+    fn name(&self) -> &'static str {
+        "day10::part1::calculate_sum_of_signal_strengths"
+    }
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Parsed> {
+        Ok(Box::new(parse_program(input)?))
+    }
+
+    fn solve_parsed(&self, parsed: Parsed) -> Result<Answer> {
+        Ok(sum_signal_strengths(&downcast::<Vec<Instruction>>(parsed)?)?.into())
+    }
 }
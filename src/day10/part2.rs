@@ -1,39 +1,77 @@
-use crate::TimedResult;
+use crate::solver::{downcast, Answer, Parsed, Solver};
 
 use super::*;
 
-struct CRT {
-    current_pixel: usize,
+#[allow(clippy::upper_case_acronyms)]
+pub struct CRT {
+    pub current_pixel: usize,
+    pub rows: Vec<String>,
 }
 
 impl Peripheral for CRT {
     fn new() -> Self {
-        Self { current_pixel: 0 }
+        Self {
+            current_pixel: 0,
+            rows: vec![String::new()],
+        }
     }
 
     fn update(&mut self, register_x: isize, _: usize) {
+        // There's always a row to draw into, because a new one is started as soon as the last one
+        // fills up.
+        let row = self.rows.last_mut().unwrap();
+
         if self.current_pixel as isize >= register_x - 1
             && self.current_pixel as isize <= register_x + 1
         {
-            print!("#");
+            row.push('#');
         } else {
-            print!(".");
+            row.push('.');
         }
 
         self.current_pixel += 1;
 
-        if self.current_pixel % 40 == 0 {
-            print!("\n");
+        if self.current_pixel.is_multiple_of(40) {
+            self.rows.push(String::new());
             self.current_pixel = 0;
         }
     }
 }
 
-pub fn print_crt() -> TimedResult<()> {
-    let start_time = Instant::now();
+pub fn render_crt<R: BufRead>(reader: R) -> Result<Vec<String>> {
+    Ok(render_program(&parse_program(reader)?))
+}
 
+pub fn render_program(program: &[Instruction]) -> Vec<String> {
     let mut cpu = CPU::<CRT>::new();
-    cpu.execute_file("inputs/day10.txt")?;
+    cpu.execute_program(program);
+
+    let mut rows = cpu.peripheral.rows;
+    rows.retain(|row| !row.is_empty());
+
+    rows
+}
+
+pub struct Solution;
+
+impl Solver for Solution {
+    fn day(&self) -> usize {
+        10
+    }
+
+    fn part(&self) -> usize {
+        2
+    }
+
+    fn name(&self) -> &'static str {
+        "day10::part2::render_crt"
+    }
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Parsed> {
+        Ok(Box::new(parse_program(input)?))
+    }
 
-    Ok(((), start_time.elapsed()))
+    fn solve_parsed(&self, parsed: Parsed) -> Result<Answer> {
+        Ok(render_program(&downcast::<Vec<Instruction>>(parsed)?).into())
+    }
 }
@@ -0,0 +1,60 @@
+use crate::solver::Answer;
+
+// The register's value during every cycle, in order.
+fn register_values(input: &str) -> Option<Vec<i64>> {
+    let mut register = 1;
+    let mut values = Vec::new();
+
+    for line in input.lines() {
+        let words = line.split_whitespace().collect::<Vec<&str>>();
+        match words.as_slice() {
+            ["noop"] => values.push(register),
+            ["addx", amount] => {
+                values.push(register);
+                values.push(register);
+                register += amount.parse::<i64>().ok()?;
+            }
+            _ => return None,
+        }
+    }
+
+    Some(values)
+}
+
+// Cycles are counted from 1, and the interesting ones are the 20th and every 40th after that.
+pub fn part1(input: &str) -> Option<Answer> {
+    let mut sum: i64 = 0;
+
+    for (index, register) in register_values(input)?.into_iter().enumerate() {
+        let cycle = index as i64 + 1;
+        if cycle % 40 == 20 {
+            sum = sum.checked_add(cycle.checked_mul(register)?)?;
+        }
+    }
+
+    Some(isize::try_from(sum).ok()?.into())
+}
+
+// Every cycle draws the next pixel along, lit if the three-pixel sprite is over it.
+pub fn part2(input: &str) -> Option<Answer> {
+    let pixels = register_values(input)?
+        .into_iter()
+        .enumerate()
+        .map(|(index, register)| {
+            let column = (index % 40) as i64;
+            if (register - column).abs() <= 1 {
+                '#'
+            } else {
+                '.'
+            }
+        })
+        .collect::<Vec<char>>();
+
+    Some(
+        pixels
+            .chunks(40)
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<String>>()
+            .into(),
+    )
+}
@@ -0,0 +1,108 @@
+use crate::animation::Simulation;
+
+use super::*;
+
+// Each step is one monkey inspecting one item and throwing it on.
+pub struct KeepAway {
+    monkeys: Vec<Monkey>,
+    inspections: Vec<usize>,
+    rounds: usize,
+    divisor: usize,
+    common_multiple: usize,
+    round: usize,
+    monkey: usize,
+    // The last throw, as (worry level, target).
+    thrown: Option<(usize, usize)>,
+    overflowed: bool,
+}
+
+pub fn animate(input: &mut dyn BufRead, part: usize) -> Result<Box<dyn Simulation>> {
+    let monkeys = parse_monkeys(input)?;
+    let (rounds, divisor) = if part == 2 { (10_000, 1) } else { (20, 3) };
+    // `parse_monkeys` already checked that this fits.
+    let common_multiple = monkeys.iter().map(Monkey::modulus).product();
+
+    Ok(Box::new(KeepAway {
+        inspections: vec![0; monkeys.len()],
+        monkeys,
+        rounds,
+        divisor,
+        common_multiple,
+        round: 1,
+        monkey: 0,
+        thrown: None,
+        overflowed: false,
+    }))
+}
+
+impl Simulation for KeepAway {
+    fn step(&mut self) -> bool {
+        if self.overflowed {
+            return false;
+        }
+
+        // Monkeys with nothing to throw just pass their turn.
+        while self.monkeys[self.monkey].items.is_empty() {
+            self.monkey += 1;
+            if self.monkey == self.monkeys.len() {
+                self.monkey = 0;
+                self.round += 1;
+            }
+            if self.round > self.rounds {
+                self.round = self.rounds;
+                self.monkey = self.monkeys.len() - 1;
+                return false;
+            }
+        }
+
+        let monkey = &mut self.monkeys[self.monkey];
+        let item = monkey.items.remove(0);
+        let Some((target, worry_level)) =
+            monkey.inspect_item(item, self.divisor, self.common_multiple)
+        else {
+            self.overflowed = true;
+            return false;
+        };
+
+        self.monkeys[target].items.push(worry_level);
+        self.inspections[self.monkey] += 1;
+        self.thrown = Some((worry_level, target));
+        true
+    }
+
+    // Like the puzzle's summaries after each round: what every monkey's holding, then how busy
+    // they've been.
+    fn draw(&self) -> Vec<String> {
+        let holding = self.monkeys.iter().enumerate().map(|(index, monkey)| {
+            let items = monkey
+                .items
+                .iter()
+                .map(usize::to_string)
+                .collect::<Vec<String>>();
+            format!("Monkey {}: {}", index, items.join(", "))
+        });
+        let inspected = self
+            .inspections
+            .iter()
+            .enumerate()
+            .map(|(index, count)| format!("Monkey {} inspected items {} times.", index, count));
+
+        holding.chain([String::new()]).chain(inspected).collect()
+    }
+
+    fn status(&self) -> String {
+        let status = match self.thrown {
+            Some((worry_level, target)) => format!(
+                "Round {} of {}: Monkey {} threw an item with worry level {} to monkey {}.",
+                self.round, self.rounds, self.monkey, worry_level, target
+            ),
+            None => format!("{} monkeys, {} rounds.", self.monkeys.len(), self.rounds),
+        };
+
+        if self.overflowed {
+            format!("{} Then a worry level got too big to hold.", status)
+        } else {
+            status
+        }
+    }
+}
@@ -1,54 +1,119 @@
-mod monkey;
+pub mod animation;
+pub mod monkey;
 pub mod part1;
 pub mod part2;
+pub mod reference;
 
-use std::error::Error;
-use std::fmt;
-use std::fs::File;
-use std::io::{BufRead, BufReader, Lines};
-use std::time::Instant;
+use std::io::BufRead;
 
-use crate::{BoxedResult, TimedResult};
+use crate::parse::{self, Fields, Record, RecordLines};
+use crate::solver::{Reference, Solver};
+use crate::trace;
+use crate::{Error, Line, Problem, Result, SourceLine};
 
-use monkey::Monkey;
+pub use monkey::Monkey;
 
-pub fn calculate_monkey_business(rounds: usize, divisor: usize) -> TimedResult<usize> {
-    let start_time = Instant::now();
+pub fn calculate_monkey_business<R: BufRead>(
+    reader: R,
+    rounds: usize,
+    divisor: usize,
+) -> Result<usize> {
+    calculate_monkey_business_of(parse_monkeys(reader)?, rounds, divisor)
+}
+
+pub fn parse_monkeys<R: BufRead>(reader: R) -> Result<Vec<Monkey>> {
+    let monkeys = parse::records(reader, 11)
+        .map(|record| Monkey::parse(&record?))
+        .collect::<Result<Vec<Monkey>>>()?;
 
-    let file = File::open("inputs/day11.txt")?;
-    let reader = BufReader::new(file);
-    let mut lines = reader.lines();
+    // The business is the two busiest monkeys' counts multiplied together.
+    match monkeys.as_slice() {
+        [] => return Err(Error::puzzle(11, Problem::Missing("a second monkey"))),
+        [monkey] => {
+            return Err(monkey.test.lines[2]
+                .line()
+                .error_at_end(Problem::Missing("a second monkey")))
+        }
+        _ => (),
+    }
 
-    let mut monkey_infos = Vec::<(Monkey, usize)>::new();
+    for monkey in &monkeys {
+        let test = &monkey.test;
 
-    loop {
-        let monkey = Monkey::parse_from_lines(&mut lines)?;
-        monkey_infos.push((monkey, 0));
-        if let None = lines.next() {
-            break;
+        for (target, line) in [
+            (test.true_target, &test.lines[1]),
+            (test.false_target, &test.lines[2]),
+        ] {
+            if target >= monkeys.len() {
+                return Err(line.error_at_word(5, Problem::NoSuchMonkey(target)));
+            }
         }
     }
 
-    for _ in 0..rounds {
+    // Worry levels are kept modulo the product of every test's modulus, so it has to fit. The
+    // first modulus it doesn't fit with is the one that's blamed.
+    let mut product = 1usize;
+    for monkey in &monkeys {
+        product = product
+            .checked_mul(monkey.modulus())
+            .ok_or_else(|| monkey.test.lines[0].error_at_word(3, Problem::Overflow("multiply")))?;
+    }
+
+    Ok(monkeys)
+}
+
+pub fn calculate_monkey_business_of(
+    monkeys: Vec<Monkey>,
+    rounds: usize,
+    divisor: usize,
+) -> Result<usize> {
+    let mut monkey_infos = monkeys
+        .into_iter()
+        .map(|monkey| (monkey, 0))
+        .collect::<Vec<(Monkey, usize)>>();
+
+    // Every test only cares about the worry level modulo its own modulus, so keeping the levels
+    // modulo the product of all of them stops them from overflowing without changing any throws.
+    // That only works while nothing's divided, though, so otherwise the levels are kept exactly.
+    let common_multiple = monkey_infos
+        .iter()
+        .map(|monkey_info| monkey_info.0.modulus())
+        .product();
+
+    for round in 1..=rounds {
         for monkey_index in 0..monkey_infos.len() {
             let thrown_items = {
                 let monkey_info = &mut monkey_infos[monkey_index];
-                let inspected = monkey_info.0.inspect_own_items(divisor);
+                let inspected = monkey_info
+                    .0
+                    .inspect_own_items(divisor, common_multiple)
+                    .ok_or(Error::puzzle(11, Problem::Overflow("keep track of")))?;
                 monkey_info.1 += inspected.len();
                 inspected
             };
 
             for item in thrown_items.into_iter() {
+                trace::debug!(
+                    11,
+                    "throw",
+                    round = round,
+                    from = monkey_index,
+                    to = item.0,
+                    worry = item.1,
+                );
                 monkey_infos[item.0].0.items.push(item.1);
             }
         }
     }
 
-    monkey_infos.sort_unstable_by(|a, b| a.1.cmp(&b.1));
+    monkey_infos.sort_unstable_by_key(|monkey_info| monkey_info.1);
     let top_two_infos = &monkey_infos[monkey_infos.len() - 2..];
 
-    Ok((
-        top_two_infos[0].1 * top_two_infos[1].1,
-        start_time.elapsed(),
-    ))
+    top_two_infos[0]
+        .1
+        .checked_mul(top_two_infos[1].1)
+        .ok_or(Error::puzzle(11, Problem::Overflow("multiply")))
 }
+
+pub const SOLVERS: [&dyn Solver; 2] = [&part1::Solution, &part2::Solution];
+pub const REFERENCES: [Reference; 2] = [reference::part1, reference::part2];
@@ -3,32 +3,36 @@ use super::*;
 #[derive(Debug)]
 pub struct Monkey {
     pub items: Vec<usize>,
-    operation: MonkeyOperation,
-    test: MonkeyTest,
+    pub operation: MonkeyOperation,
+    pub test: MonkeyTest,
 }
 
 impl Monkey {
-    pub fn parse_from_lines(lines: &mut Lines<BufReader<File>>) -> BoxedResult<Self> {
-        // Skip the "Monkey #:" line.
-        lines.next();
+    pub fn parse(record: &Record) -> Result<Self> {
+        let mut lines = record.lines();
+
+        // The "Monkey #:" line only needs checking, since monkeys are listed in order.
+        parse::labelled(&lines.next_or_missing("'Monkey' line")?, "Monkey")?;
 
         // Starting items: #, #...
-        let starting_items_line = lines.next().ok_or(MonkeyParseError::MissingLine(
-            MissingLineType::StartingItems,
-        ))??;
-        // Skip "  Starting items: ", then split the rest on commas.
-        let item_strs = starting_items_line[18..].split(", ");
-        let items = item_strs
-            .map(|item| item.parse::<usize>())
-            .collect::<Result<Vec<usize>, _>>()?;
+        let line = lines.next_or_missing("'Starting items' line")?;
+        let item_strs = parse::labelled(&line, "Starting items:")?;
+        let items = if item_strs.is_empty() {
+            Vec::new()
+        } else {
+            item_strs
+                .split(',')
+                .map(|item| line.number::<usize>(item.trim()))
+                .collect::<Result<Vec<usize>>>()?
+        };
 
         // Operation: new = ...
-        let operation = MonkeyOperation::try_from(
-            lines
-                .next()
-                .ok_or(MonkeyParseError::MissingLine(MissingLineType::Operation))??,
-        )?;
-        let test = MonkeyTest::parse_from_lines(lines)?;
+        let operation = MonkeyOperation::parse(&lines.next_or_missing("'Operation' line")?)?;
+        let test = MonkeyTest::parse_from_lines(&mut lines)?;
+
+        if let Some(line) = lines.next() {
+            return Err(line.error(Problem::Expected("a blank line between monkeys")));
+        }
 
         Ok(Self {
             items,
@@ -37,60 +41,82 @@ impl Monkey {
         })
     }
 
-    pub fn inspect_own_items(&mut self, divisor: usize) -> Vec<(usize, usize)> {
+    pub fn modulus(&self) -> usize {
+        self.test.modulus
+    }
+
+    // `None` if any of the items' worry levels got too big to hold.
+    pub fn inspect_own_items(
+        &mut self,
+        divisor: usize,
+        common_multiple: usize,
+    ) -> Option<Vec<(usize, usize)>> {
         let out_vec = self
             .items
             .iter()
-            .map(|item| self.inspect_item(*item, divisor))
+            .map(|item| self.inspect_item(*item, divisor, common_multiple))
             .collect();
         self.items.clear();
 
         out_vec
     }
 
-    fn inspect_item(&self, item: usize, divisor: usize) -> (usize, usize) {
-        let mut sides: [usize; 2] = [0, 0];
+    pub fn inspect_item(
+        &self,
+        item: usize,
+        divisor: usize,
+        common_multiple: usize,
+    ) -> Option<(usize, usize)> {
+        // Both sides fit in 64 bits, so doing the maths in 128 means it can't overflow.
+        let mut sides: [u128; 2] = [0, 0];
         match self.operation.left {
-            Value::Old => sides[0] = item,
-            Value::Const(value) => sides[0] = value,
+            Value::Old => sides[0] = item as u128,
+            Value::Const(value) => sides[0] = value as u128,
         }
 
         match self.operation.right {
-            Value::Old => sides[1] = item,
-            Value::Const(value) => sides[1] = value,
+            Value::Old => sides[1] = item as u128,
+            Value::Const(value) => sides[1] = value as u128,
         }
 
         let result = match self.operation.operation {
             MathOperation::Add => sides[0] + sides[1],
             MathOperation::Multiply => sides[0] * sides[1],
-        } / divisor;
+        } / divisor as u128;
 
-        let target = if result % self.test.modulus == 0 {
+        let target = if result.is_multiple_of(self.test.modulus as u128) {
             self.test.true_target
         } else {
             self.test.false_target
         };
 
-        (target, result % 9699690)
+        let worry_level = if divisor == 1 {
+            result % common_multiple as u128
+        } else {
+            result
+        };
+
+        Some((target, usize::try_from(worry_level).ok()?))
     }
 }
 
 #[derive(Debug)]
-struct MonkeyOperation {
-    left: Value,
-    right: Value,
-    operation: MathOperation,
+pub struct MonkeyOperation {
+    pub left: Value,
+    pub right: Value,
+    pub operation: MathOperation,
 }
 
-impl TryFrom<String> for MonkeyOperation {
-    type Error = Box<dyn Error>;
-
-    fn try_from(value: String) -> Result<Self, Self::Error> {
-        // Skip "  Operation: new = ", then split the rest on spaces.
-        let mut operation_strs = value[19..].split_ascii_whitespace();
-        let left = Value::try_from(operation_strs.next().unwrap())?;
-        let operation = MathOperation::try_from(operation_strs.next().unwrap())?;
-        let right = Value::try_from(operation_strs.next().unwrap())?;
+impl MonkeyOperation {
+    pub fn parse(line: &Line) -> Result<Self> {
+        let mut fields = Fields::of(line, parse::labelled(line, "Operation: new =")?);
+        let left = Value::parse(line, fields.next("left-hand value")?)?;
+        let operation = fields.one_of(
+            "operator",
+            &[("+", MathOperation::Add), ("*", MathOperation::Multiply)],
+        )?;
+        let right = Value::parse(line, fields.next("right-hand value")?)?;
+        fields.end()?;
 
         Ok(Self {
             left,
@@ -101,109 +127,61 @@ impl TryFrom<String> for MonkeyOperation {
 }
 
 #[derive(Debug)]
-enum Value {
+pub enum Value {
     Old,
     Const(usize),
 }
 
-impl TryFrom<&str> for Value {
-    type Error = Box<dyn Error>;
-
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
+impl Value {
+    pub fn parse(line: &Line, value: &str) -> Result<Self> {
         if value == "old" {
             Ok(Self::Old)
         } else {
-            Ok(Self::Const(value.parse::<usize>()?))
+            Ok(Self::Const(line.number::<usize>(value)?))
         }
     }
 }
 
-#[derive(Debug)]
-enum MathOperation {
+#[derive(Clone, Debug)]
+pub enum MathOperation {
     Add,
     Multiply,
 }
 
-impl TryFrom<&str> for MathOperation {
-    type Error = Box<dyn Error>;
-
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        match value {
-            "+" => Ok(Self::Add),
-            "*" => Ok(Self::Multiply),
-            _ => Err(MonkeyParseError::InvalidOperation(String::from(value)).into()),
-        }
-    }
-}
-
+// The lines are kept for the errors that can only be found once every monkey's been read, like
+// throwing to a monkey that isn't there.
 #[derive(Debug)]
-struct MonkeyTest {
-    modulus: usize,
-    true_target: usize,
-    false_target: usize,
+pub struct MonkeyTest {
+    pub modulus: usize,
+    pub true_target: usize,
+    pub false_target: usize,
+    pub lines: [SourceLine; 3],
 }
 
 impl MonkeyTest {
-    fn parse_from_lines(lines: &mut Lines<BufReader<File>>) -> BoxedResult<Self> {
-        let modulus_line = lines
-            .next()
-            .ok_or(MonkeyParseError::MissingLine(MissingLineType::Test))??;
-        let modulus = modulus_line[21..].parse::<usize>()?;
-
-        let if_true_line = lines
-            .next()
-            .ok_or(MonkeyParseError::MissingLine(MissingLineType::TestIfTrue))??;
-        let true_target = if_true_line[29..].parse::<usize>()?;
-
-        let if_false_line = lines
-            .next()
-            .ok_or(MonkeyParseError::MissingLine(MissingLineType::TestIfFalse))??;
-        let false_target = if_false_line[30..].parse::<usize>()?;
+    pub fn parse_from_lines(lines: &mut RecordLines) -> Result<Self> {
+        let test_line = lines.next_or_missing("'Test' line")?;
+        let modulus_str = parse::labelled(&test_line, "Test: divisible by")?;
+        let modulus = test_line.number::<usize>(modulus_str)?;
+        if modulus == 0 {
+            return Err(
+                test_line.error_at(modulus_str, Problem::Expected("a divisor other than 0"))
+            );
+        }
+
+        let true_line = lines.next_or_missing("'If true' line")?;
+        let true_target =
+            true_line.number::<usize>(parse::labelled(&true_line, "If true: throw to monkey")?)?;
+
+        let false_line = lines.next_or_missing("'If false' line")?;
+        let false_target = false_line
+            .number::<usize>(parse::labelled(&false_line, "If false: throw to monkey")?)?;
 
         Ok(Self {
             modulus,
             true_target,
             false_target,
+            lines: [&test_line, &true_line, &false_line].map(SourceLine::from),
         })
     }
 }
-
-#[derive(Debug)]
-enum MonkeyParseError {
-    MissingLine(MissingLineType),
-    InvalidOperation(String),
-}
-
-#[derive(Debug)]
-enum MissingLineType {
-    StartingItems,
-    Operation,
-    Test,
-    TestIfTrue,
-    TestIfFalse,
-}
-
-impl fmt::Display for MonkeyParseError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::MissingLine(line_type) => {
-                write!(f, "Missing line '{}' for monkey definition.", line_type)
-            }
-            Self::InvalidOperation(op) => write!(f, "Invalid operation '{}'", op),
-        }
-    }
-}
-
-impl Error for MonkeyParseError {}
-
-impl fmt::Display for MissingLineType {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::StartingItems => write!(f, "Starting items"),
-            Self::Operation => write!(f, "Operation"),
-            Self::Test => write!(f, "Test"),
-            Self::TestIfTrue => write!(f, "If true"),
-            Self::TestIfFalse => write!(f, "If false"),
-        }
-    }
-}
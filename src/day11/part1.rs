@@ -1,5 +1,31 @@
+use crate::solver::{downcast, Answer, Parsed, Solver};
+
 use super::*;
 
-pub fn calculate_calm_monkey_business() -> TimedResult<usize> {
-    calculate_monkey_business(20, 3)
+pub fn calculate_calm_monkey_business<R: BufRead>(reader: R) -> Result<usize> {
+    calculate_monkey_business(reader, 20, 3)
+}
+
+pub struct Solution;
+
+impl Solver for Solution {
+    fn day(&self) -> usize {
+        11
+    }
+
+    fn part(&self) -> usize {
+        1
+    }
+
+    fn name(&self) -> &'static str {
+        "day11::part1::calculate_calm_monkey_business"
+    }
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Parsed> {
+        Ok(Box::new(parse_monkeys(input)?))
+    }
+
+    fn solve_parsed(&self, parsed: Parsed) -> Result<Answer> {
+        Ok(calculate_monkey_business_of(downcast::<Vec<Monkey>>(parsed)?, 20, 3)?.into())
+    }
 }
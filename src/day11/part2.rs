@@ -1,5 +1,31 @@
+use crate::solver::{downcast, Answer, Parsed, Solver};
+
 use super::*;
 
-pub fn calculate_anxious_monkey_business() -> TimedResult<usize> {
-    calculate_monkey_business(10_000, 1)
+pub fn calculate_anxious_monkey_business<R: BufRead>(reader: R) -> Result<usize> {
+    calculate_monkey_business(reader, 10_000, 1)
+}
+
+pub struct Solution;
+
+impl Solver for Solution {
+    fn day(&self) -> usize {
+        11
+    }
+
+    fn part(&self) -> usize {
+        2
+    }
+
+    fn name(&self) -> &'static str {
+        "day11::part2::calculate_anxious_monkey_business"
+    }
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Parsed> {
+        Ok(Box::new(parse_monkeys(input)?))
+    }
+
+    fn solve_parsed(&self, parsed: Parsed) -> Result<Answer> {
+        Ok(calculate_monkey_business_of(downcast::<Vec<Monkey>>(parsed)?, 10_000, 1)?.into())
+    }
 }
@@ -0,0 +1,141 @@
+use crate::solver::Answer;
+
+// An operation's values are `None` where they're the old worry level.
+struct Monkey {
+    items: Vec<u128>,
+    operation: (Option<u128>, char, Option<u128>),
+    divisor: u128,
+    targets: [usize; 2],
+}
+
+impl Monkey {
+    fn apply(&self, old: u128) -> Option<u128> {
+        let (left, operator, right) = self.operation;
+        let (left, right) = (left.unwrap_or(old), right.unwrap_or(old));
+
+        match operator {
+            '+' => left.checked_add(right),
+            _ => left.checked_mul(right),
+        }
+    }
+}
+
+// Each monkey's lines are picked apart by the text after their colons.
+fn parse(input: &str) -> Option<Vec<Monkey>> {
+    let mut monkeys = Vec::new();
+
+    for block in input.trim_end().split("\n\n") {
+        let fields = block
+            .lines()
+            .map(|line| line.split_once(':').map(|(_, value)| value.trim()))
+            .collect::<Option<Vec<&str>>>()?;
+        let last_number = |field: &str| field.split_whitespace().last()?.parse::<usize>().ok();
+
+        let [_, items, operation, test, if_true, if_false] = fields.as_slice() else {
+            return None;
+        };
+
+        monkeys.push(Monkey {
+            items: if items.is_empty() {
+                Vec::new()
+            } else {
+                items
+                    .split(", ")
+                    .map(|item| item.parse().ok())
+                    .collect::<Option<Vec<u128>>>()?
+            },
+            operation: parse_operation(operation.strip_prefix("new = ")?)?,
+            divisor: last_number(test).filter(|divisor| *divisor > 0)? as u128,
+            targets: [last_number(if_true)?, last_number(if_false)?],
+        });
+    }
+
+    Some(monkeys)
+}
+
+fn parse_operation(operation: &str) -> Option<(Option<u128>, char, Option<u128>)> {
+    let value = |word: &str| match word {
+        "old" => Some(None),
+        number => number.parse::<u128>().ok().map(Some),
+    };
+
+    match operation
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .as_slice()
+    {
+        [left, operator @ ("+" | "*"), right] => {
+            Some((value(left)?, operator.chars().next()?, value(right)?))
+        }
+        _ => None,
+    }
+}
+
+fn monkey_business(mut counts: Vec<usize>) -> Option<Answer> {
+    counts.sort();
+    counts.reverse();
+
+    Some(counts.first()?.checked_mul(*counts.get(1)?)?.into())
+}
+
+// Worry levels are kept exactly, and if one ever gets too big to hold there's no answer.
+pub fn part1(input: &str) -> Option<Answer> {
+    let mut monkeys = parse(input)?;
+    let mut counts = vec![0; monkeys.len()];
+
+    for _ in 0..20 {
+        for index in 0..monkeys.len() {
+            for item in std::mem::take(&mut monkeys[index].items) {
+                counts[index] += 1;
+                let monkey = &monkeys[index];
+                let item = monkey.apply(item)? / 3;
+                let target = monkey.targets[usize::from(item % monkey.divisor != 0)];
+                monkeys.get_mut(target)?.items.push(item);
+            }
+        }
+    }
+
+    monkey_business(counts)
+}
+
+// Without the division the levels grow far too quickly to keep, so instead each item is kept as
+// its remainder for every monkey's divisor, which is all the tests need.
+pub fn part2(input: &str) -> Option<Answer> {
+    let mut monkeys = parse(input)?;
+    let mut divisors = monkeys
+        .iter()
+        .map(|monkey| monkey.divisor)
+        .collect::<Vec<u128>>();
+    // Monkeys often share a divisor, and there's no need to keep the same remainder twice.
+    divisors.sort();
+    divisors.dedup();
+    let mut held = monkeys
+        .iter_mut()
+        .map(|monkey| {
+            std::mem::take(&mut monkey.items)
+                .into_iter()
+                .map(|item| divisors.iter().map(|divisor| item % divisor).collect())
+                .collect()
+        })
+        .collect::<Vec<Vec<Vec<u128>>>>();
+    let mut counts = vec![0; monkeys.len()];
+
+    for _ in 0..10_000 {
+        for index in 0..monkeys.len() {
+            for remainders in std::mem::take(&mut held[index]) {
+                counts[index] += 1;
+                let monkey = &monkeys[index];
+                let remainders = remainders
+                    .iter()
+                    .zip(&divisors)
+                    .map(|(remainder, divisor)| Some(monkey.apply(*remainder)? % divisor))
+                    .collect::<Option<Vec<u128>>>()?;
+                let test = divisors.binary_search(&monkey.divisor).ok()?;
+                let target = monkey.targets[usize::from(remainders[test] != 0)];
+                held.get_mut(target)?.push(remainders);
+            }
+        }
+    }
+
+    monkey_business(counts)
+}
@@ -1,20 +1,24 @@
 pub mod part1;
 pub mod part2;
+pub mod reference;
 
 use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashSet};
-use std::error::Error;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::time::Instant;
+use std::collections::BinaryHeap;
+use std::io::BufRead;
 
-const START: u8 = 'S' as u8;
-const END: u8 = 'E' as u8;
-const LOWEST: u8 = 'a' as u8 - 1;
+use crate::grid::{Grid, Position};
+use crate::image::{self, Color, Picture};
+use crate::solver::{Reference, Solver};
+use crate::trace;
+use crate::{Error, Line, Problem, Result};
+
+const START: u8 = b'S';
+const END: u8 = b'E';
+const LOWEST: u8 = b'a' - 1;
 
 #[derive(Debug)]
 struct Dijkstra {
-    position: (usize, usize),
+    position: Position,
     distance: usize,
 }
 
@@ -28,13 +32,7 @@ impl Eq for Dijkstra {}
 
 impl PartialOrd for Dijkstra {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        if self.distance < other.distance {
-            Some(Ordering::Greater)
-        } else if self.distance > other.distance {
-            Some(Ordering::Less)
-        } else {
-            Some(Ordering::Equal)
-        }
+        Some(self.cmp(other))
     }
 }
 
@@ -52,46 +50,51 @@ impl Ord for Dijkstra {
 }
 
 #[derive(Debug)]
-struct Map {
-    heightmap: Vec<Vec<u8>>,
-    start: (usize, usize),
-    end: (usize, usize),
+pub struct Map {
+    pub heightmap: Grid<u8>,
+    pub start: Position,
+    pub end: Position,
 }
 
-impl TryFrom<File> for Map {
-    type Error = Box<dyn Error>;
-
-    fn try_from(value: File) -> Result<Self, Self::Error> {
-        let reader = BufReader::new(value);
-
-        let mut heightmap = Vec::<Vec<u8>>::new();
-        let mut start = (0, 0);
-        let mut end = (0, 0);
-        let mut x = 0;
-        let mut y = 0;
+impl Map {
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self> {
+        let mut start = None;
+        let mut end = None;
+        // Kept so a missing 'S' or 'E' can be blamed on the end of the map.
+        let mut last_row = Vec::<u8>::new();
+
+        let heightmap = Grid::from_reader(
+            reader,
+            12,
+            "a height from 'a' to 'z', 'S' or 'E'",
+            |position, byte| {
+                if position.0 == 0 {
+                    last_row.clear();
+                }
+                last_row.push(byte);
 
-        for line in reader.lines() {
-            let mut row = Vec::<u8>::new();
-            for byte in line?.bytes() {
-                row.push(match byte {
+                match byte {
                     START => {
-                        start = (x, y);
-                        1
+                        start = Some(position);
+                        Some(1)
                     }
                     END => {
-                        end = (x, y);
-                        26
+                        end = Some(position);
+                        Some(26)
                     }
-                    _ => byte - LOWEST,
-                });
-
-                x += 1;
-            }
+                    b'a'..=b'z' => Some(byte - LOWEST),
+                    _ => None,
+                }
+            },
+        )?;
 
-            heightmap.push(row);
-            x = 0;
-            y += 1;
-        }
+        let last_row = String::from_utf8_lossy(&last_row);
+        let missing = |what| match heightmap.height() {
+            0 => Error::puzzle(12, Problem::Missing(what)),
+            height => Line::new(12, height, &last_row).error_at_end(Problem::Missing(what)),
+        };
+        let start = start.ok_or_else(|| missing("the start, 'S'"))?;
+        let end = end.ok_or_else(|| missing("the end, 'E'"))?;
 
         Ok(Self {
             heightmap,
@@ -102,27 +105,43 @@ impl TryFrom<File> for Map {
 }
 
 impl Map {
-    fn find_shortest_path(&self) -> usize {
-        let mut shortest = usize::MAX;
-
-        for y in 0..self.heightmap.len() {
-            for x in 0..self.heightmap[y].len() {
-                if self.heightmap[y][x] == 1 {
-                    if let Some(length) = self.find_shortest_path_from((x, y)) {
-                        if length < shortest {
-                            shortest = length;
-                        }
-                    }
-                }
-            }
+    pub fn find_shortest_path(&self) -> Option<usize> {
+        self.heightmap
+            .positions()
+            .filter(|position| self.heightmap[*position] == 1)
+            .filter_map(|position| self.find_shortest_path_from(position))
+            .min()
+    }
+
+    pub fn find_shortest_path_from(&self, start: Position) -> Option<usize> {
+        self.search(start, None)
+    }
+
+    // Every square along a shortest path from `start` to the end, both included.
+    pub fn find_route_from(&self, start: Position) -> Option<Vec<Position>> {
+        let mut came_from = Grid::filled(self.heightmap.width(), self.heightmap.height(), None);
+        self.search(start, Some(&mut came_from))?;
+
+        let mut route = vec![self.end];
+        while let Some(previous) = came_from[route[route.len() - 1]] {
+            route.push(previous);
         }
+        route.reverse();
 
-        shortest
+        Some(route)
     }
 
-    fn find_shortest_path_from(&self, start: (usize, usize)) -> Option<usize> {
-        let mut visited = HashSet::<(usize, usize)>::new();
+    // When there's somewhere to put them, each square's step back towards `start` is kept too.
+    fn search(
+        &self,
+        start: Position,
+        mut came_from: Option<&mut Grid<Option<Position>>>,
+    ) -> Option<usize> {
+        // The start's marked straight away, so no square can claim to be the step before it.
+        let mut visited = Grid::filled(self.heightmap.width(), self.heightmap.height(), false);
+        visited[start] = true;
         let mut to_visit = BinaryHeap::<Dijkstra>::new();
+        trace::debug!(12, "search", x = start.0, y = start.1);
         let start = Dijkstra {
             position: start,
             distance: 0,
@@ -135,65 +154,53 @@ impl Map {
                 return Some(current.distance);
             }
 
-            let current_height = self.heightmap[current.position.1][current.position.0];
-
-            if current.position.1 > 0 {
-                let up_position = (current.position.0, current.position.1 - 1);
-                if !visited.contains(&up_position) {
-                    let up_height = self.heightmap[up_position.1][up_position.0];
-                    if up_height <= current_height + 1 {
-                        to_visit.push(Dijkstra {
-                            position: up_position,
-                            distance: current.distance + 1,
-                        });
-                        visited.insert(up_position);
+            let current_height = self.heightmap[current.position];
+            trace::debug!(
+                12,
+                "expand",
+                x = current.position.0,
+                y = current.position.1,
+                height = current_height,
+                distance = current.distance,
+            );
+
+            // Climbing is only ever one step up, but any drop is fine.
+            for neighbour in self.heightmap.neighbours(current.position) {
+                if !visited[neighbour] && self.heightmap[neighbour] <= current_height + 1 {
+                    to_visit.push(Dijkstra {
+                        position: neighbour,
+                        distance: current.distance + 1,
+                    });
+                    visited[neighbour] = true;
+                    if let Some(came_from) = came_from.as_deref_mut() {
+                        came_from[neighbour] = Some(current.position);
                     }
                 }
             }
+        }
 
-            if current.position.0 > 0 {
-                let left_position = (current.position.0 - 1, current.position.1);
-                if !visited.contains(&left_position) {
-                    let left_height = self.heightmap[left_position.1][left_position.0];
-                    if left_height <= current_height + 1 {
-                        to_visit.push(Dijkstra {
-                            position: left_position,
-                            distance: current.distance + 1,
-                        });
-                        visited.insert(left_position);
-                    }
-                }
-            }
+        None
+    }
+}
 
-            if current.position.1 < self.heightmap.len() - 1 {
-                let down_position = (current.position.0, current.position.1 + 1);
-                if !visited.contains(&down_position) {
-                    let down_height = self.heightmap[down_position.1][down_position.0];
-                    if down_height <= current_height + 1 {
-                        to_visit.push(Dijkstra {
-                            position: down_position,
-                            distance: current.distance + 1,
-                        });
-                        visited.insert(down_position);
-                    }
-                }
-            }
+// Higher squares are lighter, going from a dark green valley up to snow. The shortest path from the
+// start is red, or missing if there isn't one.
+pub fn paint(input: &mut dyn BufRead) -> Result<Picture> {
+    let map = Map::from_reader(input)?;
+    let mut picture = image::canvas(12, map.heightmap.width(), map.heightmap.height())?;
 
-            if current.position.0 < self.heightmap[0].len() - 1 {
-                let right_position = (current.position.0 + 1, current.position.1);
-                if !visited.contains(&right_position) {
-                    let right_height = self.heightmap[right_position.1][right_position.0];
-                    if right_height <= current_height + 1 {
-                        to_visit.push(Dijkstra {
-                            position: right_position,
-                            distance: current.distance + 1,
-                        });
-                        visited.insert(right_position);
-                    }
-                }
-            }
-        }
+    for position in map.heightmap.positions() {
+        let height = map.heightmap[position] as usize;
+        picture[position] =
+            Color::new(20, 60, 30).towards(Color::new(240, 240, 250), height - 1, 25);
+    }
 
-        None
+    for position in map.find_route_from(map.start).unwrap_or_default() {
+        picture[position] = Color::RED;
     }
+
+    Ok(picture)
 }
+
+pub const SOLVERS: [&dyn Solver; 2] = [&part1::Solution, &part2::Solution];
+pub const REFERENCES: [Reference; 2] = [reference::part1, reference::part2];
@@ -1,27 +1,36 @@
-use std::fmt;
+use crate::solver::{downcast, Answer, Parsed, Solver};
 
 use super::*;
 
-#[derive(Debug)]
-struct NoPathError {}
+pub fn find_best_path_length<R: BufRead>(reader: R) -> Result<usize> {
+    find_path_length_from_start(&Map::from_reader(reader)?)
+}
 
-impl fmt::Display for NoPathError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Couldn't find a path!")
-    }
+pub fn find_path_length_from_start(map: &Map) -> Result<usize> {
+    map.find_shortest_path_from(map.start)
+        .ok_or(Error::puzzle(12, Problem::NoPath))
 }
 
-impl Error for NoPathError {}
+pub struct Solution;
 
-pub fn find_best_path_length() -> crate::TimedResult<usize> {
-    let start_time = Instant::now();
+impl Solver for Solution {
+    fn day(&self) -> usize {
+        12
+    }
 
-    let file = File::open("inputs/day12.txt")?;
-    let map = Map::try_from(file)?;
+    fn part(&self) -> usize {
+        1
+    }
 
-    Ok((
-        map.find_shortest_path_from(map.start)
-            .ok_or(NoPathError {})?,
-        start_time.elapsed(),
-    ))
+    fn name(&self) -> &'static str {
+        "day12::part1::find_best_path_length"
+    }
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Parsed> {
+        Ok(Box::new(Map::from_reader(input)?))
+    }
+
+    fn solve_parsed(&self, parsed: Parsed) -> Result<Answer> {
+        Ok(find_path_length_from_start(&downcast::<Map>(parsed)?)?.into())
+    }
 }
@@ -1,10 +1,36 @@
+use crate::solver::{downcast, Answer, Parsed, Solver};
+
 use super::*;
 
-pub fn find_most_scenic_path_length() -> crate::TimedResult<usize> {
-    let start_time = Instant::now();
+pub fn find_most_scenic_path_length<R: BufRead>(reader: R) -> Result<usize> {
+    find_shortest_path_length(&Map::from_reader(reader)?)
+}
+
+pub fn find_shortest_path_length(map: &Map) -> Result<usize> {
+    map.find_shortest_path()
+        .ok_or(Error::puzzle(12, Problem::NoPath))
+}
+
+pub struct Solution;
+
+impl Solver for Solution {
+    fn day(&self) -> usize {
+        12
+    }
+
+    fn part(&self) -> usize {
+        2
+    }
+
+    fn name(&self) -> &'static str {
+        "day12::part2::find_most_scenic_path_length"
+    }
 
-    let file = File::open("inputs/day12.txt")?;
-    let map = Map::try_from(file)?;
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Parsed> {
+        Ok(Box::new(Map::from_reader(input)?))
+    }
 
-    Ok((map.find_shortest_path(), start_time.elapsed()))
+    fn solve_parsed(&self, parsed: Parsed) -> Result<Answer> {
+        Ok(find_shortest_path_length(&downcast::<Map>(parsed)?)?.into())
+    }
 }
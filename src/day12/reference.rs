@@ -0,0 +1,88 @@
+use std::collections::VecDeque;
+
+use crate::solver::Answer;
+
+fn elevation(square: u8) -> u8 {
+    match square {
+        b'S' => b'a',
+        b'E' => b'z',
+        _ => square,
+    }
+}
+
+// A plain breadth-first search from one square, which reaches every square at its shortest
+// distance without any need for a priority queue.
+fn steps_to_end(map: &[&[u8]], start: (usize, usize)) -> Option<usize> {
+    let mut distances = vec![vec![None; map[0].len()]; map.len()];
+    let mut queue = VecDeque::from([start]);
+    distances[start.1][start.0] = Some(0);
+
+    while let Some((x, y)) = queue.pop_front() {
+        let distance = distances[y][x]?;
+        if map[y][x] == b'E' {
+            return Some(distance);
+        }
+
+        let neighbours = [
+            (x.wrapping_sub(1), y),
+            (x + 1, y),
+            (x, y.wrapping_sub(1)),
+            (x, y + 1),
+        ];
+        for (next_x, next_y) in neighbours {
+            let Some(next) = map.get(next_y).and_then(|row| row.get(next_x)) else {
+                continue;
+            };
+            if elevation(*next) <= elevation(map[y][x]) + 1 && distances[next_y][next_x].is_none() {
+                distances[next_y][next_x] = Some(distance + 1);
+                queue.push_back((next_x, next_y));
+            }
+        }
+    }
+
+    None
+}
+
+fn parse(input: &str) -> Option<Vec<&[u8]>> {
+    let map = input.lines().map(str::as_bytes).collect::<Vec<&[u8]>>();
+    if map.is_empty() || map.iter().any(|row| row.len() != map[0].len()) {
+        return None;
+    }
+
+    Some(map)
+}
+
+// Every square at the lowest elevation, counting the start.
+fn squares_at(map: &[&[u8]], squares: &[u8]) -> Vec<(usize, usize)> {
+    let mut found = Vec::new();
+
+    for (y, row) in map.iter().enumerate() {
+        for (x, square) in row.iter().enumerate() {
+            if squares.contains(square) {
+                found.push((x, y));
+            }
+        }
+    }
+
+    found
+}
+
+pub fn part1(input: &str) -> Option<Answer> {
+    let map = parse(input)?;
+    let start = *squares_at(&map, b"S").first()?;
+
+    Some(steps_to_end(&map, start)?.into())
+}
+
+// Tries every possible starting square separately and keeps the best.
+pub fn part2(input: &str) -> Option<Answer> {
+    let map = parse(input)?;
+
+    Some(
+        squares_at(&map, b"Sa")
+            .into_iter()
+            .filter_map(|start| steps_to_end(&map, start))
+            .min()?
+            .into(),
+    )
+}
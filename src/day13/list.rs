@@ -1,43 +1,16 @@
 use std::cmp::Ordering;
-use std::error::Error;
 use std::fmt;
-use std::iter::Peekable;
-use std::str::Bytes;
 
-const OPEN_BRACKET: u8 = '[' as u8;
-const CLOSE_BRACKET: u8 = ']' as u8;
-const COMMA: u8 = ',' as u8;
-const DIGIT_START: u8 = '0' as u8;
-const DIGIT_END: u8 = '9' as u8;
+use crate::parse::Cursor;
+use crate::trace;
+use crate::{Line, Problem, Result};
 
-#[derive(Debug)]
-pub enum ListParseError {
-    ExpectedDigit(u8),
-    ExpectedList(u8),
-    ExpectedListItem(u8),
-    ExpectedEndOfList(u8),
-}
-
-type ListParseResult<T> = Result<T, ListParseError>;
-
-impl fmt::Display for ListParseError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::ExpectedDigit(found) => {
-                write!(f, "Expected a digit, found '{}'.", *found as char)
-            }
-            Self::ExpectedList(found) => write!(f, "Expected '[', found '{}'.", *found as char),
-            Self::ExpectedListItem(found) => {
-                write!(f, "Expected integer or list, found '{}'.", *found as char)
-            }
-            Self::ExpectedEndOfList(found) => {
-                write!(f, "Expected ']', found '{}'.", *found as char)
-            }
-        }
-    }
-}
-
-impl Error for ListParseError {}
+const OPEN_BRACKET: u8 = b'[';
+const CLOSE_BRACKET: u8 = b']';
+const COMMA: u8 = b',';
+// Parsing and comparing both recurse into nested lists, so this keeps a pathological packet from
+// blowing the stack.
+const MAX_DEPTH: usize = 500;
 
 #[derive(Debug)]
 pub struct List {
@@ -50,6 +23,29 @@ pub enum ListItem {
     Integer(usize),
 }
 
+// Written back out the same way it appears in the input.
+impl fmt::Display for List {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[")?;
+        for (index, item) in self.items.iter().enumerate() {
+            if index > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}", item)?;
+        }
+        write!(f, "]")
+    }
+}
+
+impl fmt::Display for ListItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::List(list) => write!(f, "{}", list),
+            Self::Integer(value) => write!(f, "{}", value),
+        }
+    }
+}
+
 impl From<&usize> for List {
     fn from(value: &usize) -> Self {
         Self {
@@ -94,11 +90,7 @@ impl PartialEq for List {
             }
         }
 
-        if self.items.len() == other.items.len() {
-            true
-        } else {
-            false
-        }
+        self.items.len() == other.items.len()
     }
 }
 
@@ -106,6 +98,12 @@ impl Eq for List {}
 
 impl PartialOrd for List {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for List {
+    fn cmp(&self, other: &Self) -> Ordering {
         let min_length = usize::min(self.items.len(), other.items.len());
 
         for i in 0..min_length {
@@ -116,137 +114,124 @@ impl PartialOrd for List {
                 ListItem::List(left_value) => match right_item {
                     ListItem::List(right_value) => {
                         if left_value.ne(right_value) {
-                            return left_value.partial_cmp(right_value);
+                            return left_value.cmp(right_value);
                         }
                     }
                     ListItem::Integer(right_value) => {
                         let right_list = List::from(right_value);
                         if left_value.ne(&right_list) {
-                            return left_value.partial_cmp(&right_list);
+                            return left_value.cmp(&right_list);
                         }
                     }
                 },
                 ListItem::Integer(left_value) => match right_item {
                     ListItem::List(right_value) => {
                         let left_list = List::from(left_value);
-                        if left_list.ne(&right_value) {
-                            return left_list.partial_cmp(&right_value);
+                        if left_list.ne(right_value) {
+                            return left_list.cmp(right_value);
                         }
                     }
                     ListItem::Integer(right_value) => {
                         if left_value != right_value {
-                            return left_value.partial_cmp(right_value);
+                            return left_value.cmp(right_value);
                         }
                     }
                 },
             }
         }
 
-        if self.items.len() < other.items.len() {
-            Some(Ordering::Less)
-        } else if self.items.len() > other.items.len() {
-            Some(Ordering::Greater)
-        } else {
-            Some(Ordering::Equal)
-        }
+        self.items.len().cmp(&other.items.len())
     }
 }
 
-impl Ord for List {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap()
+impl List {
+    // The list has to take up the whole line.
+    pub fn parse(line: &Line) -> Result<Self> {
+        let mut parser = ListParser {
+            cursor: Cursor::new(line),
+            depth: 0,
+        };
+        let list = parser.parse_list()?;
+
+        if parser.cursor.peek().is_some() {
+            return Err(parser.cursor.expected("the end of the line"));
+        }
+        trace::trace!(13, "packet", line = line.number, list = list);
+
+        Ok(list)
     }
 }
 
-impl List {
-    pub fn parse(bytes: &mut Peekable<Bytes>) -> ListParseResult<Self> {
-        // println!("Parsing list...");
-        let start_of_list = bytes.next().ok_or(ListParseError::ExpectedList(0))?;
-        // println!("Got start of list: {}", start_of_list as char);
-        if start_of_list != OPEN_BRACKET {
-            return Err(ListParseError::ExpectedList(start_of_list));
+struct ListParser<'a, 'b> {
+    cursor: Cursor<'a, 'b>,
+    depth: usize,
+}
+
+impl ListParser<'_, '_> {
+    fn parse_list(&mut self) -> Result<List> {
+        if self.cursor.peek() != Some(OPEN_BRACKET) {
+            return Err(self.cursor.expected("'['"));
+        }
+
+        if self.depth == MAX_DEPTH {
+            return Err(self
+                .cursor
+                .error_at_position(1, Problem::NestedTooDeeply(MAX_DEPTH)));
         }
 
+        self.depth += 1;
+        trace::trace!(
+            13,
+            "list",
+            column = self.cursor.position() + 1,
+            depth = self.depth,
+        );
+        let list = self.parse_list_contents();
+        self.depth -= 1;
+
+        list
+    }
+
+    fn parse_list_contents(&mut self) -> Result<List> {
+        self.cursor.advance();
+
         let mut items = Vec::<ListItem>::new();
 
-        let next = *(bytes.peek().ok_or(ListParseError::ExpectedEndOfList(0))?);
-        // println!("Peeking at: {}", next as char);
-        match next {
-            CLOSE_BRACKET => {
-                bytes.next();
-                return Ok(Self { items });
+        match self.cursor.peek() {
+            Some(CLOSE_BRACKET) => {
+                self.cursor.advance();
+                return Ok(List { items });
             }
-            OPEN_BRACKET => {
-                items.push(Self::parse_list_item(bytes)?);
+            Some(OPEN_BRACKET) => {
+                items.push(self.parse_list_item()?);
             }
-            val => {
-                if Self::is_digit(val) {
-                    items.push(Self::parse_list_item(bytes)?);
-                } else {
-                    return Err(ListParseError::ExpectedListItem(val));
-                }
+            Some(val) if val.is_ascii_digit() => {
+                items.push(self.parse_list_item()?);
             }
+            Some(_) => return Err(self.cursor.expected("an integer or a list")),
+            None => return Err(self.cursor.expected("']'")),
         }
 
-        while let Some(next_byte) = bytes.peek() {
-            // println!("Peeking at: {}", *next_byte as char);
-            if *next_byte == COMMA {
-                bytes.next();
-                items.push(Self::parse_list_item(bytes)?);
-            } else {
-                break;
-            }
+        while self.cursor.peek() == Some(COMMA) {
+            self.cursor.advance();
+            items.push(self.parse_list_item()?);
         }
 
-        let end_of_list = bytes.next().ok_or(ListParseError::ExpectedEndOfList(0))?;
-        if end_of_list != CLOSE_BRACKET {
-            return Err(ListParseError::ExpectedEndOfList(end_of_list));
+        if self.cursor.peek() != Some(CLOSE_BRACKET) {
+            return Err(self.cursor.expected("']'"));
         }
+        self.cursor.advance();
 
-        Ok(Self { items })
-    }
-
-    fn parse_list_item(bytes: &mut Peekable<Bytes>) -> ListParseResult<ListItem> {
-        // println!("Parsing list item...");
-        let next_byte = *(bytes.peek().ok_or(ListParseError::ExpectedListItem(0))?);
-        // println!("Peeking at: {}", next_byte as char);
-        if Self::is_digit(next_byte) {
-            Ok(ListItem::Integer(Self::parse_integer(bytes)?))
-        } else if next_byte == OPEN_BRACKET {
-            Ok(ListItem::List(Self::parse(bytes)?))
-        } else {
-            Err(ListParseError::ExpectedListItem(next_byte))
-        }
+        Ok(List { items })
     }
 
-    fn parse_integer(bytes: &mut Peekable<Bytes>) -> ListParseResult<usize> {
-        // println!("Parsing integer...");
-        let mut value = Self::parse_digit(bytes)?;
-        // println!("Current value: {}", value);
-        while let Some(next_byte) = bytes.peek() {
-            // println!("Peeking at: {}", *next_byte as char);
-            if Self::is_digit(*next_byte) {
-                value *= 10;
-                value += Self::parse_digit(bytes)?;
-            } else {
-                break;
+    fn parse_list_item(&mut self) -> Result<ListItem> {
+        match self.cursor.peek() {
+            Some(next_byte) if next_byte.is_ascii_digit() => {
+                Ok(ListItem::Integer(self.cursor.number("a digit")?))
             }
+            Some(OPEN_BRACKET) => Ok(ListItem::List(self.parse_list()?)),
+            _ => Err(self.cursor.expected("an integer or a list")),
         }
-
-        Ok(value)
-    }
-
-    fn parse_digit(bytes: &mut Peekable<Bytes>) -> Result<usize, ListParseError> {
-        let next_byte = bytes.next().ok_or(ListParseError::ExpectedDigit(0))?;
-
-        if Self::is_digit(next_byte) {
-            Ok((next_byte - DIGIT_START) as usize)
-        } else {
-            Err(ListParseError::ExpectedDigit(next_byte))
-        }
-    }
-
-    fn is_digit(byte: u8) -> bool {
-        byte >= DIGIT_START && byte <= DIGIT_END
     }
 }
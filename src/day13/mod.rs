@@ -1,12 +1,17 @@
-mod list;
+pub mod list;
 pub mod part1;
 pub mod part2;
+pub mod reference;
+pub mod streaming;
 
-use std::error::Error;
-use std::fmt;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::BufRead;
 
-use crate::BoxedResult;
+use crate::parse;
+use crate::solver::{Reference, Solver, Streaming};
+use crate::{Error, Problem, Result};
 
-use list::*;
+pub use list::*;
+
+pub const SOLVERS: [&dyn Solver; 2] = [&part1::Solution, &part2::Solution];
+pub const REFERENCES: [Reference; 2] = [reference::part1, reference::part2];
+pub const STREAMING: [&Streaming; 2] = [&streaming::PART1, &streaming::PART2];
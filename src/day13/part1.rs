@@ -1,71 +1,50 @@
-use super::*;
-
-#[derive(Debug)]
-enum ListSetParseError {
-    MissingRightSide,
-}
+use crate::solver::{downcast, Answer, Parsed, Solver};
 
-impl fmt::Display for ListSetParseError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::MissingRightSide => write!(f, "Missing right side list."),
-        }
-    }
-}
-
-impl Error for ListSetParseError {}
+use super::*;
 
 #[derive(Debug)]
-struct ListPair {
-    left: List,
-    right: List,
+pub struct ListPair {
+    pub left: List,
+    pub right: List,
 }
 
 impl ListPair {
-    fn is_ordered(&self) -> bool {
+    pub fn is_ordered(&self) -> bool {
         self.left < self.right
     }
 }
 
 #[derive(Debug)]
-struct ListSet {
-    pairs: Vec<ListPair>,
+pub struct ListSet {
+    pub pairs: Vec<ListPair>,
 }
 
-impl TryFrom<File> for ListSet {
-    type Error = Box<dyn Error>;
-
-    fn try_from(value: File) -> Result<Self, Self::Error> {
-        let reader = BufReader::new(value);
-        let mut lines = reader.lines();
-
+impl ListSet {
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self> {
         let mut pairs = Vec::<ListPair>::new();
 
-        while let Some(left) = lines.next() {
-            let left = left?;
-            let mut left_bytes = left.bytes().peekable();
-            let right = lines.next().ok_or(ListSetParseError::MissingRightSide)??;
-            let mut right_bytes = right.bytes().peekable();
-
-            let left_list = List::parse(&mut left_bytes)?;
-            let right_list = List::parse(&mut right_bytes)?;
+        for record in parse::records(reader, 13) {
+            let record = record?;
+            let mut lines = record.lines();
+            let left = List::parse(&lines.next_or_missing("left side list")?)?;
+            let right = List::parse(&lines.next_or_missing("right side list")?)?;
 
-            pairs.push(ListPair {
-                left: left_list,
-                right: right_list,
-            });
+            if let Some(line) = lines.next() {
+                return Err(line.error(Problem::Expected("a blank line between pairs")));
+            }
 
-            // Read out the blank line between.
-            lines.next();
+            pairs.push(ListPair { left, right });
         }
 
         Ok(Self { pairs })
     }
 }
 
-pub fn find_pairs_in_correct_order() -> BoxedResult<usize> {
-    let list_set: ListSet = File::open("inputs/day13.txt")?.try_into()?;
+pub fn find_pairs_in_correct_order<R: BufRead>(reader: R) -> Result<usize> {
+    Ok(sum_indices_of_ordered_pairs(&ListSet::from_reader(reader)?))
+}
 
+pub fn sum_indices_of_ordered_pairs(list_set: &ListSet) -> usize {
     let mut answer: usize = 0;
     for i in 0..list_set.pairs.len() {
         if list_set.pairs[i].is_ordered() {
@@ -73,5 +52,29 @@ pub fn find_pairs_in_correct_order() -> BoxedResult<usize> {
         }
     }
 
-    Ok(answer)
+    answer
+}
+
+pub struct Solution;
+
+impl Solver for Solution {
+    fn day(&self) -> usize {
+        13
+    }
+
+    fn part(&self) -> usize {
+        1
+    }
+
+    fn name(&self) -> &'static str {
+        "day13::part1::find_pairs_in_correct_order"
+    }
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Parsed> {
+        Ok(Box::new(ListSet::from_reader(input)?))
+    }
+
+    fn solve_parsed(&self, parsed: Parsed) -> Result<Answer> {
+        Ok(sum_indices_of_ordered_pairs(&downcast::<ListSet>(parsed)?).into())
+    }
 }
@@ -1,46 +1,78 @@
+use crate::solver::{downcast, Answer, Parsed, Solver};
+
 use super::*;
 
-const FIRST_DIVIDER: &'static str = "[[2]]";
-const SECOND_DIVIDER: &'static str = "[[6]]";
+pub const FIRST_DIVIDER: usize = 2;
+pub const SECOND_DIVIDER: usize = 6;
+
+pub fn locate_divider_packets<R: BufRead>(reader: R) -> Result<usize> {
+    Ok(locate_divider_packets_among(parse_packets(reader)?))
+}
+
+pub fn parse_packets<R: BufRead>(reader: R) -> Result<Vec<List>> {
+    let mut packets = Vec::<List>::new();
+
+    // Which pair a packet was in doesn't matter here, so the blank lines are just skipped.
+    for record in parse::records(reader, 13) {
+        for line in record?.lines() {
+            packets.push(List::parse(&line)?);
+        }
+    }
 
-#[derive(Debug)]
-struct MissingLine {}
+    Ok(packets)
+}
 
-impl fmt::Display for MissingLine {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Missing a line!.")
+// A divider packet is a single integer wrapped in two lists, like [[2]].
+pub fn divider(value: usize) -> List {
+    List {
+        items: vec![ListItem::List(List::from(&value))],
     }
 }
 
-impl Error for MissingLine {}
+pub fn locate_divider_packets_among(packets: Vec<List>) -> usize {
+    let first_divider_list = divider(FIRST_DIVIDER);
+    let second_divider_list = divider(SECOND_DIVIDER);
 
-pub fn locate_divider_packets() -> BoxedResult<usize> {
-    let first_divider_list = List::parse(&mut FIRST_DIVIDER.bytes().peekable())?;
-    let second_divider_list = List::parse(&mut SECOND_DIVIDER.bytes().peekable())?;
+    let mut lists = packets;
+    lists.push(first_divider_list);
+    lists.push(second_divider_list);
 
-    let file = File::open("inputs/day13.txt")?;
-    let reader = BufReader::new(file);
+    lists.sort();
+    let first_divider_list = divider(FIRST_DIVIDER);
+    let second_divider_list = divider(SECOND_DIVIDER);
 
-    let mut lists = Vec::<List>::from([first_divider_list, second_divider_list]);
+    // The sort is stable, so a divider lands after any packets in the input that are identical to
+    // it, and only the divider's own position counts.
+    let position = |divider_list: &List| {
+        lists
+            .iter()
+            .rposition(|list| list == divider_list)
+            .map_or(0, |index| index + 1)
+    };
 
-    for line in reader.lines() {
-        let line = line?;
-        if !line.is_empty() {
-            let mut bytes = line.bytes().peekable();
-            lists.push(List::parse(&mut bytes)?);
-        }
+    position(&first_divider_list) * position(&second_divider_list)
+}
+
+pub struct Solution;
+
+impl Solver for Solution {
+    fn day(&self) -> usize {
+        13
     }
 
-    lists.sort();
-    let first_divider_list = List::parse(&mut FIRST_DIVIDER.bytes().peekable())?;
-    let second_divider_list = List::parse(&mut SECOND_DIVIDER.bytes().peekable())?;
+    fn part(&self) -> usize {
+        2
+    }
 
-    let mut result: usize = 1;
-    for i in 0..lists.len() {
-        if lists[i] == first_divider_list || lists[i] == second_divider_list {
-            result *= i + 1;
-        }
+    fn name(&self) -> &'static str {
+        "day13::part2::locate_divider_packets"
+    }
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Parsed> {
+        Ok(Box::new(parse_packets(input)?))
     }
 
-    Ok(result)
+    fn solve_parsed(&self, parsed: Parsed) -> Result<Answer> {
+        Ok(locate_divider_packets_among(downcast::<Vec<List>>(parsed)?).into())
+    }
 }
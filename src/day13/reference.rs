@@ -0,0 +1,114 @@
+use std::cmp::Ordering;
+
+use crate::solver::Answer;
+
+enum Packet {
+    Integer(usize),
+    List(Vec<Packet>),
+}
+
+// Parses one value starting at `position`, leaving `position` just past it.
+fn parse_value(text: &[u8], position: &mut usize) -> Option<Packet> {
+    if *text.get(*position)? == b'[' {
+        *position += 1;
+        let mut items = Vec::new();
+
+        if *text.get(*position)? == b']' {
+            *position += 1;
+            return Some(Packet::List(items));
+        }
+
+        loop {
+            items.push(parse_value(text, position)?);
+            match text.get(*position)? {
+                b',' => *position += 1,
+                b']' => {
+                    *position += 1;
+                    return Some(Packet::List(items));
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    let start = *position;
+    while text.get(*position).is_some_and(u8::is_ascii_digit) {
+        *position += 1;
+    }
+
+    std::str::from_utf8(&text[start..*position])
+        .ok()?
+        .parse()
+        .ok()
+        .map(Packet::Integer)
+}
+
+fn parse_packet(line: &str) -> Option<Packet> {
+    let mut position = 0;
+    let packet = parse_value(line.as_bytes(), &mut position)?;
+
+    (position == line.len()).then_some(packet)
+}
+
+// Straight from the puzzle's rules: integers compare as numbers, lists item by item and then by
+// length, and an integer next to a list gets wrapped in a list of its own.
+fn compare(left: &Packet, right: &Packet) -> Ordering {
+    match (left, right) {
+        (Packet::Integer(left), Packet::Integer(right)) => left.cmp(right),
+        (Packet::List(left), Packet::List(right)) => {
+            for (left_item, right_item) in left.iter().zip(right) {
+                let order = compare(left_item, right_item);
+                if order != Ordering::Equal {
+                    return order;
+                }
+            }
+            left.len().cmp(&right.len())
+        }
+        (Packet::Integer(left), Packet::List(_)) => {
+            compare(&Packet::List(vec![Packet::Integer(*left)]), right)
+        }
+        (Packet::List(_), Packet::Integer(right)) => {
+            compare(left, &Packet::List(vec![Packet::Integer(*right)]))
+        }
+    }
+}
+
+fn packets(input: &str) -> Option<Vec<Packet>> {
+    input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(parse_packet)
+        .collect()
+}
+
+pub fn part1(input: &str) -> Option<Answer> {
+    let packets = packets(input)?;
+    let mut sum = 0;
+
+    for (index, pair) in packets.chunks(2).enumerate() {
+        if let [left, right] = pair {
+            if compare(left, right) == Ordering::Less {
+                sum += index + 1;
+            }
+        }
+    }
+
+    Some(sum.into())
+}
+
+// No sorting needed: each divider's position is one more than the number of packets that would
+// come before it. Packets identical to a divider go before it.
+pub fn part2(input: &str) -> Option<Answer> {
+    let packets = packets(input)?;
+    let position = |divider: usize, dividers_before: usize| {
+        let divider = Packet::List(vec![Packet::List(vec![Packet::Integer(divider)])]);
+        packets
+            .iter()
+            .filter(|packet| compare(packet, &divider) != Ordering::Greater)
+            .count()
+            + dividers_before
+            + 1
+    };
+
+    Some((position(2, 0) * position(6, 1)).into())
+}
@@ -0,0 +1,106 @@
+use crate::solver::{Answer, Streaming};
+
+use super::*;
+
+// Both parts hold at most two packets at once, so they need memory for the longest pair of lines,
+// however many packets there are. A packet takes more room parsed than written out, up to 48 bytes
+// for each character of its line.
+pub const PART1: Streaming = Streaming {
+    day: 13,
+    part: 1,
+    name: "day13::streaming::find_pairs_in_correct_order",
+    memory: "the current pair of packets, up to 48 bytes for each character",
+    stream: stream_pairs_in_correct_order,
+};
+
+pub const PART2: Streaming = Streaming {
+    day: 13,
+    part: 2,
+    name: "day13::streaming::locate_divider_packets",
+    memory: "the current packet, up to 48 bytes for each character",
+    stream: stream_divider_packets,
+};
+
+fn stream_pairs_in_correct_order(input: &mut dyn BufRead) -> Result<Answer> {
+    Ok(find_pairs_in_correct_order(input)?.into())
+}
+
+fn stream_divider_packets(input: &mut dyn BufRead) -> Result<Answer> {
+    Ok(locate_divider_packets(input)?.into())
+}
+
+// What the next line of the input has to be.
+enum Expecting {
+    Left,
+    Right(List),
+    BlankLine,
+}
+
+// Goes through the pairs a line at a time, with the same rules as `ListSet::from_reader`, but
+// without holding on to any pair once it's been compared.
+pub fn find_pairs_in_correct_order<R: BufRead>(reader: R) -> Result<usize> {
+    let mut expecting = Expecting::Left;
+    let mut index: usize = 0;
+    let mut answer: usize = 0;
+
+    parse::for_each_line(reader, 13, |line| {
+        let blank = line.text.trim().is_empty();
+
+        expecting = match (std::mem::replace(&mut expecting, Expecting::Left), blank) {
+            (Expecting::Left, false) => Expecting::Right(List::parse(&line)?),
+            (Expecting::Right(left), false) => {
+                index += 1;
+                if left < List::parse(&line)? {
+                    answer += index;
+                }
+                Expecting::BlankLine
+            }
+            (Expecting::BlankLine, false) => {
+                return Err(line.error(Problem::Expected("a blank line between pairs")))
+            }
+            (Expecting::BlankLine, true) => Expecting::Left,
+            // A blank line where a pair should start leaves that pair empty.
+            (Expecting::Left, true) => {
+                return Err(Error::puzzle(13, Problem::Missing("left side list")))
+            }
+            (Expecting::Right(_), true) => {
+                return Err(Error::puzzle(13, Problem::Missing("right side list")))
+            }
+        };
+
+        Ok(())
+    })?;
+
+    match expecting {
+        Expecting::Right(_) => Err(Error::puzzle(13, Problem::Missing("right side list"))),
+        _ => Ok(answer),
+    }
+}
+
+// Sorting every packet with the dividers would only be to find where the dividers end up, which is
+// just after every packet that sorts before them. The dividers go after any identical packets.
+pub fn locate_divider_packets<R: BufRead>(reader: R) -> Result<usize> {
+    let first_divider = part2::divider(part2::FIRST_DIVIDER);
+    let second_divider = part2::divider(part2::SECOND_DIVIDER);
+    let mut before_first: usize = 0;
+    let mut before_second: usize = 0;
+
+    parse::for_each_line(reader, 13, |line| {
+        if line.text.trim().is_empty() {
+            return Ok(());
+        }
+
+        let packet = List::parse(&line)?;
+        if packet <= first_divider {
+            before_first += 1;
+        }
+        if packet <= second_divider {
+            before_second += 1;
+        }
+
+        Ok(())
+    })?;
+
+    // The second divider comes after the first one, as well as the packets.
+    Ok((before_first + 1) * (before_second + 2))
+}
@@ -1,2 +1,8 @@
 pub mod part1;
 pub mod part2;
+pub mod reference;
+
+use crate::solver::{Reference, Solver};
+
+pub const SOLVERS: [&dyn Solver; 2] = [&part1::Solution, &part2::Solution];
+pub const REFERENCES: [Reference; 2] = [reference::part1, reference::part2];
@@ -1,58 +1,64 @@
-use std::fs;
-use std::io;
 use std::io::BufRead;
 
-#[derive(Clone)]
-enum Choice {
+use crate::parse::{self, Fields};
+use crate::solver::{downcast, Answer, Parsed, Solver};
+use crate::trace;
+use crate::Result;
+
+#[derive(Clone, Debug)]
+pub enum Choice {
     Rock,
     Paper,
     Scissors,
 }
 
-struct Game {
-    opponent_choice: Choice,
-    player_choice: Choice,
+pub struct Game {
+    pub opponent_choice: Choice,
+    pub player_choice: Choice,
 }
 
-pub fn calculate_score_of_strategy_guide() -> io::Result<usize> {
-    let games = parse_input("inputs/day2.txt")?;
+pub fn calculate_score_of_strategy_guide<R: BufRead>(reader: R) -> Result<usize> {
+    let games = parse_input(reader)?;
     let total_score = calculate_score_of_all_games(&games);
 
     Ok(total_score)
 }
 
-fn parse_input(path: &str) -> io::Result<Vec<Game>> {
-    let file = fs::File::open(path)?;
-    let reader = io::BufReader::new(file);
-
-    let mut games: Vec<Game> = vec![];
-    for line_result in reader.lines() {
-        let line = line_result?;
-        // println!("Line: {}", line);
-        let parts: Vec<&str> = line.split(' ').collect();
-        // println!("Parts: {:?}", parts);
-
-        let opponent_choice = match parts[0] {
-            "A" => Choice::Rock,
-            "B" => Choice::Paper,
-            "C" => Choice::Scissors,
-            _ => panic!("Unrecognized opponent choice!"),
-        };
-
-        let player_choice = match parts[1] {
-            "X" => get_losing_option(&opponent_choice),
-            "Y" => opponent_choice.clone(),
-            "Z" => get_winning_option(&opponent_choice),
-            _ => panic!("Unrecognized player choice!"),
-        };
-
-        games.push(Game {
+pub fn parse_input<R: BufRead>(reader: R) -> Result<Vec<Game>> {
+    parse::lines(reader, 2, |line| {
+        let mut fields = Fields::new(&line);
+        let opponent_choice = fields.one_of(
+            "opponent choice",
+            &[
+                ("A", Choice::Rock),
+                ("B", Choice::Paper),
+                ("C", Choice::Scissors),
+            ],
+        )?;
+        // The second column says how the round has to end, which picks the choice.
+        let strategy = fields.one_of(
+            "player choice",
+            &[
+                ("X", get_losing_option as fn(&Choice) -> Choice),
+                ("Y", Choice::clone),
+                ("Z", get_winning_option),
+            ],
+        )?;
+        let player_choice = strategy(&opponent_choice);
+        fields.end()?;
+        trace::trace!(
+            2,
+            "round",
+            line = line.number,
+            opponent = format!("{:?}", opponent_choice),
+            player = format!("{:?}", player_choice),
+        );
+
+        Ok(Game {
             opponent_choice,
             player_choice,
-        });
-    }
-
-    Ok(games)
+        })
+    })
 }
 
 fn get_winning_option(choice: &Choice) -> Choice {
@@ -71,14 +77,11 @@ fn get_losing_option(choice: &Choice) -> Choice {
     }
 }
 
-fn calculate_score_of_all_games(games: &[Game]) -> usize {
-    games
-        .iter()
-        .map(|game| calculate_score_of_one_game(game))
-        .sum()
+pub fn calculate_score_of_all_games(games: &[Game]) -> usize {
+    games.iter().map(calculate_score_of_one_game).sum()
 }
 
-fn calculate_score_of_one_game(game: &Game) -> usize {
+pub fn calculate_score_of_one_game(game: &Game) -> usize {
     let choice_score = match game.player_choice {
         Choice::Rock => 1,
         Choice::Paper => 2,
@@ -105,3 +108,27 @@ fn calculate_score_of_one_game(game: &Game) -> usize {
 
     choice_score + win_score
 }
+
+pub struct Solution;
+
+impl Solver for Solution {
+    fn day(&self) -> usize {
+        2
+    }
+
+    fn part(&self) -> usize {
+        2
+    }
+
+    fn name(&self) -> &'static str {
+        "day2::part2::calculate_score_of_strategy_guide"
+    }
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Parsed> {
+        Ok(Box::new(parse_input(input)?))
+    }
+
+    fn solve_parsed(&self, parsed: Parsed) -> Result<Answer> {
+        Ok(calculate_score_of_all_games(&downcast::<Vec<Game>>(parsed)?).into())
+    }
+}
@@ -0,0 +1,45 @@
+use crate::solver::Answer;
+
+// Every possible line and what it scores, read off the puzzle by hand.
+const PART1_SCORES: [(&str, usize); 9] = [
+    ("A X", 1 + 3),
+    ("A Y", 2 + 6),
+    ("A Z", 3),
+    ("B X", 1),
+    ("B Y", 2 + 3),
+    ("B Z", 3 + 6),
+    ("C X", 1 + 6),
+    ("C Y", 2),
+    ("C Z", 3 + 3),
+];
+
+const PART2_SCORES: [(&str, usize); 9] = [
+    ("A X", 3),
+    ("A Y", 1 + 3),
+    ("A Z", 2 + 6),
+    ("B X", 1),
+    ("B Y", 2 + 3),
+    ("B Z", 3 + 6),
+    ("C X", 2),
+    ("C Y", 3 + 3),
+    ("C Z", 1 + 6),
+];
+
+fn total_score(input: &str, scores: &[(&str, usize)]) -> Option<Answer> {
+    let mut total = 0;
+
+    for line in input.lines() {
+        let (_, score) = scores.iter().find(|(round, _)| *round == line.trim())?;
+        total += score;
+    }
+
+    Some(total.into())
+}
+
+pub fn part1(input: &str) -> Option<Answer> {
+    total_score(input, &PART1_SCORES)
+}
+
+pub fn part2(input: &str) -> Option<Answer> {
+    total_score(input, &PART2_SCORES)
+}
@@ -1,44 +1,39 @@
 pub mod part1;
 pub mod part2;
+pub mod reference;
 
-use std::error;
-use std::fmt;
+use std::io::BufRead;
 
-type Result<T> = std::result::Result<T, Box<dyn error::Error>>;
+use crate::parse;
+use crate::solver::{Reference, Solver};
+use crate::{Line, Problem, Result};
 
-#[derive(Debug)]
-struct PriorityError(u8);
+const LITTLE_A: u8 = b'a';
+const LITTLE_Z: u8 = b'z';
+const BIG_A: u8 = b'A';
+const BIG_Z: u8 = b'Z';
 
-impl fmt::Display for PriorityError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Unrecognized character {}", self.0)
-    }
+pub fn parse_rucksacks<R: BufRead>(reader: R) -> Result<Vec<String>> {
+    parse::lines(reader, 3, |line| Ok(String::from(line.text)))
 }
 
-impl error::Error for PriorityError {}
-
-#[derive(Debug)]
-struct BadCommonItems(usize);
-
-impl fmt::Display for BadCommonItems {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Bad number of common items. Expected 1, got {}", self.0)
+pub fn get_priority_of_item(item: u8) -> Option<usize> {
+    if (LITTLE_A..=LITTLE_Z).contains(&item) {
+        Some((item - LITTLE_A + 1) as usize)
+    } else if (BIG_A..=BIG_Z).contains(&item) {
+        Some((item - BIG_A + 27) as usize)
+    } else {
+        None
     }
 }
 
-impl error::Error for BadCommonItems {}
-
-const LITTLE_A: u8 = 'a' as u8;
-const LITTLE_Z: u8 = 'z' as u8;
-const BIG_A: u8 = 'A' as u8;
-const BIG_Z: u8 = 'Z' as u8;
-
-fn get_priority_of_item(item: u8) -> Result<usize> {
-    if item >= LITTLE_A && item <= LITTLE_Z {
-        Ok((item - LITTLE_A + 1) as usize)
-    } else if item >= BIG_A && item <= BIG_Z {
-        Ok((item - BIG_A + 27) as usize)
-    } else {
-        Err(PriorityError(item).into())
-    }
+// Like `get_priority_of_item`, but blames the item's first appearance in the rucksack on `line`.
+fn get_priority_of_item_on(line: &Line, item: u8) -> Result<usize> {
+    get_priority_of_item(item).ok_or_else(|| {
+        let offset = line.text.bytes().position(|byte| byte == item).unwrap_or(0);
+        line.error_at_offset(offset, 1, Problem::Unrecognized("item"))
+    })
 }
+
+pub const SOLVERS: [&dyn Solver; 2] = [&part1::Solution, &part2::Solution];
+pub const REFERENCES: [Reference; 2] = [reference::part1, reference::part2];
@@ -1,32 +1,61 @@
 use std::collections::HashSet;
-use std::fs;
-use std::io::{self, BufRead};
+use std::io::BufRead;
+
+use crate::solver::{downcast, Answer, Parsed, Solver};
 
 use super::*;
 
-pub fn get_priority_of_incorrect_items() -> Result<usize> {
-    let file = fs::File::open("inputs/day3.txt")?;
-    let reader = io::BufReader::new(file);
+pub fn get_priority_of_incorrect_items<R: BufRead>(reader: R) -> Result<usize> {
+    sum_priorities_of_incorrect_items(&parse_rucksacks(reader)?)
+}
 
+pub fn sum_priorities_of_incorrect_items(rucksacks: &[String]) -> Result<usize> {
     let mut priority_sum: usize = 0;
-    for line in reader.lines() {
-        let line = line?;
-        let compartment_size = line.len() / 2;
+    for (index, rucksack) in rucksacks.iter().enumerate() {
+        let line = Line::new(3, index + 1, rucksack);
+        let compartment_size = rucksack.len() / 2;
+        // Items are single bytes, so splitting the bytes works even when the middle of the line
+        // isn't a character boundary.
+        let (first_half, second_half) = rucksack.as_bytes().split_at(compartment_size);
 
-        let first_compartment = HashSet::<u8>::from_iter((&line[0..compartment_size]).bytes());
-        let second_compartment = HashSet::<u8>::from_iter((&line[compartment_size..]).bytes());
+        let first_compartment = HashSet::<u8>::from_iter(first_half.iter().copied());
+        let second_compartment = HashSet::<u8>::from_iter(second_half.iter().copied());
 
         let mut unique_items = first_compartment;
         unique_items.retain(|item| second_compartment.contains(item));
 
         if unique_items.len() != 1 {
-            return Err(BadCommonItems(unique_items.len()).into());
+            return Err(line.error(Problem::BadCommonItems(unique_items.len())));
         }
 
         for item in unique_items {
-            priority_sum += get_priority_of_item(item)?;
+            priority_sum += get_priority_of_item_on(&line, item)?;
         }
     }
 
     Ok(priority_sum)
 }
+
+pub struct Solution;
+
+impl Solver for Solution {
+    fn day(&self) -> usize {
+        3
+    }
+
+    fn part(&self) -> usize {
+        1
+    }
+
+    fn name(&self) -> &'static str {
+        "day3::part1::get_priority_of_incorrect_items"
+    }
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Parsed> {
+        Ok(Box::new(parse_rucksacks(input)?))
+    }
+
+    fn solve_parsed(&self, parsed: Parsed) -> Result<Answer> {
+        Ok(sum_priorities_of_incorrect_items(&downcast::<Vec<String>>(parsed)?)?.into())
+    }
+}
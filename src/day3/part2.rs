@@ -1,58 +1,79 @@
 use std::collections::HashSet;
-use std::fmt;
-use std::fs;
-use std::io::{self, BufRead};
+use std::io::BufRead;
 
-use super::*;
+use crate::solver::{downcast, Answer, Parsed, Solver};
+use crate::Error;
 
-#[derive(Debug)]
-struct WrongNumberOfRucksacks;
+use super::*;
 
-impl fmt::Display for WrongNumberOfRucksacks {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Number of rucksacks was not a multiple of three!")
-    }
+pub fn get_priority_of_team_badges<R: BufRead>(reader: R) -> Result<usize> {
+    sum_priorities_of_team_badges(&parse_rucksacks(reader)?)
 }
 
-impl error::Error for WrongNumberOfRucksacks {}
-
-pub fn get_priority_of_team_badges() -> Result<usize> {
-    let file = fs::File::open("inputs/day3.txt")?;
-    let reader = io::BufReader::new(file);
-    let mut lines = reader.lines();
+pub fn sum_priorities_of_team_badges(rucksacks: &[String]) -> Result<usize> {
+    let mut lines = rucksacks.iter().enumerate();
+    // A team that's short of rucksacks is short after the last one.
+    let wrong_number = || match rucksacks.last() {
+        Some(last) => {
+            Line::new(3, rucksacks.len(), last).error_at_end(Problem::WrongNumberOfRucksacks)
+        }
+        None => Error::puzzle(3, Problem::WrongNumberOfRucksacks),
+    };
 
     let mut priority_sum: usize = 0;
 
-    loop {
-        let first_rucksack = if let Some(i) = lines.next() {
-            HashSet::<u8>::from_iter(i?.bytes())
-        } else {
-            break;
-        };
+    while let Some((index, i)) = lines.next() {
+        let first_rucksack = HashSet::<u8>::from_iter(i.bytes());
+        // Problems with the team get pinned on its first rucksack.
+        let line = Line::new(3, index + 1, i);
 
-        let second_rucksack = if let Some(i) = lines.next() {
-            Ok(HashSet::<u8>::from_iter(i?.bytes()))
+        let second_rucksack = if let Some((_, i)) = lines.next() {
+            Ok(HashSet::<u8>::from_iter(i.bytes()))
         } else {
-            Err(WrongNumberOfRucksacks)
+            Err(wrong_number())
         }?;
 
-        let third_rucksack = if let Some(i) = lines.next() {
-            Ok(HashSet::<u8>::from_iter(i?.bytes()))
+        let third_rucksack = if let Some((_, i)) = lines.next() {
+            Ok(HashSet::<u8>::from_iter(i.bytes()))
         } else {
-            Err(WrongNumberOfRucksacks)
+            Err(wrong_number())
         }?;
 
         let mut common_items = first_rucksack;
         common_items.retain(|item| second_rucksack.contains(item) && third_rucksack.contains(item));
 
         if common_items.len() != 1 {
-            return Err(BadCommonItems(common_items.len()).into());
+            return Err(line.error(Problem::BadCommonItems(common_items.len())));
         }
 
         for item in common_items {
-            priority_sum += get_priority_of_item(item)?;
+            priority_sum += get_priority_of_item_on(&line, item)?;
         }
     }
 
     Ok(priority_sum)
 }
+
+pub struct Solution;
+
+impl Solver for Solution {
+    fn day(&self) -> usize {
+        3
+    }
+
+    fn part(&self) -> usize {
+        2
+    }
+
+    fn name(&self) -> &'static str {
+        "day3::part2::get_priority_of_team_badges"
+    }
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Parsed> {
+        Ok(Box::new(parse_rucksacks(input)?))
+    }
+
+    fn solve_parsed(&self, parsed: Parsed) -> Result<Answer> {
+        Ok(sum_priorities_of_team_badges(&downcast::<Vec<String>>(parsed)?)?.into())
+    }
+}
@@ -0,0 +1,48 @@
+use crate::solver::Answer;
+
+const ITEMS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+fn priority(item: char) -> Option<usize> {
+    Some(ITEMS.find(item)? + 1)
+}
+
+// The only item in the first list that's in all the others, if there's exactly one.
+fn only_common_item(first: &str, others: &[&str]) -> Option<char> {
+    let mut common = first
+        .chars()
+        .filter(|item| others.iter().all(|other| other.contains(*item)))
+        .collect::<Vec<char>>();
+    common.sort();
+    common.dedup();
+
+    match common.as_slice() {
+        [item] => Some(*item),
+        _ => None,
+    }
+}
+
+pub fn part1(input: &str) -> Option<Answer> {
+    let mut total = 0;
+
+    for line in input.lines() {
+        let (left, right) = line.split_at_checked(line.len() / 2)?;
+        total += priority(only_common_item(left, &[right])?)?;
+    }
+
+    Some(total.into())
+}
+
+pub fn part2(input: &str) -> Option<Answer> {
+    let lines = input.lines().collect::<Vec<&str>>();
+    if lines.len() % 3 != 0 {
+        return None;
+    }
+
+    let mut total = 0;
+
+    for group in lines.chunks(3) {
+        total += priority(only_common_item(group[0], &group[1..])?)?;
+    }
+
+    Some(total.into())
+}
@@ -1,63 +1,56 @@
 pub mod part1;
 pub mod part2;
+pub mod reference;
 
-use std::error;
-use std::fmt;
+use std::io::BufRead;
 
-type Result<T> = std::result::Result<T, Box<dyn error::Error>>;
+use crate::parse;
+use crate::solver::{Reference, Solver};
+use crate::trace;
+use crate::{Line, Result};
 
-#[derive(Debug)]
-enum ParsingError {
-    Pair,
-    Assignment,
+pub fn parse_pairs<R: BufRead>(reader: R) -> Result<Vec<Pair>> {
+    parse::lines(reader, 4, |line| Pair::new(&line))
 }
 
-impl fmt::Display for ParsingError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
-            ParsingError::Pair => write!(f, "Invalid pair."),
-            ParsingError::Assignment => write!(f, "Invalid assignment."),
-        }
-    }
-}
-
-impl error::Error for ParsingError {}
-
-struct Pair(Assignment, Assignment);
+pub struct Pair(pub Assignment, pub Assignment);
 
 impl Pair {
-    fn new(line: &str) -> Result<Self> {
-        let raw_assignments = line.split(',').collect::<Vec<&str>>();
-
-        if raw_assignments.len() != 2 {
-            return Err(ParsingError::Pair.into());
-        }
+    pub fn new(line: &Line) -> Result<Self> {
+        let (first, second) =
+            parse::split_pair(line, line.text, ',', "two assignments separated by a comma")?;
 
         Ok(Self(
-            Assignment::new(raw_assignments[0])?,
-            Assignment::new(raw_assignments[1])?,
+            Assignment::new(line, first)?,
+            Assignment::new(line, second)?,
         ))
     }
 }
 
-struct Assignment {
-    start: usize,
-    end: usize,
+pub struct Assignment {
+    pub start: usize,
+    pub end: usize,
 }
 
 impl Assignment {
-    fn new(part: &str) -> Result<Self> {
-        // println!("Got assignment {}", part);
-
-        let parts = part.split('-').collect::<Vec<&str>>();
-
-        if parts.len() != 2 {
-            return Err(ParsingError::Assignment.into());
-        }
-
-        Ok(Self {
-            start: parts[0].parse::<usize>()?,
-            end: parts[1].parse::<usize>()?,
-        })
+    // `part` is the slice of `line` holding just this assignment.
+    pub fn new(line: &Line, part: &str) -> Result<Self> {
+        let (start, end) = parse::split_pair(line, part, '-', "a range like '2-4'")?;
+        let assignment = Self {
+            start: line.number(start)?,
+            end: line.number(end)?,
+        };
+        trace::trace!(
+            4,
+            "assignment",
+            line = line.number,
+            start = assignment.start,
+            end = assignment.end,
+        );
+
+        Ok(assignment)
     }
 }
+
+pub const SOLVERS: [&dyn Solver; 2] = [&part1::Solution, &part2::Solution];
+pub const REFERENCES: [Reference; 2] = [reference::part1, reference::part2];
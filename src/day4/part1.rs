@@ -1,26 +1,52 @@
-use std::fs;
-use std::io::{self, BufRead};
+use std::io::BufRead;
+
+use crate::solver::{downcast, Answer, Parsed, Solver};
 
 use super::*;
 
 impl Pair {
-    fn is_subset(&self) -> bool {
+    pub fn is_subset(&self) -> bool {
         (self.0.start >= self.1.start && self.0.end <= self.1.end)
             || (self.1.start >= self.0.start && self.1.end <= self.0.end)
     }
 }
 
-pub fn find_subsets() -> Result<usize> {
-    let file = fs::File::open("inputs/day4.txt")?;
-    let reader = io::BufReader::new(file);
+pub fn find_subsets<R: BufRead>(reader: R) -> Result<usize> {
+    Ok(count_subsets(&parse_pairs(reader)?))
+}
 
+pub fn count_subsets(pairs: &[Pair]) -> usize {
     let mut subsets = 0;
 
-    for line in reader.lines() {
-        if Pair::new(&line?)?.is_subset() {
+    for pair in pairs {
+        if pair.is_subset() {
             subsets += 1;
         }
     }
 
-    Ok(subsets)
+    subsets
+}
+
+pub struct Solution;
+
+impl Solver for Solution {
+    fn day(&self) -> usize {
+        4
+    }
+
+    fn part(&self) -> usize {
+        1
+    }
+
+    fn name(&self) -> &'static str {
+        "day4::part1::find_subsets"
+    }
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Parsed> {
+        Ok(Box::new(parse_pairs(input)?))
+    }
+
+    fn solve_parsed(&self, parsed: Parsed) -> Result<Answer> {
+        Ok(count_subsets(&downcast::<Vec<Pair>>(parsed)?).into())
+    }
 }
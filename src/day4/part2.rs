@@ -1,10 +1,11 @@
-use std::fs;
-use std::io::{self, BufRead};
+use std::io::BufRead;
+
+use crate::solver::{downcast, Answer, Parsed, Solver};
 
 use super::*;
 
 impl Pair {
-    fn overlaps(&self) -> bool {
+    pub fn overlaps(&self) -> bool {
         (self.0.start >= self.1.start && self.0.start <= self.1.end)
             || (self.0.end >= self.1.start && self.0.end <= self.1.end)
             || (self.1.start >= self.0.start && self.1.start <= self.0.end)
@@ -12,17 +13,42 @@ impl Pair {
     }
 }
 
-pub fn find_overlaps() -> Result<usize> {
-    let file = fs::File::open("inputs/day4.txt")?;
-    let reader = io::BufReader::new(file);
+pub fn find_overlaps<R: BufRead>(reader: R) -> Result<usize> {
+    Ok(count_overlaps(&parse_pairs(reader)?))
+}
 
+pub fn count_overlaps(pairs: &[Pair]) -> usize {
     let mut overlaps: usize = 0;
 
-    for line in reader.lines() {
-        if Pair::new(&line?)?.overlaps() {
+    for pair in pairs {
+        if pair.overlaps() {
             overlaps += 1;
         }
     }
 
-    Ok(overlaps)
+    overlaps
+}
+
+pub struct Solution;
+
+impl Solver for Solution {
+    fn day(&self) -> usize {
+        4
+    }
+
+    fn part(&self) -> usize {
+        2
+    }
+
+    fn name(&self) -> &'static str {
+        "day4::part2::find_overlaps"
+    }
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Parsed> {
+        Ok(Box::new(parse_pairs(input)?))
+    }
+
+    fn solve_parsed(&self, parsed: Parsed) -> Result<Answer> {
+        Ok(count_overlaps(&downcast::<Vec<Pair>>(parsed)?).into())
+    }
 }
@@ -0,0 +1,40 @@
+use std::collections::BTreeSet;
+
+use crate::solver::Answer;
+
+// Both elves' assignments as the sets of sections they cover.
+fn assignments(line: &str) -> Option<(BTreeSet<usize>, BTreeSet<usize>)> {
+    let sections = |assignment: &str| {
+        let (start, end) = assignment.split_once('-')?;
+        Some((start.parse::<usize>().ok()?..=end.parse::<usize>().ok()?).collect())
+    };
+    let (first, second) = line.split_once(',')?;
+
+    Some((sections(first)?, sections(second)?))
+}
+
+fn count_pairs(
+    input: &str,
+    counts: fn(&BTreeSet<usize>, &BTreeSet<usize>) -> bool,
+) -> Option<Answer> {
+    let mut count: usize = 0;
+
+    for line in input.lines() {
+        let (first, second) = assignments(line)?;
+        if counts(&first, &second) {
+            count += 1;
+        }
+    }
+
+    Some(count.into())
+}
+
+pub fn part1(input: &str) -> Option<Answer> {
+    count_pairs(input, |first, second| {
+        first.is_subset(second) || second.is_subset(first)
+    })
+}
+
+pub fn part2(input: &str) -> Option<Answer> {
+    count_pairs(input, |first, second| !first.is_disjoint(second))
+}
@@ -0,0 +1,79 @@
+use crate::animation::Simulation;
+
+use super::*;
+
+// The crane does one move per step.
+pub struct Rearrangement {
+    unload_space: UnloadSpace,
+    moves: Vec<Move>,
+    done: usize,
+    crane: usize,
+    problem: Option<Error>,
+}
+
+pub fn animate(input: &mut dyn BufRead, part: usize) -> Result<Box<dyn Simulation>> {
+    let procedure = parse_procedure(input)?;
+
+    Ok(Box::new(Rearrangement {
+        unload_space: procedure.unload_space,
+        moves: procedure.moves,
+        done: 0,
+        crane: if part == 2 { 9001 } else { 9000 },
+        problem: None,
+    }))
+}
+
+impl Simulation for Rearrangement {
+    fn step(&mut self) -> bool {
+        let Some(move_to_do) = self.moves.get(self.done) else {
+            return false;
+        };
+        if self.problem.is_some() {
+            return false;
+        }
+
+        let result = if self.crane == 9001 {
+            self.unload_space.do_move_9001(move_to_do)
+        } else {
+            self.unload_space.do_move(move_to_do)
+        };
+
+        match result {
+            Ok(()) => {
+                self.done += 1;
+                true
+            }
+            Err(error) => {
+                self.problem = Some(error);
+                false
+            }
+        }
+    }
+
+    fn draw(&self) -> Vec<String> {
+        self.unload_space.draw()
+    }
+
+    fn status(&self) -> String {
+        let status = match self.done.checked_sub(1).map(|last| &self.moves[last]) {
+            Some(last) => format!(
+                "Move {} of {}: move {} from {} to {}",
+                self.done,
+                self.moves.len(),
+                last.quantity,
+                last.from + 1,
+                last.to + 1
+            ),
+            None => format!(
+                "{} moves for the CrateMover {}",
+                self.moves.len(),
+                self.crane
+            ),
+        };
+
+        match &self.problem {
+            Some(problem) => format!("{}. Stopped: {}", status, problem),
+            None => status,
+        }
+    }
+}
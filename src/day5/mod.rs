@@ -1,97 +1,202 @@
-use std::error;
 use std::fmt;
+use std::io::BufRead;
 
-use crate::{BoxedResult, TimedResult};
+use crate::parse::{self, Fields, Record};
+use crate::solver::{Reference, Solver};
+use crate::trace;
+use crate::{Error, Line, Problem, Result, SourceLine};
 
+pub mod animation;
 pub mod part1;
 pub mod part2;
+pub mod reference;
 
-const STACK_COUNT: usize = 9;
+pub struct Move {
+    pub quantity: usize,
+    pub from: usize,
+    pub to: usize,
+    pub line: SourceLine,
+}
+
+impl Move {
+    pub fn parse(line: &Line) -> Result<Self> {
+        let mut fields = Fields::new(line);
+
+        fields.literal("move")?;
+        let quantity = fields.number("a number")?;
+        fields.literal("from")?;
+        let from = Self::stack_index(line, fields.next("a number")?)?;
+        fields.literal("to")?;
+        let to = Self::stack_index(line, fields.next("a number")?)?;
+        fields.end()?;
+
+        Ok(Self {
+            quantity,
+            from,
+            to,
+            line: SourceLine::from(line),
+        })
+    }
 
-#[derive(Debug)]
-enum MoveError {
-    ParseError,
-    BadFromStack(usize),
-    BadToStack(usize),
-    NotEnoughCrates(usize),
+    // Whether the stacks are there, and the one moved from has enough crates, is only known once
+    // it's this move's turn, so it's checked then. The errors point at the number that's wrong, and
+    // number the stacks from 1 like the input does.
+    pub fn check(&self, stacks: &[Stack]) -> Result<()> {
+        if self.from >= stacks.len() {
+            return Err(self
+                .line
+                .error_at_word(3, Problem::NoSuchStack(self.from + 1)));
+        }
+
+        if self.to >= stacks.len() {
+            return Err(self
+                .line
+                .error_at_word(5, Problem::NoSuchStack(self.to + 1)));
+        }
+
+        if stacks[self.from].len() < self.quantity {
+            return Err(self
+                .line
+                .error_at_word(1, Problem::NotEnoughCrates(self.from + 1)));
+        }
+
+        Ok(())
+    }
+
+    // Stacks are numbered from 1 in the input, but indexed from 0.
+    fn stack_index(line: &Line, value: &str) -> Result<usize> {
+        let number = line.number::<usize>(value)?;
+
+        number
+            .checked_sub(1)
+            .ok_or_else(|| line.error_at(value, Problem::NoSuchStack(number)))
+    }
 }
 
-impl fmt::Display for MoveError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
-            Self::ParseError => write!(f, "Couldn't parse move."),
-            Self::BadFromStack(stack_index) => write!(f, "Bad from stack: {}", stack_index),
-            Self::BadToStack(stack_index) => write!(f, "Bad to stack: {}", stack_index),
-            Self::NotEnoughCrates(stack_index) => {
-                write!(f, "Not enough crates in stack {}", stack_index)
-            }
+// The starting drawing and every move in the rearrangement procedure that follows it.
+pub struct Procedure {
+    pub unload_space: UnloadSpace,
+    pub moves: Vec<Move>,
+}
+
+// The drawing is the first group of lines, and every line after the blank one is a move.
+pub fn parse_procedure<R: BufRead>(reader: R) -> Result<Procedure> {
+    let mut records = parse::records(reader, 5);
+    let drawing = records.next().transpose()?.unwrap_or_default();
+    let unload_space = UnloadSpace::parse(&drawing)?;
+    let mut moves = Vec::<Move>::new();
+
+    for record in records {
+        for line in record?.lines() {
+            moves.push(Move::parse(&line)?);
         }
     }
+
+    Ok(Procedure {
+        unload_space,
+        moves,
+    })
 }
 
-impl error::Error for MoveError {}
+pub type Stack = Vec<char>;
 
-struct Move {
-    quantity: usize,
-    from: usize,
-    to: usize,
+pub struct UnloadSpace {
+    pub stacks: Vec<Stack>,
 }
 
-impl Move {
-    fn from_str(line: &str) -> BoxedResult<Self> {
-        let mut line_parts = line.split_whitespace();
-
-        let mut quantity: usize = 0;
-        let mut from = 0;
-        let mut to = 0;
-
-        while let Some(part) = line_parts.next() {
-            if part == "move" {
-                quantity = if let Some(i) = line_parts.next() {
-                    Ok(i.parse::<usize>()?)
-                } else {
-                    Err(MoveError::ParseError)
-                }?;
-            } else if part == "from" {
-                from = if let Some(i) = line_parts.next() {
-                    Ok(i.parse::<usize>()? - 1)
-                } else {
-                    Err(MoveError::ParseError)
-                }?;
-            } else if part == "to" {
-                to = if let Some(i) = line_parts.next() {
-                    Ok(i.parse::<usize>()? - 1)
-                } else {
-                    Err(MoveError::ParseError)
-                }?;
-            } else {
-                return Err(MoveError::ParseError.into());
+// Each stack on its own line, bottom crate first, like "2: [M] [C] [D]".
+impl fmt::Display for UnloadSpace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, stack) in self.stacks.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}:", index + 1)?;
+            for label in stack {
+                write!(f, " [{}]", label)?;
             }
         }
 
-        Ok(Self { quantity, from, to })
+        Ok(())
     }
 }
 
-type Stack = Vec<char>;
-struct UnloadSpace {
-    stacks: [Stack; STACK_COUNT],
-}
-
 impl UnloadSpace {
-    fn new() -> Self {
-        Self {
-            stacks: [
-                vec!['J', 'H', 'P', 'M', 'S', 'F', 'N', 'V'],
-                vec!['S', 'R', 'L', 'M', 'J', 'D', 'Q'],
-                vec!['N', 'Q', 'D', 'H', 'C', 'S', 'W', 'B'],
-                vec!['R', 'S', 'C', 'L'],
-                vec!['M', 'V', 'T', 'P', 'F', 'B'],
-                vec!['T', 'R', 'Q', 'N', 'C'],
-                vec!['G', 'V', 'R'],
-                vec!['C', 'Z', 'S', 'P', 'D', 'L', 'R'],
-                vec!['D', 'S', 'J', 'V', 'G', 'P', 'B', 'F'],
-            ],
+    // The drawing is read top to bottom, but the stacks are built bottom to top, so the rows get
+    // replayed in reverse. The last row of the drawing is just the stack numbers, which is how we
+    // know how many stacks there are.
+    pub fn parse(drawing: &Record) -> Result<Self> {
+        let mut rows = drawing.lines().map(|line| line.text).collect::<Vec<&str>>();
+
+        let stack_numbers = rows
+            .pop()
+            .ok_or_else(|| Error::puzzle(5, Problem::Missing("the drawing's stack numbers")))?;
+        let mut stacks = vec![Stack::new(); stack_numbers.split_whitespace().count()];
+
+        for row in rows.iter().rev() {
+            let row = row.as_bytes();
+
+            for (index, stack) in stacks.iter_mut().enumerate() {
+                // Every crate takes up four columns, "[X] ", so its label is the second one.
+                if let Some(label) = row.get(index * 4 + 1) {
+                    if *label != b' ' {
+                        stack.push(*label as char);
+                    }
+                }
+            }
         }
+
+        trace::trace!(
+            5,
+            "drawing",
+            stacks = stacks.len(),
+            crates = stacks.iter().map(Vec::len).sum::<usize>(),
+        );
+
+        Ok(Self { stacks })
+    }
+
+    // Drawn like the puzzle's own drawing, with the top crates first and the stack numbers last.
+    pub fn draw(&self) -> Vec<String> {
+        let height = self.stacks.iter().map(Vec::len).max().unwrap_or(0);
+        let mut rows = (0..height)
+            .rev()
+            .map(|level| {
+                self.stacks
+                    .iter()
+                    .map(|stack| match stack.get(level) {
+                        Some(label) => format!("[{}]", label),
+                        None => String::from("   "),
+                    })
+                    .collect::<Vec<String>>()
+                    .join(" ")
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<String>>();
+
+        rows.push(
+            (1..=self.stacks.len())
+                .map(|number| format!(" {} ", number))
+                .collect::<Vec<String>>()
+                .join(" "),
+        );
+
+        rows
+    }
+
+    pub fn get_tops_of_stacks(&self) -> String {
+        let mut result = String::with_capacity(self.stacks.len());
+
+        for stack in &self.stacks {
+            if let Some(i) = stack.last() {
+                result.push(*i);
+            }
+        }
+
+        result
     }
 }
+
+pub const SOLVERS: [&dyn Solver; 2] = [&part1::Solution, &part2::Solution];
+pub const REFERENCES: [Reference; 2] = [reference::part1, reference::part2];
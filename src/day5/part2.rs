@@ -1,22 +1,13 @@
-use std::fs;
-use std::io::{self, BufRead};
-use std::time;
+use std::io::BufRead;
+
+use crate::solver::{downcast, Answer, Parsed, Solver};
+use crate::trace;
 
 use super::*;
 
 impl UnloadSpace {
-    fn do_move_9001(&mut self, move_to_do: &Move) -> BoxedResult<()> {
-        if move_to_do.from >= STACK_COUNT {
-            return Err(MoveError::BadFromStack(move_to_do.from).into());
-        }
-
-        if move_to_do.to >= STACK_COUNT {
-            return Err(MoveError::BadToStack(move_to_do.to).into());
-        }
-
-        if self.stacks[move_to_do.from].len() < move_to_do.quantity {
-            return Err(MoveError::NotEnoughCrates(move_to_do.from).into());
-        }
+    pub fn do_move_9001(&mut self, move_to_do: &Move) -> Result<()> {
+        move_to_do.check(&self.stacks)?;
 
         let mut buffer: Vec<char> = Vec::with_capacity(move_to_do.quantity);
 
@@ -35,28 +26,49 @@ impl UnloadSpace {
     }
 }
 
-pub fn get_tops_of_stacks_for_9001() -> TimedResult<String> {
-    let start = time::Instant::now();
+pub fn get_tops_of_stacks_for_9001<R: BufRead>(reader: R) -> Result<String> {
+    rearrange_with_9001(parse_procedure(reader)?)
+}
 
-    let mut unload_space = UnloadSpace::new();
-    let file = fs::File::open("inputs/day5.txt")?;
-    let reader = io::BufReader::new(file);
-    let mut lines = reader.lines();
-    _ = lines.nth(9); // Skip the specification in the file.
+pub fn rearrange_with_9001(procedure: Procedure) -> Result<String> {
+    let mut unload_space = procedure.unload_space;
 
-    for line in lines {
-        let line = line?;
-        let move_to_do = Move::from_str(&line)?;
-        unload_space.do_move_9001(&move_to_do)?;
+    for move_to_do in &procedure.moves {
+        unload_space.do_move_9001(move_to_do)?;
+        trace::debug!(
+            5,
+            "move",
+            crane = 9001,
+            quantity = move_to_do.quantity,
+            from = move_to_do.from + 1,
+            to = move_to_do.to + 1,
+            tops = unload_space.get_tops_of_stacks(),
+        );
     }
 
-    let mut result = String::with_capacity(STACK_COUNT);
+    Ok(unload_space.get_tops_of_stacks())
+}
 
-    for stack in unload_space.stacks {
-        if let Some(i) = stack.last() {
-            result.push(*i);
-        }
+pub struct Solution;
+
+impl Solver for Solution {
+    fn day(&self) -> usize {
+        5
+    }
+
+    fn part(&self) -> usize {
+        2
+    }
+
+    fn name(&self) -> &'static str {
+        "day5::part2::get_tops_of_stacks_for_9001"
+    }
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Parsed> {
+        Ok(Box::new(parse_procedure(input)?))
     }
 
-    Ok((result, start.elapsed()))
+    fn solve_parsed(&self, parsed: Parsed) -> Result<Answer> {
+        Ok(rearrange_with_9001(downcast::<Procedure>(parsed)?)?.into())
+    }
 }
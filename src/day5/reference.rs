@@ -0,0 +1,75 @@
+use crate::solver::Answer;
+
+// A move's quantity, and the stacks it's from and to counted from 0.
+type Move = (usize, usize, usize);
+
+// The stacks bottom crate first, read column by column from the drawing, and then the moves.
+fn parse(input: &str) -> Option<(Vec<Vec<char>>, Vec<Move>)> {
+    let (drawing, procedure) = input.split_once("\n\n")?;
+    let mut rows = drawing.lines().rev();
+    let count = rows.next()?.split_whitespace().count();
+
+    let mut stacks = vec![Vec::<char>::new(); count];
+    for row in rows {
+        let row = row.chars().collect::<Vec<char>>();
+        for (index, stack) in stacks.iter_mut().enumerate() {
+            match row.get(index * 4 + 1) {
+                Some(' ') | None => {}
+                Some(label) => stack.push(*label),
+            }
+        }
+    }
+
+    let mut moves = Vec::new();
+    for line in procedure.lines().filter(|line| !line.is_empty()) {
+        let numbers = line
+            .split_whitespace()
+            .filter_map(|word| word.parse::<usize>().ok())
+            .collect::<Vec<usize>>();
+        match numbers.as_slice() {
+            [quantity, from, to] => {
+                moves.push((*quantity, from.checked_sub(1)?, to.checked_sub(1)?))
+            }
+            _ => return None,
+        }
+    }
+
+    Some((stacks, moves))
+}
+
+fn tops(stacks: &[Vec<char>]) -> Answer {
+    stacks
+        .iter()
+        .filter_map(|stack| stack.last())
+        .collect::<String>()
+        .into()
+}
+
+// The CrateMover 9000 really does move one crate at a time.
+pub fn part1(input: &str) -> Option<Answer> {
+    let (mut stacks, moves) = parse(input)?;
+
+    for (quantity, from, to) in moves {
+        for _ in 0..quantity {
+            let label = stacks.get_mut(from)?.pop()?;
+            stacks.get_mut(to)?.push(label);
+        }
+    }
+
+    Some(tops(&stacks))
+}
+
+// The CrateMover 9001 picks them all up at once, so they're put down in the same order.
+pub fn part2(input: &str) -> Option<Answer> {
+    let (mut stacks, moves) = parse(input)?;
+
+    for (quantity, from, to) in moves {
+        let mut picked_up = Vec::new();
+        for _ in 0..quantity {
+            picked_up.insert(0, stacks.get_mut(from)?.pop()?);
+        }
+        stacks.get_mut(to)?.extend(picked_up);
+    }
+
+    Some(tops(&stacks))
+}
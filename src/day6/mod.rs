@@ -1,44 +1,83 @@
-use std::error;
-use std::fmt;
-use std::fs;
-use std::time;
+use std::io::BufRead;
 
-use crate::TimedResult;
+use crate::solver::{Reference, Solver, Streaming};
+use crate::{Error, Problem, Result};
 
 pub mod part1;
 pub mod part2;
+pub mod reference;
+pub mod streaming;
 
-#[derive(Debug)]
-struct PacketStartNotFound {}
+pub fn read_datastream<R: BufRead>(mut reader: R) -> Result<Vec<u8>> {
+    let mut bytes = Vec::<u8>::new();
+    reader.read_to_end(&mut bytes)?;
 
-impl fmt::Display for PacketStartNotFound {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Start of packet not found.")
-    }
+    Ok(bytes)
 }
 
-impl error::Error for PacketStartNotFound {}
+// Reads only as far as the marker, a buffer at a time, so the datastream can be any length. It's
+// all on the first line, so the search gives up at the end of it.
+pub fn find_start_of_marker<R: BufRead>(mut reader: R, marker_size: usize) -> Result<usize> {
+    let mut search = MarkerSearch::new(marker_size);
 
-pub fn find_start_of_marker(marker_size: usize) -> TimedResult<usize> {
-    let file = fs::read_to_string("inputs/day6.txt")?;
+    loop {
+        let buffer = reader.fill_buf()?;
+        if buffer.is_empty() {
+            break;
+        }
 
-    let start_time = time::Instant::now();
-    let bytes = file.as_bytes();
+        for &byte in buffer {
+            if byte == b'\n' || byte == b'\r' {
+                return Err(Error::puzzle(6, Problem::MarkerNotFound(marker_size)));
+            }
+            if let Some(end) = search.push(byte) {
+                return Ok(end);
+            }
+        }
 
-    let mut start_index: usize = 0;
+        let length = buffer.len();
+        reader.consume(length);
+    }
 
-    for end_index in 1..bytes.len() {
-        if start_index + marker_size == end_index {
-            return Ok((end_index, start_time.elapsed()));
-        }
+    Err(Error::puzzle(6, Problem::MarkerNotFound(marker_size)))
+}
 
-        for i in start_index..end_index {
-            if bytes[i] == bytes[end_index] {
-                start_index = i + 1;
-                break;
-            }
+pub fn find_start_of_marker_in(bytes: &[u8], marker_size: usize) -> Result<usize> {
+    find_start_of_marker(bytes, marker_size)
+}
+
+// A sliding window over the datastream. When a new byte repeats one already in the window, the
+// window starts again just after the earlier copy, since no marker can include both. Once the
+// window's as big as a marker, every byte in it is different.
+pub struct MarkerSearch {
+    marker_size: usize,
+    start: usize,
+    end: usize,
+    // Where the window would have to start to leave out each byte value's last appearance.
+    after_last_seen: [usize; 256],
+}
+
+impl MarkerSearch {
+    pub fn new(marker_size: usize) -> Self {
+        Self {
+            marker_size,
+            start: 0,
+            end: 0,
+            after_last_seen: [0; 256],
         }
     }
 
-    Err(PacketStartNotFound {}.into())
+    // Adds the next byte to the window, and once it makes a marker, returns how many bytes that
+    // took.
+    pub fn push(&mut self, byte: u8) -> Option<usize> {
+        self.start = self.start.max(self.after_last_seen[byte as usize]);
+        self.end += 1;
+        self.after_last_seen[byte as usize] = self.end;
+
+        (self.end - self.start == self.marker_size).then_some(self.end)
+    }
 }
+
+pub const SOLVERS: [&dyn Solver; 2] = [&part1::Solution, &part2::Solution];
+pub const REFERENCES: [Reference; 2] = [reference::part1, reference::part2];
+pub const STREAMING: [&Streaming; 2] = [&streaming::PART1, &streaming::PART2];
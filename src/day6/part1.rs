@@ -1,5 +1,31 @@
+use crate::solver::{downcast, Answer, Parsed, Solver};
+
 use super::*;
 
-pub fn find_start_of_packet_marker() -> TimedResult<usize> {
-    find_start_of_marker(4)
+pub fn find_start_of_packet_marker<R: BufRead>(reader: R) -> Result<usize> {
+    find_start_of_marker(reader, 4)
+}
+
+pub struct Solution;
+
+impl Solver for Solution {
+    fn day(&self) -> usize {
+        6
+    }
+
+    fn part(&self) -> usize {
+        1
+    }
+
+    fn name(&self) -> &'static str {
+        "day6::part1::find_start_of_packet_marker"
+    }
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Parsed> {
+        Ok(Box::new(read_datastream(input)?))
+    }
+
+    fn solve_parsed(&self, parsed: Parsed) -> Result<Answer> {
+        Ok(find_start_of_marker_in(&downcast::<Vec<u8>>(parsed)?, 4)?.into())
+    }
 }
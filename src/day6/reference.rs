@@ -0,0 +1,24 @@
+use crate::solver::Answer;
+
+// Checks every window in turn, comparing every character in it with every other.
+fn find_marker(input: &str, size: usize) -> Option<Answer> {
+    let characters = input.trim_end().chars().collect::<Vec<char>>();
+
+    for end in size..=characters.len() {
+        let window = &characters[end - size..end];
+        let all_different = (0..size).all(|i| (i + 1..size).all(|j| window[i] != window[j]));
+        if all_different {
+            return Some(end.into());
+        }
+    }
+
+    None
+}
+
+pub fn part1(input: &str) -> Option<Answer> {
+    find_marker(input, 4)
+}
+
+pub fn part2(input: &str) -> Option<Answer> {
+    find_marker(input, 14)
+}
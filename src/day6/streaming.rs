@@ -0,0 +1,31 @@
+use crate::solver::{Answer, Streaming};
+
+use super::*;
+
+// Neither part keeps any of the datastream. The search is a fixed table of where each byte value
+// was last seen, 2 KiB on the stack, and only the reader's own buffer is ever read into.
+const MEMORY: &str = "nothing on the heap, whatever the datastream's length";
+
+pub const PART1: Streaming = Streaming {
+    day: 6,
+    part: 1,
+    name: "day6::streaming::find_start_of_packet_marker",
+    memory: MEMORY,
+    stream: stream_packet_marker,
+};
+
+pub const PART2: Streaming = Streaming {
+    day: 6,
+    part: 2,
+    name: "day6::streaming::find_start_of_message_marker",
+    memory: MEMORY,
+    stream: stream_message_marker,
+};
+
+fn stream_packet_marker(input: &mut dyn BufRead) -> Result<Answer> {
+    Ok(find_start_of_marker(input, 4)?.into())
+}
+
+fn stream_message_marker(input: &mut dyn BufRead) -> Result<Answer> {
+    Ok(find_start_of_marker(input, 14)?.into())
+}
@@ -1,18 +1,22 @@
-mod node;
+pub mod node;
 pub mod part1;
 pub mod part2;
-mod tree_builder;
+pub mod reference;
+pub mod tree_builder;
 
 use std::cell::RefCell;
-use std::error;
 use std::fmt;
-use std::fs;
-use std::io::{self, BufRead};
+use std::io::BufRead;
 use std::rc::Rc;
-use std::time;
 
-use crate::{BoxedResult, TimedResult};
-use node::Node;
-use tree_builder::TreeBuilder;
+use crate::parse::{self, Fields};
+use crate::solver::{Reference, Solver};
+use crate::trace;
+use crate::{Problem, Result};
+pub use node::Node;
+pub use tree_builder::TreeBuilder;
 
-type MagicNode = Rc<RefCell<Node>>;
+pub type MagicNode = Rc<RefCell<Node>>;
+
+pub const SOLVERS: [&dyn Solver; 2] = [&part1::Solution, &part2::Solution];
+pub const REFERENCES: [Reference; 2] = [reference::part1, reference::part2];
@@ -8,6 +8,12 @@ pub struct Node {
     pub parent: Option<MagicNode>,
 }
 
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
 impl Node {
     pub fn create_file(name: &str, size: usize) -> MagicNode {
         Rc::new(RefCell::new(Self {
@@ -29,6 +35,25 @@ impl Node {
         }))
     }
 
+    // Draws this node and everything under it like the tree in the puzzle, one line each.
+    fn fmt_indented(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        write!(
+            f,
+            "{}- {} ({}, size={})",
+            "  ".repeat(depth),
+            self.name,
+            if self.is_directory { "dir" } else { "file" },
+            self.size
+        )?;
+
+        for node in &self.contents {
+            writeln!(f)?;
+            node.borrow().fmt_indented(f, depth + 1)?;
+        }
+
+        Ok(())
+    }
+
     pub fn add_size(&mut self, size: usize) {
         self.size += size;
 
@@ -1,12 +1,15 @@
-use super::*;
+use crate::solver::{downcast, Answer, Parsed, Solver};
 
-pub fn find_directory_sizes() -> TimedResult<usize> {
-    let file = fs::File::open("inputs/day7.txt")?;
-    let start_time = time::Instant::now();
+use super::*;
 
-    let root = TreeBuilder::build_from_file(file)?;
+pub fn find_directory_sizes<R: BufRead>(reader: R) -> Result<usize> {
+    Ok(sum_small_directory_sizes(&TreeBuilder::build_from_reader(
+        reader,
+    )?))
+}
 
-    Ok((traverse_tree(&root, 0), start_time.elapsed()))
+pub fn sum_small_directory_sizes(root: &MagicNode) -> usize {
+    traverse_tree(root, 0)
 }
 
 fn traverse_tree(node: &MagicNode, initial_sum: usize) -> usize {
@@ -25,3 +28,27 @@ fn traverse_tree(node: &MagicNode, initial_sum: usize) -> usize {
         sum
     }
 }
+
+pub struct Solution;
+
+impl Solver for Solution {
+    fn day(&self) -> usize {
+        7
+    }
+
+    fn part(&self) -> usize {
+        1
+    }
+
+    fn name(&self) -> &'static str {
+        "day7::part1::find_directory_sizes"
+    }
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Parsed> {
+        Ok(Box::new(TreeBuilder::build_from_reader(input)?))
+    }
+
+    fn solve_parsed(&self, parsed: Parsed) -> Result<Answer> {
+        Ok(sum_small_directory_sizes(&downcast::<MagicNode>(parsed)?).into())
+    }
+}
@@ -1,20 +1,24 @@
+use crate::solver::{downcast, Answer, Parsed, Solver};
+
 use super::*;
 
 const TOTAL_SIZE: usize = 70_000_000;
 const UPDATE_SIZE: usize = 30_000_000;
 
-pub fn find_directory_to_delete() -> TimedResult<usize> {
-    let file = fs::File::open("inputs/day7.txt")?;
-    let start_time = time::Instant::now();
+pub fn find_directory_to_delete<R: BufRead>(reader: R) -> Result<usize> {
+    Ok(find_smallest_directory_to_delete(
+        &TreeBuilder::build_from_reader(reader)?,
+    ))
+}
 
-    let root = TreeBuilder::build_from_file(file)?;
-    let current_free_space = TOTAL_SIZE - root.borrow().size;
-    let free_space_needed = UPDATE_SIZE - current_free_space;
+pub fn find_smallest_directory_to_delete(root: &MagicNode) -> usize {
+    // This is how far over the limit the update would take us, which is zero if there's already
+    // enough room.
+    let free_space_needed = (root.borrow().size)
+        .saturating_add(UPDATE_SIZE)
+        .saturating_sub(TOTAL_SIZE);
 
-    Ok((
-        find_minimum_big_enough(&root, free_space_needed),
-        start_time.elapsed(),
-    ))
+    find_minimum_big_enough(root, free_space_needed)
 }
 
 fn find_minimum_big_enough(node: &MagicNode, target_size: usize) -> usize {
@@ -38,3 +42,27 @@ fn find_minimum_big_enough(node: &MagicNode, target_size: usize) -> usize {
         minimum
     }
 }
+
+pub struct Solution;
+
+impl Solver for Solution {
+    fn day(&self) -> usize {
+        7
+    }
+
+    fn part(&self) -> usize {
+        2
+    }
+
+    fn name(&self) -> &'static str {
+        "day7::part2::find_directory_to_delete"
+    }
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Parsed> {
+        Ok(Box::new(TreeBuilder::build_from_reader(input)?))
+    }
+
+    fn solve_parsed(&self, parsed: Parsed) -> Result<Answer> {
+        Ok(find_smallest_directory_to_delete(&downcast::<MagicNode>(parsed)?).into())
+    }
+}
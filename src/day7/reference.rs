@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+
+use crate::solver::Answer;
+
+// Every directory's size, keyed by its full path. Each file's size is added to every directory on
+// the way down to it, rather than building a tree.
+fn directory_sizes(input: &str) -> Option<HashMap<String, usize>> {
+    let mut path = Vec::<&str>::new();
+    let mut sizes = HashMap::<String, usize>::new();
+    sizes.insert(String::from("/"), 0);
+
+    for line in input.lines() {
+        let words = line.split_whitespace().collect::<Vec<&str>>();
+        match words.as_slice() {
+            ["$", "cd", "/"] => path.clear(),
+            ["$", "cd", ".."] => {
+                path.pop()?;
+            }
+            ["$", "cd", directory] => path.push(directory),
+            ["$", "ls"] => {}
+            // Listing a directory is enough for it to count, even if it turns out to be empty.
+            ["dir", directory] => {
+                let directory = path
+                    .iter()
+                    .chain([directory])
+                    .copied()
+                    .collect::<Vec<&str>>();
+                sizes
+                    .entry(format!("/{}", directory.join("/")))
+                    .or_insert(0);
+            }
+            [size, _] => {
+                let size = size.parse::<usize>().ok()?;
+                for depth in 0..=path.len() {
+                    let directory = format!("/{}", path[..depth].join("/"));
+                    *sizes.entry(directory).or_insert(0) += size;
+                }
+            }
+            _ => return None,
+        }
+    }
+
+    Some(sizes)
+}
+
+pub fn part1(input: &str) -> Option<Answer> {
+    let sizes = directory_sizes(input)?;
+
+    Some(
+        sizes
+            .values()
+            .filter(|size| **size <= 100_000)
+            .sum::<usize>()
+            .into(),
+    )
+}
+
+pub fn part2(input: &str) -> Option<Answer> {
+    let sizes = directory_sizes(input)?;
+    // A disk this full would have negative free space, so the sums are done signed.
+    let free = 70_000_000 - sizes["/"] as i128;
+    let needed = (30_000_000 - free).max(0) as usize;
+
+    Some(
+        sizes
+            .values()
+            .filter(|size| **size >= needed)
+            .min()?
+            .to_owned()
+            .into(),
+    )
+}
@@ -1,72 +1,74 @@
 use super::*;
 
-#[derive(Debug)]
-enum TreeBuilderError {
-    NoSuchNode,
-    UnrecognizedCommand,
-    MissingCommand,
-    MissingName,
-}
-
-impl fmt::Display for TreeBuilderError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
-            Self::NoSuchNode => write!(f, "No such node."),
-            Self::UnrecognizedCommand => write!(f, "Unrecognized command."),
-            Self::MissingCommand => write!(f, "Missing command."),
-            Self::MissingName => write!(f, "Missing name."),
-        }
-    }
-}
-
-impl error::Error for TreeBuilderError {}
+// Sizes are added up every directory above a file and the tree is walked recursively, so a very
+// deep one would be slow to build and could overflow the stack.
+const MAX_DEPTH: usize = 1000;
 
 pub struct TreeBuilder {
     current_node: MagicNode,
+    depth: usize,
 }
 
 impl TreeBuilder {
-    pub fn build_from_file(file: fs::File) -> BoxedResult<MagicNode> {
-        let reader = io::BufReader::new(file);
-
+    pub fn build_from_reader<R: BufRead>(reader: R) -> Result<MagicNode> {
         let root_node = Node::create_directory("/");
 
         let mut terminal = Self {
             current_node: Rc::clone(&root_node),
+            depth: 0,
         };
 
-        for line in reader.lines() {
-            let line = line?;
-            let mut tokens = line.split_whitespace();
+        parse::for_each_line(reader, 7, |line| {
+            let mut fields = Fields::new(&line);
+            if fields.is_empty() {
+                return Ok(());
+            }
 
-            while let Some(token) = tokens.next() {
-                if token == "$" {
-                    let command = tokens.next().ok_or(TreeBuilderError::MissingCommand)?;
-                    if command == "cd" {
-                        terminal
-                            .change_node(tokens.next().ok_or(TreeBuilderError::MissingName)?)?;
-                    } else if command != "ls" {
-                        return Err(TreeBuilderError::UnrecognizedCommand.into());
+            match fields.next("a command or listing")? {
+                "$" => match fields.next("command")? {
+                    "cd" => {
+                        let target = fields.next("directory name")?;
+                        if !terminal.change_node(target) {
+                            return Err(line.error_at(target, Problem::NoSuchDirectory));
+                        }
+                        if terminal.depth > MAX_DEPTH {
+                            return Err(line.error_at(target, Problem::NestedTooDeeply(MAX_DEPTH)));
+                        }
+                        trace::debug!(
+                            7,
+                            "enter",
+                            line = line.number,
+                            directory = terminal.current_node.borrow().name,
+                            depth = terminal.depth,
+                        );
                     }
-                } else if token == "dir" {
-                    terminal.add_node(Node::create_directory(
-                        tokens.next().ok_or(TreeBuilderError::MissingName)?,
-                    ));
-                } else {
-                    let file_size = token.parse::<usize>()?;
-                    let file_name = tokens.next().ok_or(TreeBuilderError::MissingName)?;
+                    "ls" => {}
+                    command => return Err(line.error_at(command, Problem::Unrecognized("command"))),
+                },
+                "dir" => terminal.add_node(Node::create_directory(fields.next("directory name")?)),
+                size => {
+                    let file_size = line.number::<usize>(size)?;
+                    // The root holds every file, so if its total fits then every directory's does.
+                    if root_node.borrow().size.checked_add(file_size).is_none() {
+                        return Err(line.error_at(size, Problem::Overflow("add up")));
+                    }
+                    let file_name = fields.next("file name")?;
                     terminal.add_node(Node::create_file(file_name, file_size));
+                    trace::trace!(7, "file", name = file_name, size = file_size);
                 }
             }
-        }
+
+            fields.end()
+        })?;
 
         Ok(root_node)
     }
 
-    fn change_node(&mut self, target: &str) -> Result<(), TreeBuilderError> {
+    // Returns whether there was a directory to change into.
+    fn change_node(&mut self, target: &str) -> bool {
         if target == "/" {
             // This only happens at the beginning, so we can just ignore it.
-            return Ok(());
+            return true;
         }
 
         let current_node = Rc::clone(&self.current_node);
@@ -75,9 +77,10 @@ impl TreeBuilder {
         if target == ".." {
             if let Some(parent) = &current_node.parent {
                 self.current_node = Rc::clone(parent);
-                return Ok(());
+                self.depth -= 1;
+                return true;
             } else {
-                return Err(TreeBuilderError::NoSuchNode);
+                return false;
             }
         }
 
@@ -85,11 +88,12 @@ impl TreeBuilder {
             let inner_node = node.borrow();
             if inner_node.is_directory && inner_node.name == target {
                 self.current_node = Rc::clone(node);
-                return Ok(());
+                self.depth += 1;
+                return true;
             }
         }
 
-        Err(TreeBuilderError::NoSuchNode)
+        false
     }
 
     fn add_node(&self, node: MagicNode) {
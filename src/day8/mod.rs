@@ -1,68 +1,63 @@
 pub mod part1;
 pub mod part2;
+pub mod reference;
+pub mod streaming;
 
 use std::fmt;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::time::Instant;
+use std::io::BufRead;
 
-use crate::{BoxedResult, TimedResult};
+use crate::geometry::Direction;
+use crate::grid::{Grid, Position};
+use crate::image::{self, Color, Picture};
+use crate::solver::{Reference, Solver, Streaming};
+use crate::{Error, Problem, Result};
 
-#[derive(Debug)]
-struct Tree {
-    height: u8,
-    visible: bool,
+const ASCII_ZERO: u8 = b'0';
+
+pub struct Map {
+    pub trees: Grid<u8>,
 }
 
-impl fmt::Display for Tree {
+// Just the heights, laid out like the input.
+impl fmt::Display for Map {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}{}", self.height, if self.visible { 'O' } else { '-' })
+        write!(f, "{}", self.trees)
     }
 }
 
-const ASCII_ZERO: u8 = '0' as u8;
-
-struct Map {
-    width: usize,
-    height: usize,
-    trees: Vec<Vec<Tree>>,
-}
-
 impl Map {
-    fn from_file(path: &str) -> BoxedResult<Self> {
-        let mut grid: Vec<Vec<Tree>> = Vec::new();
-
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
-        let lines = reader.lines();
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self> {
+        let trees = Grid::from_reader(reader, 8, "a digit", |_, byte| {
+            byte.is_ascii_digit().then(|| byte - ASCII_ZERO)
+        })?;
 
-        for line in lines {
-            let trees = line?
-                .into_bytes()
-                .into_iter()
-                .map(|item| Tree {
-                    height: item - ASCII_ZERO,
-                    visible: false,
-                })
-                .collect();
-            grid.push(trees);
+        if trees.is_empty() {
+            return Err(Error::puzzle(8, Problem::Missing("trees")));
         }
 
-        let height = grid.len();
-        let width = grid[0].len();
-
-        Ok(Self {
-            width,
-            height,
-            trees: grid,
-        })
+        Ok(Self { trees })
     }
+}
 
-    fn tree(&self, x: usize, y: usize) -> &Tree {
-        &self.trees[y][x]
-    }
+// Taller trees are lighter, and the ones that can be seen from outside the forest are green.
+pub fn paint(input: &mut dyn BufRead) -> Result<Picture> {
+    let map = Map::from_reader(input)?;
+    let visible = part1::mark_visible_trees(&map);
+
+    let mut picture = image::canvas(8, map.trees.width(), map.trees.height())?;
 
-    fn tree_mut(&mut self, x: usize, y: usize) -> &mut Tree {
-        &mut self.trees[y][x]
+    for position in map.trees.positions() {
+        let height = map.trees[position] as usize;
+        picture[position] = if visible[position] {
+            Color::new(20, 70, 20).towards(Color::new(140, 240, 100), height, 9)
+        } else {
+            Color::gray(30).towards(Color::gray(150), height, 9)
+        };
     }
+
+    Ok(picture)
 }
+
+pub const SOLVERS: [&dyn Solver; 2] = [&part1::Solution, &part2::Solution];
+pub const REFERENCES: [Reference; 2] = [reference::part1, reference::part2];
+pub const STREAMING: [&Streaming; 2] = [&streaming::PART1, &streaming::PART2];
@@ -1,86 +1,72 @@
+use std::iter;
+
+use crate::solver::{downcast, Answer, Parsed, Solver};
+
 use super::*;
 
-pub fn count_visible_trees() -> TimedResult<usize> {
-    let start_time = Instant::now();
+pub fn count_visible_trees<R: BufRead>(reader: R) -> Result<usize> {
+    Ok(mark_and_count_visible_trees(&Map::from_reader(reader)?))
+}
 
-    let mut map = Map::from_file("inputs/day8.txt")?;
+pub fn mark_and_count_visible_trees(map: &Map) -> usize {
+    mark_visible_trees(map)
+        .iter()
+        .filter(|visible| **visible)
+        .count()
+}
 
-    // Mark the top edge as visible.
-    for x in 0..map.width {
-        map.tree_mut(x, 0).visible = true;
-    }
+// Looking along every row and column from both ends, a tree is visible if it's taller than every
+// tree before it.
+pub fn mark_visible_trees(map: &Map) -> Grid<bool> {
+    let trees = &map.trees;
+    let (width, height) = (trees.width(), trees.height());
+    let mut visible = Grid::filled(width, height, false);
 
-    // Check the intermediate rows.
-    for y in 1..map.height - 1 {
-        // The left edge is always visible.
-        map.tree_mut(0, y).visible = true;
-        // Going from left to right, find what's visible and what's not.
-        let mut tallest_tree = map.tree(0, y).height;
-        for x in 1..map.width - 1 {
-            if map.tree(x, y).height > tallest_tree {
-                map.tree_mut(x, y).visible = true;
-                tallest_tree = map.tree(x, y).height;
-            }
-        }
+    let lines = (0..height)
+        .flat_map(|y| {
+            [
+                ((0, y), Direction::Right),
+                ((width - 1, y), Direction::Left),
+            ]
+        })
+        .chain(
+            (0..width).flat_map(|x| [((x, 0), Direction::Down), ((x, height - 1), Direction::Up)]),
+        );
+
+    for (start, direction) in lines {
+        let mut tallest_tree = None;
 
-        // Now go from right to left.
-        map.tree_mut(map.width - 1, y).visible = true;
-        tallest_tree = map.tree(map.width - 1, y).height;
-        for x in (1..map.width - 1).rev() {
-            if map.tree(x, y).height > tallest_tree {
-                map.tree_mut(x, y).visible = true;
-                tallest_tree = map.tree(x, y).height;
+        for position in iter::once(start).chain(trees.ray(start, direction.vector())) {
+            if tallest_tree.is_none_or(|tallest_tree| trees[position] > tallest_tree) {
+                visible[position] = true;
+                tallest_tree = Some(trees[position]);
             }
         }
     }
 
-    // Mark the bottom edge as visible.
-    for x in 0..map.width {
-        map.tree_mut(x, map.height - 1).visible = true;
-    }
+    visible
+}
 
-    // Mark the left edge as visible.
-    for y in 0..map.height {
-        map.tree_mut(0, y).visible = true;
-    }
+pub struct Solution;
 
-    // Check the intermediate columns.
-    for x in 1..map.width - 1 {
-        // From top to bottom...
-        map.tree_mut(x, 0).visible = true;
-        let mut tallest_tree = map.tree(x, 0).height;
-        for y in 1..map.height - 1 {
-            if map.tree(x, y).height > tallest_tree {
-                map.tree_mut(x, y).visible = true;
-                tallest_tree = map.tree(x, y).height;
-            }
-        }
+impl Solver for Solution {
+    fn day(&self) -> usize {
+        8
+    }
 
-        // ... and from bottom to top!
-        map.tree_mut(x, map.height - 1).visible = true;
-        tallest_tree = map.tree(x, map.height - 1).height;
-        for y in (1..map.height - 1).rev() {
-            if map.tree(x, y).height > tallest_tree {
-                map.tree_mut(x, y).visible = true;
-                tallest_tree = map.tree(x, y).height;
-            }
-        }
+    fn part(&self) -> usize {
+        1
     }
 
-    // Mark the right edge as visible.
-    for y in 0..map.height {
-        map.tree_mut(map.width - 1, y).visible = true;
+    fn name(&self) -> &'static str {
+        "day8::part1::count_visible_trees"
     }
 
-    // Count the visible trees.
-    let mut visible_trees = 0;
-    for y in 0..map.height {
-        for x in 0..map.width {
-            if map.tree(x, y).visible {
-                visible_trees += 1;
-            }
-        }
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Parsed> {
+        Ok(Box::new(Map::from_reader(input)?))
     }
 
-    Ok((visible_trees, start_time.elapsed()))
+    fn solve_parsed(&self, parsed: Parsed) -> Result<Answer> {
+        Ok(mark_and_count_visible_trees(&downcast::<Map>(parsed)?).into())
+    }
 }
@@ -1,62 +1,59 @@
-use super::*;
+use crate::solver::{downcast, Answer, Parsed, Solver};
 
-pub fn get_best_scenic_score() -> TimedResult<usize> {
-    let start_time = Instant::now();
+use super::*;
 
-    let map = Map::from_file("inputs/day8.txt")?;
+pub fn get_best_scenic_score<R: BufRead>(reader: R) -> Result<usize> {
+    Ok(find_best_scenic_score(&Map::from_reader(reader)?))
+}
 
-    let mut best_scenic_score: usize = 0;
+pub fn find_best_scenic_score(map: &Map) -> usize {
+    map.trees
+        .positions()
+        .map(|position| calculate_scenic_score_for_tree(map, position))
+        .max()
+        .unwrap_or(0)
+}
 
-    for y in 0..map.height {
-        for x in 0..map.width {
-            let scenic_score = calculate_scenic_score_for_tree(&map, x, y);
-            if scenic_score > best_scenic_score {
-                best_scenic_score = scenic_score;
+// How many trees can be seen in each direction, up to and including the first one at least as tall,
+// all multiplied together.
+pub fn calculate_scenic_score_for_tree(map: &Map, tree: Position) -> usize {
+    let trees = &map.trees;
+
+    Direction::ALL
+        .into_iter()
+        .map(|direction| {
+            let mut view: usize = 0;
+            for position in trees.ray(tree, direction.vector()) {
+                view += 1;
+                if trees[position] >= trees[tree] {
+                    break;
+                }
             }
-        }
-    }
-
-    Ok((best_scenic_score, start_time.elapsed()))
+            view
+        })
+        .product()
 }
 
-fn calculate_scenic_score_for_tree(map: &Map, tree_x: usize, tree_y: usize) -> usize {
-    let tree = map.tree(tree_x, tree_y);
+pub struct Solution;
 
-    // Go right.
-    let mut right_view: usize = 0;
-    for x in tree_x + 1..map.width {
-        right_view += 1;
-        if map.tree(x, tree_y).height >= tree.height {
-            break;
-        }
+impl Solver for Solution {
+    fn day(&self) -> usize {
+        8
     }
 
-    // Go left.
-    let mut left_view: usize = 0;
-    for x in (0..tree_x).rev() {
-        left_view += 1;
-        if map.tree(x, tree_y).height >= tree.height {
-            break;
-        }
+    fn part(&self) -> usize {
+        2
     }
 
-    // Go down.
-    let mut down_view: usize = 0;
-    for y in tree_y + 1..map.height {
-        down_view += 1;
-        if map.tree(tree_x, y).height >= tree.height {
-            break;
-        }
+    fn name(&self) -> &'static str {
+        "day8::part2::get_best_scenic_score"
     }
 
-    // Go up.
-    let mut up_view: usize = 0;
-    for y in (0..tree_y).rev() {
-        up_view += 1;
-        if map.tree(tree_x, y).height >= tree.height {
-            break;
-        }
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Parsed> {
+        Ok(Box::new(Map::from_reader(input)?))
     }
 
-    right_view * left_view * down_view * up_view
+    fn solve_parsed(&self, parsed: Parsed) -> Result<Answer> {
+        Ok(find_best_scenic_score(&downcast::<Map>(parsed)?).into())
+    }
 }
@@ -0,0 +1,63 @@
+use crate::solver::Answer;
+
+fn parse(input: &str) -> Option<Vec<Vec<u32>>> {
+    input
+        .lines()
+        .map(|line| line.chars().map(|tree| tree.to_digit(10)).collect())
+        .collect()
+}
+
+// The trees in each direction from (x, y), nearest first.
+fn lines_of_sight(map: &[Vec<u32>], x: usize, y: usize) -> [Vec<u32>; 4] {
+    let row = &map[y];
+    let column = map.iter().map(|row| row[x]).collect::<Vec<u32>>();
+
+    [
+        row[..x].iter().rev().copied().collect(),
+        row[x + 1..].to_vec(),
+        column[..y].iter().rev().copied().collect(),
+        column[y + 1..].to_vec(),
+    ]
+}
+
+// A tree is visible if every tree in some direction is shorter, even if there aren't any.
+pub fn part1(input: &str) -> Option<Answer> {
+    let map = parse(input)?;
+    let mut visible: usize = 0;
+
+    for y in 0..map.len() {
+        for x in 0..map[y].len() {
+            let height = map[y][x];
+            if lines_of_sight(&map, x, y)
+                .iter()
+                .any(|line| line.iter().all(|tree| *tree < height))
+            {
+                visible += 1;
+            }
+        }
+    }
+
+    Some(visible.into())
+}
+
+// Each direction's view stops at the edge or at the first tree at least as tall.
+pub fn part2(input: &str) -> Option<Answer> {
+    let map = parse(input)?;
+    let mut best = 0;
+
+    for y in 0..map.len() {
+        for x in 0..map[y].len() {
+            let height = map[y][x];
+            let score = lines_of_sight(&map, x, y)
+                .iter()
+                .map(|line| match line.iter().position(|tree| *tree >= height) {
+                    Some(blocker) => blocker + 1,
+                    None => line.len(),
+                })
+                .product::<usize>();
+            best = best.max(score);
+        }
+    }
+
+    Some(best.into())
+}
@@ -0,0 +1,199 @@
+use crate::parse;
+use crate::solver::{Answer, Streaming};
+
+use super::*;
+
+// Both parts read the forest a row at a time and never look back at earlier rows, only at a little
+// that's kept about each column. So however tall the forest is, they only need memory for each
+// column: its byte of the line being read and its height, and then what each part keeps about it.
+const ROW_BYTES_PER_COLUMN: usize = 2;
+pub const PART1_BYTES_PER_COLUMN: usize =
+    ROW_BYTES_PER_COLUMN + size_of::<Column>() + size_of::<Option<u8>>();
+pub const PART2_BYTES_PER_COLUMN: usize =
+    ROW_BYTES_PER_COLUMN + size_of::<ViewColumn>() + size_of::<usize>();
+
+pub const PART1: Streaming = Streaming {
+    day: 8,
+    part: 1,
+    name: "day8::streaming::count_visible_trees",
+    memory: "8 bytes for each column, 2 of them for the row being read",
+    stream: stream_visible_trees,
+};
+
+pub const PART2: Streaming = Streaming {
+    day: 8,
+    part: 2,
+    name: "day8::streaming::get_best_scenic_score",
+    memory: "258 bytes for each column, 2 of them for the row being read",
+    stream: stream_best_scenic_score,
+};
+
+fn stream_visible_trees(input: &mut dyn BufRead) -> Result<Answer> {
+    Ok(count_visible_trees(input)?.into())
+}
+
+fn stream_best_scenic_score(input: &mut dyn BufRead) -> Result<Answer> {
+    Ok(get_best_scenic_score(input)?.into())
+}
+
+// Hands `row` the heights in each row in turn, checked the same way `Map::from_reader` checks them.
+fn for_each_row<R, F>(reader: R, mut row: F) -> Result<()>
+where
+    R: BufRead,
+    F: FnMut(&[u8]) -> Result<()>,
+{
+    let mut width = None;
+    let mut heights = Vec::<u8>::new();
+
+    parse::for_each_line(reader, 8, |line| {
+        if width.is_some_and(|width| line.text.len() != width) {
+            return Err(line.error(Problem::RowLength(width.unwrap_or_default())));
+        }
+        width = Some(line.text.len());
+
+        heights.clear();
+        for (x, byte) in line.text.bytes().enumerate() {
+            if !byte.is_ascii_digit() {
+                return Err(line.error_at_offset(x, 1, Problem::Expected("a digit")));
+            }
+            heights.push(byte - ASCII_ZERO);
+        }
+
+        row(&heights)
+    })?;
+
+    if width.unwrap_or(0) == 0 {
+        return Err(Error::puzzle(8, Problem::Missing("trees")));
+    }
+
+    Ok(())
+}
+
+// Whether a tree's visible from the top, left or right is known as soon as its row is read. If it
+// isn't, it's still visible from the bottom unless a tree at least as tall turns up below it, so
+// each column keeps the trees that are waiting on that. A new tree rules out every waiting tree
+// above it that's no taller, which leaves the waiting trees getting shorter going down. So there's
+// never more than one of each height waiting, and a bit for each height is enough to keep them.
+#[derive(Clone, Copy, Default)]
+struct Column {
+    tallest: Option<u8>,
+    waiting: u16,
+}
+
+pub fn count_visible_trees<R: BufRead>(reader: R) -> Result<usize> {
+    let mut columns = Vec::<Column>::new();
+    let mut tallest_to_the_right = Vec::<Option<u8>>::new();
+    let mut visible: usize = 0;
+
+    for_each_row(reader, |row| {
+        columns.resize(row.len(), Column::default());
+
+        // `None` is shorter than any tree, so the trees on the edge are always taller.
+        tallest_to_the_right.clear();
+        tallest_to_the_right.resize(row.len(), None);
+        let mut tallest = None;
+        for x in (0..row.len()).rev() {
+            tallest_to_the_right[x] = tallest;
+            tallest = tallest.max(Some(row[x]));
+        }
+
+        let mut tallest_to_the_left = None;
+        for (x, &height) in row.iter().enumerate() {
+            let column = &mut columns[x];
+            column.waiting &= !((2 << height) - 1);
+
+            if Some(height) > tallest_to_the_left
+                || Some(height) > tallest_to_the_right[x]
+                || Some(height) > column.tallest
+            {
+                visible += 1;
+            } else {
+                column.waiting |= 1 << height;
+            }
+
+            tallest_to_the_left = tallest_to_the_left.max(Some(height));
+            column.tallest = column.tallest.max(Some(height));
+        }
+
+        Ok(())
+    })?;
+
+    let visible_from_the_bottom = columns
+        .iter()
+        .map(|column| column.waiting.count_ones() as usize)
+        .sum::<usize>();
+
+    Ok(visible + visible_from_the_bottom)
+}
+
+// The views up, left and right only need what's already been read, so they're counted as soon as
+// a tree's row is. The view down needs the rows still to come, so each column keeps the trees it
+// hasn't been cut short for yet, with their scores so far. Like the waiting trees in part 1,
+// there's never more than one of each height.
+#[derive(Clone, Copy, Default)]
+struct ViewColumn {
+    // The last row with a tree at least as tall as each height. When there isn't one, 0 works just
+    // as well, since the view up then goes all the way to the edge.
+    blocking_up: [usize; 10],
+    // A bit for each height with a tree that's still looking down, and that tree's row and score
+    // so far.
+    looking_down: u16,
+    trees: [(usize, usize); 10],
+}
+
+pub fn get_best_scenic_score<R: BufRead>(reader: R) -> Result<usize> {
+    let mut columns = Vec::<ViewColumn>::new();
+    let mut views_right = Vec::<usize>::new();
+    let mut best: usize = 0;
+    let mut y: usize = 0;
+
+    for_each_row(reader, |row| {
+        columns.resize(row.len(), ViewColumn::default());
+
+        // The same trick as looking up, going along the row from the other end.
+        views_right.clear();
+        views_right.resize(row.len(), 0);
+        let mut blocking_right = [row.len().saturating_sub(1); 10];
+        for x in (0..row.len()).rev() {
+            let height = row[x] as usize;
+            views_right[x] = blocking_right[height] - x;
+            blocking_right[..=height].fill(x);
+        }
+
+        let mut blocking_left = [0; 10];
+        for (x, &height) in row.iter().enumerate() {
+            let height = height as usize;
+            let column = &mut columns[x];
+
+            let mut cut_short = column.looking_down & ((2 << height) - 1);
+            while cut_short != 0 {
+                let (tree_y, score) = column.trees[cut_short.trailing_zeros() as usize];
+                best = best.max(score * (y - tree_y));
+                cut_short &= cut_short - 1;
+            }
+
+            let score =
+                (y - column.blocking_up[height]) * (x - blocking_left[height]) * views_right[x];
+            column.looking_down = (column.looking_down & !((2 << height) - 1)) | 1 << height;
+            column.trees[height] = (y, score);
+
+            column.blocking_up[..=height].fill(y);
+            blocking_left[..=height].fill(x);
+        }
+
+        y += 1;
+        Ok(())
+    })?;
+
+    // Whatever's still looking down can see all the way to the bottom row.
+    let bottom = y - 1;
+    for column in &columns {
+        for (height, (tree_y, score)) in column.trees.iter().enumerate() {
+            if column.looking_down & 1 << height != 0 {
+                best = best.max(score * (bottom - tree_y));
+            }
+        }
+    }
+
+    Ok(best)
+}
@@ -0,0 +1,128 @@
+use crate::animation::Simulation;
+
+use super::*;
+
+// Bigger than the puzzle's own diagrams, which fit the whole rope. Past this, the view follows the
+// head around instead.
+const VIEW_WIDTH: usize = 61;
+const VIEW_HEIGHT: usize = 21;
+
+// The head moves one square per step, so a move like "R 4" takes four.
+pub struct RopeAnimation {
+    rope: Rope,
+    moves: Vec<RopeMove>,
+    current: usize,
+    steps: usize,
+}
+
+pub fn animate(input: &mut dyn BufRead, part: usize) -> Result<Box<dyn Simulation>> {
+    Ok(Box::new(RopeAnimation {
+        rope: Rope::new(if part == 2 { 9 } else { 1 }),
+        moves: parse_moves(input)?,
+        current: 0,
+        steps: 0,
+    }))
+}
+
+impl RopeAnimation {
+    // The head's `H` and the tail's `T`, or with more than two knots they're numbered from the
+    // head, so the tail of ten is `9`.
+    fn label(&self, knot: usize, knots: usize) -> char {
+        match knot {
+            0 => 'H',
+            _ if knots == 2 => 'T',
+            _ => char::from_digit((knot % 36) as u32, 36).unwrap_or('?'),
+        }
+    }
+
+    // Everything that's been visited and where the knots are now, unless that's too much to show.
+    fn view(&self, knots: &[Vector2]) -> BoundingBox {
+        let mut view = BoundingBox::new(knots[0]);
+        for point in knots.iter().copied().chain(
+            self.rope
+                .get_tail_positions()
+                .bounds()
+                .into_iter()
+                .flat_map(|trail| [trail.min, trail.max]),
+        ) {
+            view.include(point);
+        }
+        let head = knots[0];
+
+        if view.width() > VIEW_WIDTH {
+            view.min.x = head.x - (VIEW_WIDTH / 2) as isize;
+            view.max.x = view.min.x + VIEW_WIDTH as isize - 1;
+        }
+        if view.height() > VIEW_HEIGHT {
+            view.min.y = head.y - (VIEW_HEIGHT / 2) as isize;
+            view.max.y = view.min.y + VIEW_HEIGHT as isize - 1;
+        }
+
+        view
+    }
+}
+
+impl Simulation for RopeAnimation {
+    fn step(&mut self) -> bool {
+        while self
+            .moves
+            .get(self.current)
+            .is_some_and(|rope_move| self.steps == rope_move.distance)
+        {
+            self.current += 1;
+            self.steps = 0;
+        }
+
+        let Some(rope_move) = self.moves.get(self.current) else {
+            return false;
+        };
+
+        self.rope.move_in_direction(rope_move.direction.vector());
+        self.steps += 1;
+        true
+    }
+
+    // Like the puzzle's diagrams, with `s` for the start and `#` wherever the tail's been. Knots
+    // nearer the head are drawn over the ones behind them.
+    fn draw(&self) -> Vec<String> {
+        let knots = self.rope.knots();
+        let tail_positions = self.rope.get_tail_positions();
+        let view = self.view(&knots);
+
+        (view.min.y..=view.max.y)
+            .map(|y| {
+                (view.min.x..=view.max.x)
+                    .map(|x| {
+                        let point = Vector2::new(x, y);
+                        match knots.iter().position(|knot| *knot == point) {
+                            Some(knot) => self.label(knot, knots.len()),
+                            None if point == Vector2::zero() => 's',
+                            None if tail_positions.contains(&point) => '#',
+                            None => '.',
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn status(&self) -> String {
+        match self.moves.get(self.current) {
+            Some(rope_move) if self.steps > 0 => format!(
+                "Move {} of {}: {}, step {} of {}. The tail has visited {} squares.",
+                self.current + 1,
+                self.moves.len(),
+                rope_move,
+                self.steps,
+                rope_move.distance,
+                self.rope.get_tail_positions().len()
+            ),
+            _ => format!(
+                "{} moves with {} knots. The tail has visited {} squares.",
+                self.moves.len(),
+                self.rope.knots().len(),
+                self.rope.get_tail_positions().len()
+            ),
+        }
+    }
+}
@@ -1,28 +1,105 @@
+pub mod animation;
 pub mod part1;
 pub mod part2;
-mod rope;
-mod rope_move;
-mod vector2;
+pub mod reference;
+pub mod rope;
+pub mod rope_move;
+pub mod trail;
 
 use std::fmt;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::time::Instant;
-
-use rope::Rope;
-use rope_move::RopeMove;
-use vector2::Vector2;
-
-fn count_unique_tail_positions_of_length(len: usize) -> crate::TimedResult<usize> {
-    let start_time = Instant::now();
-    let file = File::open("inputs/day9.txt")?;
-    let reader = BufReader::new(file);
+use std::io::BufRead;
+
+pub use rope::Rope;
+pub use rope_move::RopeMove;
+pub use trail::Trail;
+
+use crate::geometry::{BoundingBox, Direction, Vector2};
+use crate::image::{self, Color, Picture};
+use crate::parse::{self, Fields};
+use crate::solver::{Reference, Solver};
+use crate::{Line, Problem, Result};
+
+pub fn count_unique_tail_positions_of_length<R: BufRead>(reader: R, len: usize) -> Result<usize> {
+    Ok(count_unique_tail_positions_of_moves(
+        &parse_moves(reader)?,
+        len,
+    ))
+}
+
+// No knot can ever get further from the start than the head's moved in total, so as long as that
+// fits, none of the rope's positions can overflow.
+pub fn parse_moves<R: BufRead>(reader: R) -> Result<Vec<RopeMove>> {
+    let mut total: isize = 0;
+
+    parse::lines(reader, 9, |line| {
+        let rope_move = RopeMove::parse(&line)?;
+        total = isize::try_from(rope_move.distance)
+            .ok()
+            .and_then(|distance| total.checked_add(distance))
+            .ok_or_else(|| {
+                line.error_at(&rope_move.distance.to_string(), Problem::Overflow("add up"))
+            })?;
+
+        Ok(rope_move)
+    })
+}
+
+pub fn count_unique_tail_positions_of_moves(moves: &[RopeMove], len: usize) -> usize {
+    move_rope(moves, len).get_tail_positions().len()
+}
+
+pub fn move_rope(moves: &[RopeMove], len: usize) -> Rope {
     let mut rope = Rope::new(len);
 
-    for line in reader.lines() {
-        let rope_move = RopeMove::from_str(&line?)?;
-        rope.move_times_in_direction(rope_move.direction, rope_move.distance);
+    for rope_move in moves {
+        rope.move_times_in_direction(rope_move.direction.vector(), rope_move.distance);
     }
 
-    Ok((rope.get_tail_positions().len(), start_time.elapsed()))
+    rope
 }
+
+// Both parts' tails on one picture: blue where only the short rope's tail went, orange where only
+// the long one's did, and white where both did. The start is red.
+pub fn paint(input: &mut dyn BufRead) -> Result<Picture> {
+    let moves = parse_moves(input)?;
+    let short = move_rope(&moves, 1);
+    let long = move_rope(&moves, 9);
+    let (short, long) = (short.get_tail_positions(), long.get_tail_positions());
+
+    // Both tails start at the origin, so there's always somewhere to draw.
+    let mut bounds = BoundingBox::new(Vector2::zero());
+    for corner in [short, long]
+        .iter()
+        .filter_map(|tail| tail.bounds())
+        .flat_map(|tail| [tail.min, tail.max])
+    {
+        bounds.include(corner);
+    }
+    let mut picture = image::canvas(9, bounds.width(), bounds.height())?;
+
+    for (tail, color) in [
+        (short, Color::new(60, 120, 230)),
+        (long, Color::new(240, 150, 40)),
+    ] {
+        for position in tail
+            .squares()
+            .iter()
+            .filter_map(|point| bounds.to_position(*point))
+        {
+            picture[position] = if picture[position] == Color::BLACK {
+                color
+            } else {
+                Color::WHITE
+            };
+        }
+    }
+
+    if let Some(start) = bounds.to_position(Vector2::zero()) {
+        picture[start] = Color::RED;
+    }
+
+    Ok(picture)
+}
+
+pub const SOLVERS: [&dyn Solver; 2] = [&part1::Solution, &part2::Solution];
+pub const REFERENCES: [Reference; 2] = [reference::part1, reference::part2];
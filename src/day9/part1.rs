@@ -1,5 +1,31 @@
+use crate::solver::{downcast, Answer, Parsed, Solver};
+
 use super::*;
 
-pub fn count_unique_tail_positions() -> crate::TimedResult<usize> {
-    count_unique_tail_positions_of_length(1)
+pub fn count_unique_tail_positions<R: BufRead>(reader: R) -> crate::Result<usize> {
+    count_unique_tail_positions_of_length(reader, 1)
+}
+
+pub struct Solution;
+
+impl Solver for Solution {
+    fn day(&self) -> usize {
+        9
+    }
+
+    fn part(&self) -> usize {
+        1
+    }
+
+    fn name(&self) -> &'static str {
+        "day9::part1::count_unique_tail_positions"
+    }
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Parsed> {
+        Ok(Box::new(parse_moves(input)?))
+    }
+
+    fn solve_parsed(&self, parsed: Parsed) -> Result<Answer> {
+        Ok(count_unique_tail_positions_of_moves(&downcast::<Vec<RopeMove>>(parsed)?, 1).into())
+    }
 }
@@ -0,0 +1,115 @@
+use std::collections::HashSet;
+
+use crate::solver::Answer;
+
+// A line the tail ran straight along, as (x or y it stays on, first, last) with the ends in order.
+type Run = (i64, i64, i64);
+
+// Moves the head one step at a time, and after every step lets each knot catch up with the one in
+// front of it if they've stopped touching. Once a step moves every knot the same way as the head,
+// the rest of the move would too, so the rope jumps to the end of it and the tail's run is kept.
+fn count_tail_positions(input: &str, knots: usize) -> Option<Answer> {
+    let mut rope = vec![(0i64, 0i64); knots];
+    let mut visited = HashSet::from([(0, 0)]);
+    let mut rows = Vec::<Run>::new();
+    let mut columns = Vec::<Run>::new();
+
+    for line in input.lines() {
+        let (direction, distance) = line.split_once(' ')?;
+        let (dx, dy) = match direction {
+            "R" => (1, 0),
+            "L" => (-1, 0),
+            "U" => (0, -1),
+            "D" => (0, 1),
+            _ => return None,
+        };
+
+        let mut remaining = i64::try_from(distance.parse::<usize>().ok()?).ok()?;
+        while remaining > 0 {
+            let before = rope.clone();
+            rope[0].0 += dx;
+            rope[0].1 += dy;
+            remaining -= 1;
+
+            for knot in 1..knots {
+                let (ahead_x, ahead_y) = rope[knot - 1];
+                let (x, y) = &mut rope[knot];
+                if (ahead_x - *x).abs() > 1 || (ahead_y - *y).abs() > 1 {
+                    *x += (ahead_x - *x).signum();
+                    *y += (ahead_y - *y).signum();
+                }
+            }
+
+            let (x, y) = rope[knots - 1];
+            visited.insert((x, y));
+
+            let moved_together = rope
+                .iter()
+                .zip(&before)
+                .all(|(after, before)| (after.0 - before.0, after.1 - before.1) == (dx, dy));
+            if moved_together && remaining > 0 {
+                for knot in &mut rope {
+                    knot.0 = knot.0.checked_add(dx * remaining)?;
+                    knot.1 = knot.1.checked_add(dy * remaining)?;
+                }
+
+                let (end_x, end_y) = rope[knots - 1];
+                if dy == 0 {
+                    rows.push((y, x.min(end_x), x.max(end_x)));
+                } else {
+                    columns.push((x, y.min(end_y), y.max(end_y)));
+                }
+                remaining = 0;
+            }
+        }
+    }
+
+    let rows = joined(rows);
+    let columns = joined(columns);
+    let length = |(_, first, last): &Run| (last - first + 1) as usize;
+    let on = |runs: &[Run], along: i64, across: i64| {
+        runs.iter()
+            .any(|(at, first, last)| *at == along && (*first..=*last).contains(&across))
+    };
+
+    // Every square on a run, less the ones where a row's run crosses a column's, and then the
+    // squares that aren't on any run.
+    let mut count = rows.iter().chain(&columns).map(length).sum::<usize>();
+    for (x, first, last) in &columns {
+        count -= rows
+            .iter()
+            .filter(|(y, left, right)| (first..=last).contains(&y) && (left..=right).contains(&x))
+            .count();
+    }
+    count += visited
+        .iter()
+        .filter(|(x, y)| !on(&rows, *y, *x) && !on(&columns, *x, *y))
+        .count();
+
+    Some(count.into())
+}
+
+// Runs on the same row or column that overlap or touch are merged, so no square's on two of them.
+fn joined(mut runs: Vec<Run>) -> Vec<Run> {
+    runs.sort();
+    let mut joined = Vec::<Run>::new();
+
+    for (at, first, last) in runs {
+        match joined.last_mut() {
+            Some(previous) if previous.0 == at && first <= previous.2 + 1 => {
+                previous.2 = previous.2.max(last)
+            }
+            _ => joined.push((at, first, last)),
+        }
+    }
+
+    joined
+}
+
+pub fn part1(input: &str) -> Option<Answer> {
+    count_tail_positions(input, 2)
+}
+
+pub fn part2(input: &str) -> Option<Answer> {
+    count_tail_positions(input, 10)
+}
@@ -1,10 +1,8 @@
-use std::collections::HashSet;
-
 use super::*;
 
 pub struct Rope {
     position: Vector2,
-    past_positions: HashSet<Vector2>,
+    trail: Trail,
     next: Option<Box<Rope>>,
 }
 
@@ -13,49 +11,83 @@ impl Rope {
         if len == 0 {
             Self {
                 position: Vector2::zero(),
-                past_positions: HashSet::from([Vector2::zero()]),
+                trail: Trail::new(Vector2::zero()),
                 next: None,
             }
         } else {
             let rest = Rope::new(len - 1);
             Self {
                 position: Vector2::zero(),
-                past_positions: HashSet::from([Vector2::zero()]),
+                trail: Trail::new(Vector2::zero()),
                 next: Some(Box::new(rest)),
             }
         }
     }
 
+    // The head goes one square at a time until the whole rope moves as one. After that every step
+    // would be the same as the last, so the rest of the move is made all at once.
     pub fn move_times_in_direction(&mut self, direction: Vector2, times: usize) {
-        for _ in 0..times {
-            self.move_in_direction(direction);
+        for step in 1..=times {
+            if self.move_in_direction(direction) {
+                self.move_straight(direction, times - step);
+                return;
+            }
         }
     }
 
-    fn move_in_direction(&mut self, direction: Vector2) {
+    // Whether every knot moved the same way as this one.
+    pub fn move_in_direction(&mut self, direction: Vector2) -> bool {
         self.position += direction;
-        self.past_positions.insert(self.position);
 
-        if let Some(next) = &mut self.next {
-            let diff = self.position - next.position;
+        // Only the tail's trail is ever asked for, so only the tail keeps one.
+        match &mut self.next {
+            Some(next) => {
+                let diff = self.position - next.position;
 
-            let move_direction = if diff.magnitude() >= 2 {
-                Vector2::new(diff.x.signum(), diff.y.signum())
-            } else {
-                Vector2::zero()
-            };
+                // The next knot only follows once it's no longer touching.
+                if diff.chebyshev() < 2 {
+                    return false;
+                }
 
-            if move_direction.x != 0 || move_direction.y != 0 {
-                next.move_in_direction(move_direction);
+                next.move_in_direction(diff.signum()) && diff.signum() == direction
             }
+            None => {
+                self.trail.insert(self.position);
+                true
+            }
+        }
+    }
+
+    // Every knot goes `times` squares in `direction`, which is only right for a straight rope.
+    fn move_straight(&mut self, direction: Vector2, times: usize) {
+        if times == 0 {
+            return;
+        }
+
+        let start = self.position + direction;
+        self.position += direction * times as isize;
+
+        match &mut self.next {
+            Some(next) => next.move_straight(direction, times),
+            None => self.trail.insert_line(start, self.position),
         }
     }
 
-    pub fn get_tail_positions(&self) -> &HashSet<Vector2> {
+    // Where every knot is, starting with the head.
+    pub fn knots(&self) -> Vec<Vector2> {
+        let mut knots = vec![self.position];
+        if let Some(next) = &self.next {
+            knots.extend(next.knots());
+        }
+
+        knots
+    }
+
+    pub fn get_tail_positions(&self) -> &Trail {
         if let Some(next) = &self.next {
             next.get_tail_positions()
         } else {
-            &self.past_positions
+            &self.trail
         }
     }
 }
@@ -0,0 +1,145 @@
+use std::collections::{BTreeMap, HashSet};
+
+use super::*;
+
+// Every square the tail's been on. Steps are kept square by square, but a run straight along a row
+// or a column is kept as just its two ends, so a move however long takes no more room than a short
+// one.
+pub struct Trail {
+    squares: HashSet<Vector2>,
+    lines: Vec<BoundingBox>,
+}
+
+// Lines along one row or column, keyed by which one, as (start, end) with both ends included.
+type Lines = BTreeMap<isize, Vec<(isize, isize)>>;
+
+impl Trail {
+    pub fn new(start: Vector2) -> Self {
+        Self {
+            squares: HashSet::from([start]),
+            lines: Vec::new(),
+        }
+    }
+
+    pub fn insert(&mut self, square: Vector2) {
+        self.squares.insert(square);
+    }
+
+    // Every square from `from` to `to`, which have to be in the same row or column.
+    pub fn insert_line(&mut self, from: Vector2, to: Vector2) {
+        let mut line = BoundingBox::new(from);
+        line.include(to);
+        self.lines.push(line);
+    }
+
+    pub fn contains(&self, square: &Vector2) -> bool {
+        self.squares.contains(square) || self.lines.iter().any(|line| line.contains(*square))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.squares.is_empty() && self.lines.is_empty()
+    }
+
+    // How many different squares there are. Lines along the same row, or the same column, are
+    // joined up first, so a square can then only be counted twice where a row's line crosses a
+    // column's, or where a single square is on a line too.
+    pub fn len(&self) -> usize {
+        if self.lines.is_empty() {
+            return self.squares.len();
+        }
+
+        let (along_rows, along_columns): (Vec<&BoundingBox>, Vec<&BoundingBox>) =
+            self.lines.iter().partition(|line| line.height() == 1);
+        let rows = join_lines(
+            along_rows
+                .iter()
+                .map(|line| (line.min.y, line.min.x, line.max.x)),
+        );
+        let columns = join_lines(
+            along_columns
+                .iter()
+                .map(|line| (line.min.x, line.min.y, line.max.y)),
+        );
+
+        let mut count = [&rows, &columns]
+            .iter()
+            .flat_map(|lines| lines.values().flatten())
+            .map(|(start, end)| end.abs_diff(*start) + 1)
+            .sum::<usize>();
+
+        for (x, lines) in &columns {
+            for (start, end) in lines {
+                count -= rows
+                    .range(start..=end)
+                    .filter(|(y, _)| is_on(&rows, **y, *x))
+                    .count();
+            }
+        }
+
+        count
+            + self
+                .squares
+                .iter()
+                .filter(|square| {
+                    !is_on(&rows, square.y, square.x) && !is_on(&columns, square.x, square.y)
+                })
+                .count()
+    }
+
+    // The smallest box around every square, or `None` if there aren't any.
+    pub fn bounds(&self) -> Option<BoundingBox> {
+        BoundingBox::around(
+            self.squares
+                .iter()
+                .copied()
+                .chain(self.lines.iter().flat_map(|line| [line.min, line.max])),
+        )
+    }
+
+    // Every square on its own, which is only worth asking for once the trail's known to be small
+    // enough, like when it has to fit in a picture.
+    pub fn squares(&self) -> HashSet<Vector2> {
+        let mut squares = self.squares.clone();
+
+        for line in &self.lines {
+            for y in line.min.y..=line.max.y {
+                squares.extend((line.min.x..=line.max.x).map(|x| Vector2::new(x, y)));
+            }
+        }
+
+        squares
+    }
+}
+
+// Lines that overlap or meet end to end become one, leaving each row's or column's sorted and
+// apart from each other.
+fn join_lines<I: Iterator<Item = (isize, isize, isize)>>(lines: I) -> Lines {
+    let mut joined = Lines::new();
+
+    for (along, start, end) in lines {
+        joined.entry(along).or_default().push((start, end));
+    }
+
+    for lines in joined.values_mut() {
+        lines.sort_unstable();
+        let mut kept = Vec::<(isize, isize)>::with_capacity(lines.len());
+
+        for &(start, end) in lines.iter() {
+            match kept.last_mut() {
+                Some(last) if start <= last.1.saturating_add(1) => last.1 = last.1.max(end),
+                _ => kept.push((start, end)),
+            }
+        }
+
+        *lines = kept;
+    }
+
+    joined
+}
+
+fn is_on(lines: &Lines, along: isize, across: isize) -> bool {
+    lines.get(&along).is_some_and(|lines| {
+        let index = lines.partition_point(|(_, end)| *end < across);
+        lines.get(index).is_some_and(|(start, _)| *start <= across)
+    })
+}
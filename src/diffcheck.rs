@@ -0,0 +1,87 @@
+use std::fmt;
+
+use crate::generate;
+use crate::random::Random;
+use crate::solver::{Answer, Reference, Solver};
+
+// A generated input that a solution and its reference came up with different answers for, along
+// with everything needed to make it again.
+pub struct Disagreement {
+    pub day: usize,
+    pub part: usize,
+    pub size: usize,
+    pub seed: u64,
+    pub input: String,
+    pub solution: Result<Answer, String>,
+    pub reference: Option<Answer>,
+}
+
+impl fmt::Display for Disagreement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let answer = |answer: &Answer| {
+            if answer.is_multiline() {
+                format!("\n{}", answer)
+            } else {
+                answer.to_string()
+            }
+        };
+
+        writeln!(
+            f,
+            "The input from 'generate {} --size {} --seed {}':",
+            self.day, self.size, self.seed
+        )?;
+        write!(f, "  solution:  ")?;
+        match &self.solution {
+            Ok(solution) => writeln!(f, "{}", answer(solution))?,
+            Err(error) => writeln!(f, "Errored: {}", error)?,
+        }
+        write!(f, "  reference: ")?;
+        match &self.reference {
+            Some(reference) => write!(f, "{}", answer(reference)),
+            None => write!(f, "No answer"),
+        }
+    }
+}
+
+// Runs the solution and the reference on `runs` generated inputs, growing from size 1 up to
+// `max_size`, so the first disagreement found is about as small as it gets. Each run has its own
+// seed, counting up from `seed`.
+pub fn find_disagreement(
+    solver: &dyn Solver,
+    reference: Reference,
+    runs: usize,
+    max_size: usize,
+    seed: u64,
+) -> Option<Disagreement> {
+    let generator = generate::generator(solver.day())?;
+
+    for run in 0..runs {
+        let size = 1 + run * (max_size - 1) / runs.saturating_sub(1).max(1);
+        let run_seed = seed.wrapping_add(run as u64);
+
+        let mut input = Vec::<u8>::new();
+        // Writing into memory can't fail.
+        generator(size, &mut Random::new(run_seed), &mut input).ok()?;
+        let input = String::from_utf8_lossy(&input).into_owned();
+
+        let solution = solver
+            .solve(&mut input.as_bytes())
+            .map_err(|error| error.to_string());
+        let expected = reference(&input);
+
+        if solution.as_ref().ok() != expected.as_ref() {
+            return Some(Disagreement {
+                day: solver.day(),
+                part: solver.part(),
+                size,
+                seed: run_seed,
+                input,
+                solution,
+                reference: expected,
+            });
+        }
+    }
+
+    None
+}
@@ -0,0 +1,267 @@
+use std::error;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+// Every way solving a puzzle can fail. Problems with a particular spot in the input carry its
+// location, so the offending line can be shown with a caret under the bad part.
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    // Opening an input file failed, which on its own wouldn't say which file.
+    File {
+        path: PathBuf,
+        error: io::Error,
+    },
+    Input {
+        day: usize,
+        location: Location,
+        problem: Problem,
+    },
+    Puzzle {
+        day: usize,
+        problem: Problem,
+    },
+    WrongParsedType(&'static str),
+}
+
+impl Error {
+    pub fn puzzle(day: usize, problem: Problem) -> Self {
+        Self::Puzzle { day, problem }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "{}", error),
+            Self::File { path, error } => write!(f, "Couldn't open {}: {}", path.display(), error),
+            Self::Input {
+                day,
+                location,
+                problem,
+            } => write!(
+                f,
+                "Day {}, {}: {}\n{}",
+                day,
+                location,
+                problem,
+                location.snippet()
+            ),
+            Self::Puzzle { day, problem } => write!(f, "Day {}: {}", day, problem),
+            Self::WrongParsedType(name) => write!(f, "Parsed input wasn't a {}.", name),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Io(error) | Self::File { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Problem {
+    Expected(&'static str),
+    Missing(&'static str),
+    Unrecognized(&'static str),
+    BadCommonItems(usize),
+    WrongNumberOfRucksacks,
+    NoSuchStack(usize),
+    NotEnoughCrates(usize),
+    MarkerNotFound(usize),
+    NoSuchDirectory,
+    NoPath,
+    Overflow(&'static str),
+    RowLength(usize),
+    NoSuchMonkey(usize),
+    NestedTooDeeply(usize),
+    Label(&'static str),
+    Word(&'static str),
+    TooBigToDraw(usize, usize),
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Expected(what) => write!(f, "Expected {}.", what),
+            Self::Missing(what) => write!(f, "Missing {}.", what),
+            Self::Unrecognized(what) => write!(f, "Unrecognized {}.", what),
+            Self::BadCommonItems(count) => {
+                write!(f, "Expected exactly one common item, found {}.", count)
+            }
+            Self::WrongNumberOfRucksacks => {
+                write!(f, "Number of rucksacks was not a multiple of three.")
+            }
+            Self::NoSuchStack(stack) => write!(f, "There's no stack {}.", stack),
+            Self::NotEnoughCrates(stack) => write!(f, "Not enough crates in stack {}.", stack),
+            Self::MarkerNotFound(size) => {
+                write!(
+                    f,
+                    "No marker found: no {} characters in a row were all different.",
+                    size
+                )
+            }
+            Self::NoSuchDirectory => write!(f, "No such directory."),
+            Self::NoPath => write!(f, "Couldn't find a path!"),
+            Self::Overflow(operation) => write!(f, "The numbers got too big to {}.", operation),
+            Self::RowLength(length) => {
+                write!(f, "Expected {} columns, like the first row.", length)
+            }
+            Self::NoSuchMonkey(monkey) => write!(f, "There's no monkey {}.", monkey),
+            Self::NestedTooDeeply(depth) => {
+                write!(f, "Can't nest things more than {} deep.", depth)
+            }
+            Self::Label(label) => write!(f, "Expected a line starting with '{}'.", label),
+            Self::Word(word) => write!(f, "Expected '{}'.", word),
+            Self::TooBigToDraw(width, height) => {
+                write!(f, "A {} by {} picture is too big to draw.", width, height)
+            }
+        }
+    }
+}
+
+// Lines and columns count from 1, like in an editor. An empty `text` means something was missing
+// from where the column points.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub source: String,
+}
+
+impl Location {
+    pub fn snippet(&self) -> String {
+        let gutter = " ".repeat(self.line.to_string().len());
+        let carets = "^".repeat(self.text.chars().count().max(1));
+
+        format!(
+            "{} |\n{} | {}\n{} | {}{}",
+            gutter,
+            self.line,
+            self.source,
+            gutter,
+            " ".repeat(self.column - 1),
+            carets
+        )
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+// One line of a day's puzzle input, with enough context to point at problems in it.
+#[derive(Clone, Copy, Debug)]
+pub struct Line<'a> {
+    pub day: usize,
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    pub fn new(day: usize, number: usize, text: &'a str) -> Self {
+        Self { day, number, text }
+    }
+
+    // Points at `part`, which should be a slice of this line (like one of its `split_whitespace`
+    // tokens). Anything else is looked for in the line, and failing that the whole line is blamed.
+    pub fn error_at(&self, part: &str, problem: Problem) -> Error {
+        let offset = (part.as_ptr() as usize).wrapping_sub(self.text.as_ptr() as usize);
+
+        if offset <= self.text.len() && offset + part.len() <= self.text.len() {
+            self.error_at_offset(offset, part.len(), problem)
+        } else if let Some(offset) = self.text.find(part) {
+            self.error_at_offset(offset, part.len(), problem)
+        } else {
+            self.error(problem)
+        }
+    }
+
+    // `offset` and `length` are in bytes, and get turned into character columns.
+    pub fn error_at_offset(&self, offset: usize, length: usize, problem: Problem) -> Error {
+        let mut offset = offset.min(self.text.len());
+        let mut end = (offset + length).min(self.text.len());
+
+        // Byte-by-byte parsers can stop partway through a character, so widen out to whole ones.
+        while !self.text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        while !self.text.is_char_boundary(end) {
+            end += 1;
+        }
+
+        Error::Input {
+            day: self.day,
+            location: Location {
+                line: self.number,
+                column: self.text[..offset].chars().count() + 1,
+                text: String::from(&self.text[offset..end]),
+                source: String::from(self.text),
+            },
+            problem,
+        }
+    }
+
+    pub fn error(&self, problem: Problem) -> Error {
+        self.error_at_offset(0, self.text.len(), problem)
+    }
+
+    pub fn error_at_end(&self, problem: Problem) -> Error {
+        self.error_at_offset(self.text.len(), 0, problem)
+    }
+
+    pub fn number<T: FromStr>(&self, part: &str) -> crate::Result<T> {
+        part.parse::<T>()
+            .map_err(|_| self.error_at(part, Problem::Expected("a number")))
+    }
+}
+
+// A `Line` that keeps its own copy of the text, for whatever's parsed up front but can still turn
+// out to be wrong later on, like a move asking for more crates than its stack has.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SourceLine {
+    pub day: usize,
+    pub number: usize,
+    pub text: String,
+}
+
+impl SourceLine {
+    pub fn line(&self) -> Line<'_> {
+        Line::new(self.day, self.number, &self.text)
+    }
+
+    // Points at the `index`th of the line's whitespace-separated words, or the end of the line if
+    // it doesn't have that many.
+    pub fn error_at_word(&self, index: usize, problem: Problem) -> Error {
+        let line = self.line();
+
+        match line.text.split_whitespace().nth(index) {
+            Some(word) => line.error_at(word, problem),
+            None => line.error_at_end(problem),
+        }
+    }
+}
+
+impl From<&Line<'_>> for SourceLine {
+    fn from(line: &Line<'_>) -> Self {
+        Self {
+            day: line.day,
+            number: line.number,
+            text: String::from(line.text),
+        }
+    }
+}
@@ -0,0 +1,428 @@
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use crate::random::Random;
+use crate::solver::Solver;
+use crate::trace;
+
+// Pieces of real inputs that random bytes would take forever to stumble on, plus a few numbers
+// that sit right on the edge of what fits.
+const TOKENS: &[&[u8]] = &[
+    b"\n",
+    b"\n\n",
+    b" ",
+    b",",
+    b"-",
+    b":",
+    b"[",
+    b"]",
+    b"[[[[",
+    b"]]]]",
+    b"$ cd ",
+    b"$ cd ..",
+    b"$ cd /",
+    b"$ ls",
+    b"dir ",
+    b"move ",
+    b" from ",
+    b" to ",
+    b"addx ",
+    b"noop",
+    b"old",
+    b" * ",
+    b" + ",
+    b"Monkey ",
+    b"throw to monkey ",
+    b"divisible by ",
+    b"S",
+    b"E",
+    b"\r\n",
+    b"\xff",
+];
+const NUMBERS: &[&[u8]] = &[
+    b"0",
+    b"1",
+    b"-1",
+    b"4294967296",
+    b"9223372036854775807",
+    b"18446744073709551615",
+    b"18446744073709551616",
+    b"99999999999999999999999999",
+];
+
+// Mutated inputs are kept below this, so a run of duplications can't eat all the memory.
+const MAX_INPUT_LENGTH: usize = 1 << 20;
+// Minimizing a hang waits out the timeout on every attempt that still hangs, so it's cut short.
+const MAX_MINIMIZE_ATTEMPTS: usize = 2000;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Failure {
+    // Where the panic happened, and its message.
+    Panic(String, String),
+    Hang,
+    StackOverflow,
+    // The worker died some other way, described by its exit status.
+    Crash(String),
+}
+
+impl Failure {
+    // Two failures are the same bug if they're the same kind, and for panics, in the same place.
+    pub fn is_same_as(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Panic(location, _), Self::Panic(other_location, _)) => {
+                location == other_location
+            }
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Panic(_, _) => "panic",
+            Self::Hang => "hang",
+            Self::StackOverflow => "stack-overflow",
+            Self::Crash(_) => "crash",
+        }
+    }
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Panic(location, message) => write!(f, "panicked at {}: {}", location, message),
+            Self::Hang => write!(f, "hung"),
+            Self::StackOverflow => write!(f, "overflowed its stack"),
+            Self::Crash(status) => write!(f, "crashed ({})", status),
+        }
+    }
+}
+
+// A failure and the smallest input found that still causes it.
+pub struct Finding {
+    pub failure: Failure,
+    pub input: Vec<u8>,
+    pub original_length: usize,
+}
+
+// Changes a handful of things about `input`: bytes swapped for random ones, tokens and edge-case
+// numbers dropped in, ranges deleted or repeated, and so on.
+pub fn mutate(input: &[u8], random: &mut Random) -> Vec<u8> {
+    let mut output = input.to_vec();
+
+    for _ in 0..random.range(1..=4) {
+        let position = random.range(0..=output.len());
+
+        match random.below(7) {
+            0 if position < output.len() => output[position] = random.below(256) as u8,
+            1 => {
+                let token = random.choose(TOKENS);
+                output.splice(position..position, token.iter().copied());
+            }
+            2 => {
+                let end = (position + random.range(1..=16)).min(output.len());
+                output.drain(position..end);
+            }
+            3 => {
+                let end = (position + random.range(1..=64)).min(output.len());
+                let chunk = output[position..end].to_vec();
+                let times = if random.one_in(4) {
+                    random.range(1..=1000)
+                } else {
+                    random.range(1..=4)
+                };
+                let repeated = chunk.repeat(times);
+                output.splice(end..end, repeated);
+            }
+            4 => replace_number(&mut output, position, random),
+            5 => output.truncate(position),
+            _ => {
+                // Swapping two lines around breaks anything that expects them in order.
+                let lines = output
+                    .split_inclusive(|byte| *byte == b'\n')
+                    .map(<[u8]>::to_vec)
+                    .collect::<Vec<Vec<u8>>>();
+                if !lines.is_empty() {
+                    let mut lines = lines;
+                    let (first, second) = (random.below(lines.len()), random.below(lines.len()));
+                    lines.swap(first, second);
+                    output = lines.concat();
+                }
+            }
+        }
+    }
+
+    output.truncate(MAX_INPUT_LENGTH);
+    output
+}
+
+// Swaps the run of digits at or after `position` for one of the edge-case numbers.
+fn replace_number(output: &mut Vec<u8>, position: usize, random: &mut Random) {
+    let Some(start) = (position..output.len()).find(|&index| output[index].is_ascii_digit()) else {
+        return;
+    };
+    let end = (start..output.len())
+        .find(|&index| !output[index].is_ascii_digit())
+        .unwrap_or(output.len());
+
+    output.splice(start..end, random.choose(NUMBERS).iter().copied());
+}
+
+// Cuts chunks out of `input`, halving their size each time none of them can go, for as long as
+// `still_fails` says what's left causes the same failure.
+pub fn minimize<F: FnMut(&[u8]) -> bool>(input: &[u8], mut still_fails: F) -> Vec<u8> {
+    let mut current = input.to_vec();
+    let mut chunk = current.len().div_ceil(2);
+    let mut attempts = 0;
+
+    while chunk > 0 && attempts < MAX_MINIMIZE_ATTEMPTS {
+        let mut start = 0;
+
+        while start < current.len() && attempts < MAX_MINIMIZE_ATTEMPTS {
+            let end = (start + chunk).min(current.len());
+            let mut candidate = current[..start].to_vec();
+            candidate.extend_from_slice(&current[end..]);
+            attempts += 1;
+
+            if still_fails(&candidate) {
+                current = candidate;
+            } else {
+                start += chunk;
+            }
+        }
+
+        chunk /= 2;
+    }
+
+    current
+}
+
+// Feeds `runs` mutations of the seed inputs to `worker`, and minimizes the first input that
+// causes each distinct failure.
+pub fn fuzz(
+    worker: &mut Worker,
+    seeds: &[Vec<u8>],
+    runs: usize,
+    random: &mut Random,
+) -> io::Result<Vec<Finding>> {
+    let mut findings = Vec::<Finding>::new();
+
+    // The seeds themselves go first, since there's no point mutating them if they already fail.
+    let inputs = seeds.iter().cloned().chain((0..runs).map(|_| {
+        let seed = random.choose(seeds);
+        mutate(seed, random)
+    }));
+
+    for input in inputs {
+        let Some(failure) = worker.run(&input)? else {
+            continue;
+        };
+        if findings
+            .iter()
+            .any(|finding| finding.failure.is_same_as(&failure))
+        {
+            continue;
+        }
+
+        let minimized = minimize(
+            &input,
+            |candidate| matches!(worker.run(candidate), Ok(Some(other)) if other.is_same_as(&failure)),
+        );
+        // The failure that's kept is the minimized input's, so its message matches what's saved.
+        let failure = worker.run(&minimized)?.unwrap_or(failure);
+
+        findings.push(Finding {
+            failure,
+            input: minimized,
+            original_length: input.len(),
+        });
+    }
+
+    Ok(findings)
+}
+
+// A child process that runs one part's parser on whatever it's sent. A stack overflow takes the
+// whole process down and a hung thread can't be stopped, so both are only survivable from the
+// outside. A new child is started whenever the last one died or was killed.
+pub struct Worker {
+    program: PathBuf,
+    args: Vec<String>,
+    timeout: Duration,
+    running: Option<Running>,
+}
+
+struct Running {
+    child: Child,
+    stdin: ChildStdin,
+    replies: Receiver<String>,
+}
+
+impl Worker {
+    // `program` run with `args` has to end up in `serve_worker`.
+    pub fn new(program: PathBuf, args: Vec<String>, timeout: Duration) -> Self {
+        Self {
+            program,
+            args,
+            timeout,
+            running: None,
+        }
+    }
+
+    pub fn run(&mut self, input: &[u8]) -> io::Result<Option<Failure>> {
+        if self.running.is_none() {
+            self.running = Some(self.start()?);
+        }
+        let running = self.running.as_mut().expect("started above");
+
+        // Writing fails if the child has already died, and then so will reading the reply.
+        let length = (input.len() as u32).to_le_bytes();
+        let _ = running
+            .stdin
+            .write_all(&length)
+            .and_then(|()| running.stdin.write_all(input))
+            .and_then(|()| running.stdin.flush());
+
+        match running.replies.recv_timeout(self.timeout) {
+            Ok(reply) => Ok(parse_reply(&reply)),
+            Err(RecvTimeoutError::Timeout) => {
+                self.stop();
+                Ok(Some(Failure::Hang))
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                let mut running = self.running.take().expect("started above");
+                let status = running.child.wait()?;
+                let mut stderr = String::new();
+                if let Some(mut pipe) = running.child.stderr.take() {
+                    pipe.read_to_string(&mut stderr)?;
+                }
+
+                if stderr.contains("overflowed its stack") {
+                    Ok(Some(Failure::StackOverflow))
+                } else {
+                    Ok(Some(Failure::Crash(status.to_string())))
+                }
+            }
+        }
+    }
+
+    fn start(&self) -> io::Result<Running> {
+        // Nothing reads the worker's stderr until it exits, so a traced parser could fill the pipe
+        // and stall.
+        let mut child = Command::new(&self.program)
+            .args(&self.args)
+            .env_remove(trace::LEVEL_VARIABLE)
+            .env_remove(trace::FILE_VARIABLE)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = child.stdout.take().expect("stdout is piped");
+
+        // Reading happens on its own thread so that waiting for a reply can time out.
+        let (sender, replies) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else { break };
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Running {
+            child,
+            stdin,
+            replies,
+        })
+    }
+
+    fn stop(&mut self) {
+        if let Some(mut running) = self.running.take() {
+            let _ = running.child.kill();
+            let _ = running.child.wait();
+        }
+    }
+}
+
+impl Drop for Worker {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+// Replies are one line each: "ok", or "panic", the location and the message separated by tabs.
+fn parse_reply(reply: &str) -> Option<Failure> {
+    let mut fields = reply.splitn(3, '\t');
+
+    match fields.next() {
+        Some("panic") => Some(Failure::Panic(
+            String::from(fields.next().unwrap_or_default()),
+            String::from(fields.next().unwrap_or_default()),
+        )),
+        _ => None,
+    }
+}
+
+static LAST_PANIC: Mutex<Option<(String, String)>> = Mutex::new(None);
+
+// The child side of `Worker`: reads length-prefixed inputs from stdin until it closes, parses each
+// with `solver` and replies on stdout. Errors are what a parser should give for bad input, so only
+// panics are reported.
+pub fn serve_worker(solver: &dyn Solver) -> io::Result<()> {
+    // Panics are reported through the reply instead of being printed.
+    panic::set_hook(Box::new(|info| {
+        let location = info
+            .location()
+            .map_or_else(String::new, |location| location.to_string());
+        let message = if let Some(message) = info.payload().downcast_ref::<&str>() {
+            String::from(*message)
+        } else if let Some(message) = info.payload().downcast_ref::<String>() {
+            message.clone()
+        } else {
+            String::new()
+        };
+
+        if let Ok(mut last_panic) = LAST_PANIC.lock() {
+            *last_panic = Some((location, message.replace(['\n', '\t'], " ")));
+        }
+    }));
+
+    let mut stdin = io::stdin().lock();
+    let mut stdout = io::stdout().lock();
+
+    loop {
+        let mut length = [0; 4];
+        match stdin.read_exact(&mut length) {
+            Ok(()) => {}
+            Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+            Err(error) => return Err(error),
+        }
+        let mut input = vec![0; u32::from_le_bytes(length) as usize];
+        stdin.read_exact(&mut input)?;
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            // Dropping what was parsed counts too, since deep structures can overflow there.
+            drop(solver.parse(&mut input.as_slice()));
+        }));
+
+        match result {
+            Ok(()) => writeln!(stdout, "ok")?,
+            Err(_) => {
+                let (location, message) = LAST_PANIC
+                    .lock()
+                    .ok()
+                    .and_then(|mut last_panic| last_panic.take())
+                    .unwrap_or_default();
+                writeln!(stdout, "panic\t{}\t{}", location, message)?;
+            }
+        }
+        stdout.flush()?;
+    }
+}
@@ -0,0 +1,513 @@
+use std::io::{self, Write};
+
+use crate::random::Random;
+
+// Writes a random puzzle input of roughly the given size.
+pub type Generator = fn(usize, &mut Random, &mut dyn Write) -> io::Result<()>;
+
+// Every day that can have inputs generated, as (day, what the size counts, generator). Whatever the
+// seed, the inputs are ones the day's parser accepts and both parts can solve.
+pub const GENERATORS: &[(usize, &str, Generator)] = &[
+    (1, "elves", day1),
+    (2, "rounds", day2),
+    (3, "rucksacks, rounded up to a group of three", day3),
+    (4, "pairs of assignments", day4),
+    (5, "moves", day5),
+    (6, "characters", day6),
+    (7, "files and directories", day7),
+    (8, "trees along each side of the forest", day8),
+    (9, "moves", day9),
+    (10, "instructions", day10),
+    (11, "monkeys, at least 2", day11),
+    (12, "squares along each side of the map, at least 14", day12),
+    (13, "pairs of packets", day13),
+];
+
+pub fn generator(day: usize) -> Option<Generator> {
+    GENERATORS
+        .iter()
+        .find(|(generator_day, _, _)| *generator_day == day)
+        .map(|(_, _, generator)| *generator)
+}
+
+const LOWERCASE: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+fn random_name(random: &mut Random, length: usize) -> String {
+    (0..length)
+        .map(|_| *random.choose(LOWERCASE) as char)
+        .collect()
+}
+
+fn day1(elves: usize, random: &mut Random, writer: &mut dyn Write) -> io::Result<()> {
+    for elf in 0..elves {
+        if elf > 0 {
+            writeln!(writer)?;
+        }
+
+        for _ in 0..random.range(1..=15) {
+            writeln!(writer, "{}", random.range(1000..=60000))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn day2(rounds: usize, random: &mut Random, writer: &mut dyn Write) -> io::Result<()> {
+    for _ in 0..rounds {
+        writeln!(
+            writer,
+            "{} {}",
+            random.choose(&['A', 'B', 'C']),
+            random.choose(&['X', 'Y', 'Z'])
+        )?;
+    }
+
+    Ok(())
+}
+
+// Each elf in a group gets its own share of the item types, so the badge is the only item all three
+// carry, and the one item in both of a rucksack's compartments is the only one they share.
+fn day3(rucksacks: usize, random: &mut Random, writer: &mut dyn Write) -> io::Result<()> {
+    let item_types = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+    for _ in 0..rucksacks.div_ceil(3) {
+        let mut items = item_types.to_vec();
+        random.shuffle(&mut items);
+        let badge = items.pop().unwrap();
+
+        for share in items.chunks(items.len() / 3) {
+            let (shared, rest) = share.split_first().unwrap();
+            let (left_only, right_only) = rest.split_at(rest.len() / 2);
+            let compartment_size = random.range(2..=16);
+
+            let mut left = vec![badge, *shared];
+            let mut right = vec![*shared];
+            left.extend((2..compartment_size).map(|_| *random.choose(left_only)));
+            right.extend((1..compartment_size).map(|_| *random.choose(right_only)));
+            random.shuffle(&mut left);
+            random.shuffle(&mut right);
+
+            writer.write_all(&left)?;
+            writer.write_all(&right)?;
+            writeln!(writer)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn day4(pairs: usize, random: &mut Random, writer: &mut dyn Write) -> io::Result<()> {
+    let assignment = |random: &mut Random| {
+        let start = random.range(1..=99);
+        (start, random.range(start..=99))
+    };
+
+    for _ in 0..pairs {
+        let (first_start, first_end) = assignment(random);
+        let (second_start, second_end) = assignment(random);
+        writeln!(
+            writer,
+            "{}-{},{}-{}",
+            first_start, first_end, second_start, second_end
+        )?;
+    }
+
+    Ok(())
+}
+
+// The crates are only tracked by how many are on each stack, which is all it takes to make sure
+// every move has enough to take.
+fn day5(moves: usize, random: &mut Random, writer: &mut dyn Write) -> io::Result<()> {
+    const STACKS: usize = 9;
+
+    let mut heights = (0..STACKS)
+        .map(|_| random.range(1..=8))
+        .collect::<Vec<usize>>();
+    let tallest = *heights.iter().max().unwrap();
+
+    for level in (0..tallest).rev() {
+        let row = heights
+            .iter()
+            .map(|height| {
+                if level < *height {
+                    format!("[{}]", (b'A' + random.below(26) as u8) as char)
+                } else {
+                    String::from("   ")
+                }
+            })
+            .collect::<Vec<String>>();
+        writeln!(writer, "{}", row.join(" "))?;
+    }
+
+    let numbers = (1..=STACKS)
+        .map(|number| format!(" {} ", number))
+        .collect::<Vec<String>>();
+    writeln!(writer, "{}\n", numbers.join(" "))?;
+
+    for _ in 0..moves {
+        let from = loop {
+            let from = random.below(STACKS);
+            if heights[from] > 0 {
+                break from;
+            }
+        };
+        let to = (from + random.range(1..=STACKS - 1)) % STACKS;
+        let quantity = random.range(1..=heights[from]);
+
+        heights[from] -= quantity;
+        heights[to] += quantity;
+        writeln!(writer, "move {} from {} to {}", quantity, from + 1, to + 1)?;
+    }
+
+    Ok(())
+}
+
+// Three letters can never make a marker, so the search has to go all the way to the end, where
+// there's one long enough for either part.
+fn day6(characters: usize, random: &mut Random, writer: &mut dyn Write) -> io::Result<()> {
+    const MARKER: &[u8] = b"defghijklmnopq";
+
+    let filler = characters.saturating_sub(MARKER.len());
+    for _ in 0..filler {
+        writer.write_all(&[*random.choose(b"abc")])?;
+    }
+
+    writer.write_all(MARKER)?;
+    writeln!(writer)
+}
+
+// A depth-first walk that lists each directory and then visits its subdirectories in turn, like the
+// puzzle's transcript. The nesting is kept shallow, since the tree is built and dropped recursively.
+fn day7(entries: usize, random: &mut Random, writer: &mut dyn Write) -> io::Result<()> {
+    const MAX_DEPTH: usize = 20;
+
+    let mut remaining = entries;
+    // The subdirectories still to visit in every directory from the root down to the current one.
+    let mut unvisited = Vec::<Vec<String>>::new();
+
+    writeln!(writer, "$ cd /")?;
+
+    loop {
+        writeln!(writer, "$ ls")?;
+
+        let count = random.range(1..=10).min(remaining);
+        remaining -= count;
+
+        let mut names = Vec::<String>::with_capacity(count);
+        let mut directories = Vec::<String>::new();
+        while names.len() < count {
+            let length = random.range(1..=8);
+            let name = random_name(random, length);
+            if names.contains(&name) {
+                continue;
+            }
+
+            if unvisited.len() < MAX_DEPTH && random.one_in(3) {
+                writeln!(writer, "dir {}", name)?;
+                directories.push(name.clone());
+            } else if random.one_in(2) {
+                writeln!(writer, "{} {}", random.range(1..=300000), name)?;
+            } else {
+                let extension = random_name(random, 3);
+                writeln!(
+                    writer,
+                    "{} {}.{}",
+                    random.range(1..=300000),
+                    name,
+                    extension
+                )?;
+            }
+            names.push(name);
+        }
+
+        unvisited.push(directories);
+
+        // Climb back up until there's somewhere left to go.
+        loop {
+            match unvisited.last_mut().and_then(Vec::pop) {
+                Some(directory) => {
+                    writeln!(writer, "$ cd {}", directory)?;
+                    break;
+                }
+                None => {
+                    unvisited.pop();
+                    if unvisited.is_empty() {
+                        return Ok(());
+                    }
+                    writeln!(writer, "$ cd ..")?;
+                }
+            }
+        }
+    }
+}
+
+fn day8(side: usize, random: &mut Random, writer: &mut dyn Write) -> io::Result<()> {
+    for _ in 0..side {
+        let row = (0..side)
+            .map(|_| b'0' + random.below(10) as u8)
+            .collect::<Vec<u8>>();
+        writer.write_all(&row)?;
+        writeln!(writer)?;
+    }
+
+    Ok(())
+}
+
+// Most moves are short, like the puzzle's, but one in ten goes anywhere up to `LONGEST_MOVE`, far
+// past what stepping through a square at a time could manage. The first is always that long, so
+// every input has one.
+pub const LONGEST_MOVE: usize = u32::MAX as usize;
+
+fn day9(moves: usize, random: &mut Random, writer: &mut dyn Write) -> io::Result<()> {
+    for index in 0..moves {
+        let distance = if index == 0 {
+            LONGEST_MOVE
+        } else if random.one_in(10) {
+            random.range(1..=LONGEST_MOVE)
+        } else {
+            random.range(1..=20)
+        };
+
+        writeln!(
+            writer,
+            "{} {}",
+            random.choose(&['R', 'U', 'L', 'D']),
+            distance
+        )?;
+    }
+
+    Ok(())
+}
+
+// The register is steered back towards the screen, so the sprite is drawn somewhere sensible.
+fn day10(instructions: usize, random: &mut Random, writer: &mut dyn Write) -> io::Result<()> {
+    let mut register: isize = 1;
+
+    for _ in 0..instructions {
+        if random.one_in(3) {
+            writeln!(writer, "noop")?;
+            continue;
+        }
+
+        let target = random.range(0..=39) as isize;
+        let amount = (target - register).clamp(-20, 20);
+        register += amount;
+        writeln!(writer, "addx {}", amount)?;
+    }
+
+    Ok(())
+}
+
+enum Operation {
+    Square,
+    Multiply(usize),
+    Add(usize),
+}
+
+struct Monkey {
+    items: Vec<usize>,
+    operation: Operation,
+    divisor: usize,
+    targets: [usize; 2],
+}
+
+// Plays part 1's rounds, where worry levels are kept exactly, and returns the first monkey that
+// pushes one past what a usize can hold.
+fn first_overflowing_monkey(monkeys: &[Monkey]) -> Option<usize> {
+    let mut items = monkeys
+        .iter()
+        .map(|monkey| monkey.items.clone())
+        .collect::<Vec<Vec<usize>>>();
+
+    for _ in 0..20 {
+        for (index, monkey) in monkeys.iter().enumerate() {
+            for item in std::mem::take(&mut items[index]) {
+                let item = item as u128;
+                let worry_level = match monkey.operation {
+                    Operation::Square => item * item,
+                    Operation::Multiply(factor) => item * factor as u128,
+                    Operation::Add(amount) => item + amount as u128,
+                } / 3;
+
+                let Ok(worry_level) = usize::try_from(worry_level) else {
+                    return Some(index);
+                };
+                let target = monkey.targets[usize::from(worry_level % monkey.divisor != 0)];
+                items[target].push(worry_level);
+            }
+        }
+    }
+
+    None
+}
+
+// Worry levels are kept modulo the product of every monkey's divisor, which has to fit in a usize,
+// so once the primes would make it overflow the remaining monkeys test divisibility by 1. Part 1
+// keeps the levels exactly instead, so any monkey that makes them overflow is switched to adding,
+// which can't.
+fn day11(monkeys: usize, random: &mut Random, writer: &mut dyn Write) -> io::Result<()> {
+    const PRIMES: [usize; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+
+    let count = monkeys.max(2);
+    let mut product: usize = 1;
+
+    let mut monkeys = (0..count)
+        .map(|monkey| {
+            let items = (0..random.range(0..=8))
+                .map(|_| random.range(50..=99))
+                .collect();
+
+            let operation = match random.below(8) {
+                0 => Operation::Square,
+                1..=3 => Operation::Multiply(random.range(2..=19)),
+                _ => Operation::Add(random.range(1..=9)),
+            };
+
+            let prime = *random.choose(&PRIMES);
+            let divisor = match product.checked_mul(prime) {
+                Some(new_product) => {
+                    product = new_product;
+                    prime
+                }
+                None => 1,
+            };
+
+            // Monkeys never throw to themselves.
+            let mut other_monkey = || (monkey + random.range(1..=count - 1)) % count;
+            let targets = [other_monkey(), other_monkey()];
+
+            Monkey {
+                items,
+                operation,
+                divisor,
+                targets,
+            }
+        })
+        .collect::<Vec<Monkey>>();
+
+    while let Some(index) = first_overflowing_monkey(&monkeys) {
+        monkeys[index].operation = Operation::Add(random.range(1..=9));
+    }
+
+    for (index, monkey) in monkeys.iter().enumerate() {
+        if index > 0 {
+            writeln!(writer)?;
+        }
+
+        let items = monkey
+            .items
+            .iter()
+            .map(usize::to_string)
+            .collect::<Vec<String>>();
+        let operation = match monkey.operation {
+            Operation::Square => String::from("old * old"),
+            Operation::Multiply(factor) => format!("old * {}", factor),
+            Operation::Add(amount) => format!("old + {}", amount),
+        };
+
+        writeln!(writer, "Monkey {}:", index)?;
+        writeln!(writer, "  Starting items: {}", items.join(", "))?;
+        writeln!(writer, "  Operation: new = {}", operation)?;
+        writeln!(writer, "  Test: divisible by {}", monkey.divisor)?;
+        writeln!(writer, "    If true: throw to monkey {}", monkey.targets[0])?;
+        writeln!(
+            writer,
+            "    If false: throw to monkey {}",
+            monkey.targets[1]
+        )?;
+    }
+
+    Ok(())
+}
+
+// Random heights everywhere, apart from a staircase of right and down steps from S in the top left
+// to E in the bottom right that never climbs more than one at a time. The staircase needs at least
+// 25 steps to climb from 'a' to 'z', which is why the map can't be any smaller.
+fn day12(side: usize, random: &mut Random, writer: &mut dyn Write) -> io::Result<()> {
+    let side = side.max(14);
+
+    let mut map = (0..side)
+        .map(|_| {
+            (0..side)
+                .map(|_| *random.choose(LOWERCASE))
+                .collect::<Vec<u8>>()
+        })
+        .collect::<Vec<Vec<u8>>>();
+
+    let steps = 2 * (side - 1);
+    let (mut x, mut y) = (0, 0);
+    for step in 0..steps {
+        map[y][x] = b'a' + (25 * step / steps) as u8;
+
+        if y == side - 1 || (x < side - 1 && random.one_in(2)) {
+            x += 1;
+        } else {
+            y += 1;
+        }
+    }
+
+    map[0][0] = b'S';
+    map[side - 1][side - 1] = b'E';
+
+    for row in map {
+        writer.write_all(&row)?;
+        writeln!(writer)?;
+    }
+
+    Ok(())
+}
+
+// Mostly small, shallow packets like the puzzle's, plus one pair nested as deep as the size allows
+// (up to 500) that only differs at the very bottom, so comparing them goes all the way down.
+fn day13(pairs: usize, random: &mut Random, writer: &mut dyn Write) -> io::Result<()> {
+    const DEEPEST: usize = 500;
+
+    let deep_pair = random.below(pairs.max(1));
+    let depth = pairs.clamp(1, DEEPEST);
+
+    for pair in 0..pairs {
+        if pair > 0 {
+            writeln!(writer)?;
+        }
+
+        if pair == deep_pair {
+            let value = random.range(0..=9);
+            for right_value in [value, value + 1] {
+                writeln!(
+                    writer,
+                    "{}{}{}",
+                    "[".repeat(depth),
+                    right_value,
+                    "]".repeat(depth)
+                )?;
+            }
+            continue;
+        }
+
+        for _ in 0..2 {
+            let mut packet = String::new();
+            write_packet(&mut packet, random, 4);
+            writeln!(writer, "{}", packet)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_packet(packet: &mut String, random: &mut Random, depth: usize) {
+    packet.push('[');
+
+    for index in 0..random.range(0..=5) {
+        if index > 0 {
+            packet.push(',');
+        }
+
+        if depth > 1 && random.one_in(3) {
+            write_packet(packet, random, depth - 1);
+        } else {
+            packet.push_str(&random.range(0..=10).to_string());
+        }
+    }
+
+    packet.push(']');
+}
@@ -0,0 +1,228 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::grid::Position;
+
+// Like the grid, y counts down, so up is negative y and turning right is clockwise on the screen.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Vector2 {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Vector2 {
+    // The eight steps to the squares around a point, row by row.
+    pub const AROUND: [Self; 8] = [
+        Self::new(-1, -1),
+        Self::new(0, -1),
+        Self::new(1, -1),
+        Self::new(-1, 0),
+        Self::new(1, 0),
+        Self::new(-1, 1),
+        Self::new(0, 1),
+        Self::new(1, 1),
+    ];
+
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    pub const fn zero() -> Self {
+        Self::new(0, 0)
+    }
+
+    // How many orthogonal steps it takes to get here from the origin.
+    pub fn manhattan(&self) -> usize {
+        self.x.unsigned_abs() + self.y.unsigned_abs()
+    }
+
+    // How many steps it takes when diagonal steps are allowed too.
+    pub fn chebyshev(&self) -> usize {
+        self.x.unsigned_abs().max(self.y.unsigned_abs())
+    }
+
+    pub fn euclidean(&self) -> f64 {
+        (self.x as f64).hypot(self.y as f64)
+    }
+
+    // Each coordinate squashed to -1, 0 or 1, which is a single step in this one's direction.
+    pub fn signum(&self) -> Self {
+        Self::new(self.x.signum(), self.y.signum())
+    }
+
+    pub fn rotated_left(&self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+
+    pub fn rotated_right(&self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    // Where this is on a grid, if it's not off the top or the left.
+    pub fn to_position(&self) -> Option<Position> {
+        Some((usize::try_from(self.x).ok()?, usize::try_from(self.y).ok()?))
+    }
+}
+
+impl From<Position> for Vector2 {
+    // Grids that don't fit an `isize` couldn't fit in memory either.
+    fn from((x, y): Position) -> Self {
+        Self::new(x as isize, y as isize)
+    }
+}
+
+impl Add for Vector2 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Vector2 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Vector2 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Vector2 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Vector2 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<isize> for Vector2 {
+    type Output = Self;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl MulAssign<isize> for Vector2 {
+    fn mul_assign(&mut self, rhs: isize) {
+        *self = *self * rhs;
+    }
+}
+
+impl fmt::Display for Vector2 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Direction {
+    Up,
+    Left,
+    Down,
+    Right,
+}
+
+impl Direction {
+    // Counterclockwise, starting from up.
+    pub const ALL: [Self; 4] = [Self::Up, Self::Left, Self::Down, Self::Right];
+
+    // A single step this way.
+    pub fn vector(self) -> Vector2 {
+        match self {
+            Self::Up => Vector2::new(0, -1),
+            Self::Left => Vector2::new(-1, 0),
+            Self::Down => Vector2::new(0, 1),
+            Self::Right => Vector2::new(1, 0),
+        }
+    }
+
+    pub fn turned_left(self) -> Self {
+        match self {
+            Self::Up => Self::Left,
+            Self::Left => Self::Down,
+            Self::Down => Self::Right,
+            Self::Right => Self::Up,
+        }
+    }
+
+    pub fn turned_right(self) -> Self {
+        self.turned_left().opposite()
+    }
+
+    pub fn opposite(self) -> Self {
+        self.turned_left().turned_left()
+    }
+}
+
+impl From<Direction> for Vector2 {
+    fn from(direction: Direction) -> Self {
+        direction.vector()
+    }
+}
+
+// The smallest rectangle holding every point it's been given, with both corners included.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BoundingBox {
+    pub min: Vector2,
+    pub max: Vector2,
+}
+
+impl BoundingBox {
+    pub fn new(point: Vector2) -> Self {
+        Self {
+            min: point,
+            max: point,
+        }
+    }
+
+    // `None` if there aren't any points to go around.
+    pub fn around<I: IntoIterator<Item = Vector2>>(points: I) -> Option<Self> {
+        let mut points = points.into_iter();
+        let mut bounds = Self::new(points.next()?);
+
+        for point in points {
+            bounds.include(point);
+        }
+
+        Some(bounds)
+    }
+
+    pub fn include(&mut self, point: Vector2) {
+        self.min = Vector2::new(self.min.x.min(point.x), self.min.y.min(point.y));
+        self.max = Vector2::new(self.max.x.max(point.x), self.max.y.max(point.y));
+    }
+
+    pub fn width(&self) -> usize {
+        self.max.x.abs_diff(self.min.x) + 1
+    }
+
+    pub fn height(&self) -> usize {
+        self.max.y.abs_diff(self.min.y) + 1
+    }
+
+    pub fn contains(&self, point: Vector2) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    // Where `point` lands on a grid the size of this box, with `min` at the top left.
+    pub fn to_position(&self, point: Vector2) -> Option<Position> {
+        if self.contains(point) {
+            (point - self.min).to_position()
+        } else {
+            None
+        }
+    }
+}
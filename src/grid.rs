@@ -0,0 +1,228 @@
+use std::fmt;
+use std::io::BufRead;
+use std::ops::{Index, IndexMut};
+
+use crate::geometry::{Direction, Vector2};
+use crate::parse;
+use crate::{Problem, Result};
+
+// Positions are (x, y), with y counting down from the top row like the lines of an input. Moving
+// between them is done with the same `Vector2` steps as everything else spatial.
+pub type Position = (usize, usize);
+
+// A rectangle of cells, kept in one flat row-major `Vec` rather than a `Vec` per row.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    // Panics if there aren't exactly `width * height` cells.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "a grid needs width * height cells"
+        );
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(width, height, vec![value; width * height])
+    }
+
+    // Reads one row per line, turning each byte into a cell with `cell`, which also gets the
+    // byte's position. A byte it returns `None` for is an error, described by `expected`, and so
+    // is a row that isn't as wide as the first.
+    pub fn from_reader<R, F>(
+        reader: R,
+        day: usize,
+        expected: &'static str,
+        mut cell: F,
+    ) -> Result<Self>
+    where
+        R: BufRead,
+        F: FnMut(Position, u8) -> Option<T>,
+    {
+        let mut cells = Vec::<T>::new();
+        let mut width = None;
+        let mut height = 0;
+
+        parse::for_each_line(reader, day, |line| {
+            if width.is_some_and(|width| line.text.len() != width) {
+                return Err(line.error(Problem::RowLength(width.unwrap_or_default())));
+            }
+            width = Some(line.text.len());
+
+            for (x, byte) in line.text.bytes().enumerate() {
+                match cell((x, height), byte) {
+                    Some(value) => cells.push(value),
+                    None => return Err(line.error_at_offset(x, 1, Problem::Expected(expected))),
+                }
+            }
+
+            height += 1;
+            Ok(())
+        })?;
+
+        Ok(Self::new(width.unwrap_or(0), height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, (x, y): Position) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.contains(position)
+            .then(|| &self.cells[self.index_of(position)])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        if self.contains(position) {
+            let index = self.index_of(position);
+            Some(&mut self.cells[index])
+        } else {
+            None
+        }
+    }
+
+    fn index_of(&self, (x, y): Position) -> usize {
+        y * self.width + x
+    }
+
+    // Every cell, a row at a time.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    // Every position, in the same order as `iter`.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        (0..self.height).map(move |y| &self[(x, y)])
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    // The position one `step` away, if it's still on the grid.
+    pub fn step(&self, (x, y): Position, step: Vector2) -> Option<Position> {
+        let position = (x.checked_add_signed(step.x)?, y.checked_add_signed(step.y)?);
+        self.contains(position).then_some(position)
+    }
+
+    // Every position taking `step` after step from `from`, not counting `from` itself, until the
+    // edge of the grid.
+    pub fn ray(&self, from: Position, step: Vector2) -> impl Iterator<Item = Position> + '_ {
+        let mut position = Some(from);
+
+        std::iter::from_fn(move || {
+            position = self.step(position?, step);
+            position
+        })
+    }
+
+    // The up to four positions straight above, below and beside `position`.
+    pub fn neighbours(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(position, direction.vector()))
+    }
+
+    // The up to eight positions around `position`, diagonals included.
+    pub fn neighbours_with_diagonals(
+        &self,
+        position: Position,
+    ) -> impl Iterator<Item = Position> + '_ {
+        Vector2::AROUND
+            .into_iter()
+            .filter_map(move |step| self.step(position, step))
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, function: F) -> Grid<U> {
+        Grid::new(
+            self.width,
+            self.height,
+            self.cells.iter().map(function).collect(),
+        )
+    }
+
+    // Rows become columns and columns become rows.
+    pub fn transposed(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .flat_map(|x| self.column(x).cloned())
+            .collect();
+
+        Self::new(self.height, self.width, cells)
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        assert!(self.contains(position), "{:?} is off the grid", position);
+        &self.cells[self.index_of(position)]
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        assert!(self.contains(position), "{:?} is off the grid", position);
+        let index = self.index_of(position);
+        &mut self.cells[index]
+    }
+}
+
+// Each cell's own `Display` one after the other, with a line per row.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,218 @@
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use crate::grid::Grid;
+use crate::{day10, day12, day8, day9, Error, Problem, Result};
+
+// Pictures are grids of colours, one per square of the puzzle, which get scaled up when they're
+// written out so that a single square is big enough to see.
+pub type Picture = Grid<Color>;
+
+// Reads a day's puzzle input and draws what solving it looks like.
+pub type Painter = fn(&mut dyn BufRead) -> Result<Picture>;
+
+// Every day that can be drawn, as (day, what the picture shows, painter).
+pub const PAINTERS: &[(usize, &str, Painter)] = &[
+    (
+        8,
+        "tree heights, with the trees visible from outside the forest in green",
+        day8::paint,
+    ),
+    (
+        9,
+        "every square the tails visited, blue for 2 knots, orange for 10 and white for both",
+        day9::paint,
+    ),
+    (10, "the CRT, one pixel per square", day10::paint),
+    (
+        12,
+        "the heightmap, with the shortest path from the start in red",
+        day12::paint,
+    ),
+];
+
+// Four million squares is far more than any real puzzle needs, but a made-up input can easily ask
+// for a picture that wouldn't fit in memory.
+pub const MAX_SQUARES: usize = 1 << 22;
+
+// Scaled up, a picture can still only be this many pixels, which is a 200 MB PPM.
+pub const MAX_PIXELS: usize = 1 << 26;
+
+// A blank picture, as long as it isn't too big.
+pub fn canvas(day: usize, width: usize, height: usize) -> Result<Picture> {
+    match width.checked_mul(height) {
+        Some(squares) if squares <= MAX_SQUARES => Ok(Grid::filled(width, height, Color::BLACK)),
+        _ => Err(Error::puzzle(day, Problem::TooBigToDraw(width, height))),
+    }
+}
+
+// The biggest scale, up to `scale`, that keeps `picture` within `MAX_PIXELS`.
+pub fn fitting_scale(picture: &Picture, scale: usize) -> usize {
+    let squares = (picture.width() * picture.height()).max(1);
+
+    (1..=scale)
+        .rev()
+        .find(|scale| squares * scale * scale <= MAX_PIXELS)
+        .unwrap_or(1)
+}
+
+pub fn painter(day: usize) -> Option<Painter> {
+    PAINTERS
+        .iter()
+        .find(|(painter_day, _, _)| *painter_day == day)
+        .map(|(_, _, painter)| *painter)
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Color {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+impl Color {
+    pub const BLACK: Self = Self::new(0, 0, 0);
+    pub const WHITE: Self = Self::new(255, 255, 255);
+    pub const RED: Self = Self::new(220, 40, 40);
+
+    pub const fn new(red: u8, green: u8, blue: u8) -> Self {
+        Self { red, green, blue }
+    }
+
+    pub const fn gray(level: u8) -> Self {
+        Self::new(level, level, level)
+    }
+
+    // How bright it looks, for writing it out in grayscale.
+    pub fn luma(&self) -> u8 {
+        ((self.red as u32 * 299 + self.green as u32 * 587 + self.blue as u32 * 114) / 1000) as u8
+    }
+
+    // Part of the way from `self` to `other`, where `value` out of `max` is how far.
+    pub fn towards(&self, other: Self, value: usize, max: usize) -> Self {
+        let mix = |from: u8, to: u8| {
+            let (from, to) = (from as usize, to as usize);
+            let value = value.min(max);
+            ((from * (max - value) + to * value) / max.max(1)) as u8
+        };
+
+        Self::new(
+            mix(self.red, other.red),
+            mix(self.green, other.green),
+            mix(self.blue, other.blue),
+        )
+    }
+
+    fn hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.red, self.green, self.blue)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    // Binary colour and grayscale netpbm images, which almost any image viewer can open.
+    Ppm,
+    Pgm,
+    Svg,
+}
+
+impl Format {
+    // Going by the file's extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+
+        match extension.as_str() {
+            "ppm" => Some(Self::Ppm),
+            "pgm" => Some(Self::Pgm),
+            "svg" => Some(Self::Svg),
+            _ => None,
+        }
+    }
+}
+
+// Writes `picture` with every square `scale` pixels across.
+pub fn write(
+    writer: &mut dyn Write,
+    format: Format,
+    picture: &Picture,
+    scale: usize,
+) -> io::Result<()> {
+    match format {
+        Format::Ppm => write_netpbm(writer, "P6", picture, scale, |color| {
+            vec![color.red, color.green, color.blue]
+        }),
+        Format::Pgm => write_netpbm(writer, "P5", picture, scale, |color| vec![color.luma()]),
+        Format::Svg => write_svg(writer, picture, scale),
+    }
+}
+
+// The header's the magic number, the size in pixels and the biggest value a channel can have,
+// followed by every pixel's channels as bytes, row by row.
+fn write_netpbm<F>(
+    writer: &mut dyn Write,
+    magic: &str,
+    picture: &Picture,
+    scale: usize,
+    channels: F,
+) -> io::Result<()>
+where
+    F: Fn(&Color) -> Vec<u8>,
+{
+    write!(
+        writer,
+        "{}\n{} {}\n255\n",
+        magic,
+        picture.width() * scale,
+        picture.height() * scale
+    )?;
+
+    for row in picture.rows() {
+        let mut line = Vec::<u8>::new();
+        for color in row {
+            let pixel = channels(color);
+            for _ in 0..scale {
+                line.extend_from_slice(&pixel);
+            }
+        }
+
+        for _ in 0..scale {
+            writer.write_all(&line)?;
+        }
+    }
+
+    Ok(())
+}
+
+// Runs of the same colour along a row share one rectangle, which keeps big pictures to a size
+// browsers are happy to open.
+fn write_svg(writer: &mut dyn Write, picture: &Picture, scale: usize) -> io::Result<()> {
+    let (width, height) = (picture.width() * scale, picture.height() * scale);
+    writeln!(
+        writer,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\" \
+         shape-rendering=\"crispEdges\">",
+        width, height, width, height
+    )?;
+
+    for (y, row) in picture.rows().enumerate() {
+        let mut x = 0;
+        while x < row.len() {
+            let length = row[x..]
+                .iter()
+                .take_while(|color| **color == row[x])
+                .count();
+            writeln!(
+                writer,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+                x * scale,
+                y * scale,
+                length * scale,
+                scale,
+                row[x].hex()
+            )?;
+            x += length;
+        }
+    }
+
+    writeln!(writer, "</svg>")
+}
@@ -0,0 +1,87 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+use crate::{Error, Result};
+
+// Where a solver's puzzle input comes from. Anything that implements `BufRead` can be handed to
+// the solvers directly (including `&[u8]`, so `text.as_bytes()` works for a `&str`), but this is
+// handy for picking a source at runtime.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Input {
+    Path(PathBuf),
+    Stdin,
+    Text(String),
+}
+
+impl Input {
+    pub fn for_day(day: usize) -> Self {
+        Self::in_directory("inputs", day)
+    }
+
+    pub fn in_directory<P: AsRef<Path>>(directory: P, day: usize) -> Self {
+        Self::Path(directory.as_ref().join(format!("day{}.txt", day)))
+    }
+
+    pub fn open(&self) -> Result<Box<dyn BufRead + '_>> {
+        match self {
+            Self::Path(path) => match File::open(path) {
+                Ok(file) => Ok(Box::new(BufReader::new(file))),
+                Err(error) => Err(Error::File {
+                    path: path.clone(),
+                    error,
+                }),
+            },
+            Self::Stdin => Ok(Box::new(io::stdin().lock())),
+            Self::Text(text) => Ok(Box::new(text.as_bytes())),
+        }
+    }
+
+    // Stdin can only be read once, so this pulls it into memory when the same input needs to be
+    // handed to more than one solver.
+    pub fn into_reusable(self) -> io::Result<Self> {
+        match self {
+            Self::Stdin => {
+                let mut text = String::new();
+                io::stdin().read_to_string(&mut text)?;
+                Ok(Self::Text(text))
+            }
+            input => Ok(input),
+        }
+    }
+}
+
+impl From<&str> for Input {
+    fn from(value: &str) -> Self {
+        Self::Text(String::from(value))
+    }
+}
+
+impl From<String> for Input {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<PathBuf> for Input {
+    fn from(value: PathBuf) -> Self {
+        Self::Path(value)
+    }
+}
+
+impl From<&Path> for Input {
+    fn from(value: &Path) -> Self {
+        Self::Path(value.to_path_buf())
+    }
+}
+
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Path(path) => write!(f, "{}", path.display()),
+            Self::Stdin => write!(f, "<stdin>"),
+            Self::Text(_) => write!(f, "<text>"),
+        }
+    }
+}
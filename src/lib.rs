@@ -0,0 +1,42 @@
+use std::result;
+use std::time;
+
+pub mod animation;
+pub mod answers;
+pub mod diffcheck;
+pub mod error;
+pub mod fuzz;
+pub mod generate;
+pub mod geometry;
+pub mod grid;
+pub mod image;
+pub mod input;
+pub mod memory;
+pub mod parallel;
+pub mod parse;
+pub mod random;
+pub mod report;
+pub mod server;
+pub mod solver;
+pub mod timing;
+pub mod trace;
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+
+pub use error::{Error, Line, Location, Problem, SourceLine};
+
+pub type Result<T> = result::Result<T, Error>;
+pub type BoxedResult<T> = result::Result<T, Box<dyn std::error::Error>>;
+pub type TimedResult<T> = BoxedResult<(T, time::Duration)>;
@@ -1,147 +1,758 @@
-use std::error;
-use std::fmt;
-use std::result;
-use std::time;
-
-use crate::timing::time_function;
-
-mod timing;
-
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-
-type BoxedResult<T> = result::Result<T, Box<dyn error::Error>>;
-type TimedResult<T> = BoxedResult<(T, time::Duration)>;
-
-fn main() {
-    println!(
-        "Day 1, part 1: {}",
-        day1::part1::find_elf_with_most_calories()
-    );
-    println!(
-        "Day 1, part 2: {}\n",
-        day1::part2::find_three_elves_with_most_calories()
-    );
-
-    println!(
-        "Day 2, part 1: {}",
-        day2::part1::calculate_score_of_strategy_guide().unwrap()
-    );
-    println!(
-        "Day 2, part 2: {}\n",
-        day2::part2::calculate_score_of_strategy_guide().unwrap()
-    );
-
-    println!(
-        "Day 3, part 1: {}",
-        day3::part1::get_priority_of_incorrect_items().unwrap()
-    );
-    println!(
-        "Day 3, part 2: {}\n",
-        day3::part2::get_priority_of_team_badges().unwrap()
-    );
-
-    println!("Day 4, part 1: {}", day4::part1::find_subsets().unwrap());
-    println!("Day 4, part 2: {}\n", day4::part2::find_overlaps().unwrap());
-
-    println!(
-        "Day 5, part 1: {}",
-        format_timed_result(day5::part1::get_tops_of_stacks())
-    );
-    println!(
-        "Day 5, part 2: {}\n",
-        format_timed_result(day5::part2::get_tops_of_stacks_for_9001())
-    );
-
-    println!(
-        "Day 6, part 1: {}",
-        format_timed_result(day6::part1::find_start_of_packet_marker())
-    );
-    println!(
-        "Day 6, part 2: {}\n",
-        format_timed_result(day6::part2::find_start_of_message_marker())
-    );
-
-    println!(
-        "Day 7, part 1: {}",
-        format_timed_result(day7::part1::find_directory_sizes())
-    );
-    println!(
-        "Day 7, part 2: {}\n",
-        format_timed_result(day7::part2::find_directory_to_delete())
-    );
-
-    println!(
-        "Day 8, part 1: {}",
-        format_timed_result(day8::part1::count_visible_trees())
-    );
-    println!(
-        "Day 8, part 2: {}\n",
-        format_timed_result(day8::part2::get_best_scenic_score())
-    );
-
-    println!(
-        "Day 9, part 1: {}",
-        format_timed_result(day9::part1::count_unique_tail_positions())
-    );
-    println!(
-        "Day 9, part 2: {}\n",
-        format_timed_result(day9::part2::count_unique_long_tail_positions())
-    );
-
-    println!(
-        "Day 10, part 1: {}",
-        format_timed_result(day10::part1::calculate_sum_of_signal_strengths())
-    );
-    println!("Day 10, part 2:");
-    let day10_part2_result = day10::part2::print_crt();
-    match day10_part2_result {
-        Ok((_, duration)) => println!("Took {} ms\n", duration.as_millis()),
-        Err(error) => println!("Errored: {}\n", error),
-    }
-
-    println!(
-        "Day 11, part 1: {}",
-        format_timed_result(day11::part1::calculate_calm_monkey_business())
-    );
-    println!(
-        "Day 11, part 2: {}\n",
-        format_timed_result(day11::part2::calculate_anxious_monkey_business())
-    );
-
-    println!(
-        "Day 12, part 1: {}",
-        format_timed_result(day12::part1::find_best_path_length()),
-    );
-    println!(
-        "Day 12, part 2: {}\n",
-        format_timed_result(day12::part2::find_most_scenic_path_length()),
-    );
-
-    println!(
-        "Day 13, part 1: {}",
-        time_function(day13::part1::find_pairs_in_correct_order),
-    );
-    println!(
-        "Day 13, part 2: {}",
-        time_function(day13::part2::locate_divider_packets),
-    );
-}
-
-fn format_timed_result<T: fmt::Display>(result: TimedResult<T>) -> String {
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use advent_of_code_2022::animation;
+use advent_of_code_2022::answers::Answers;
+use advent_of_code_2022::diffcheck::{self, Disagreement};
+use advent_of_code_2022::fuzz::{self, Worker};
+use advent_of_code_2022::generate;
+use advent_of_code_2022::image;
+use advent_of_code_2022::input::Input;
+use advent_of_code_2022::memory::{self, CountingAllocator, Usage};
+use advent_of_code_2022::parallel;
+use advent_of_code_2022::random::Random;
+use advent_of_code_2022::report::{self, Record, Status};
+use advent_of_code_2022::server::Server;
+use advent_of_code_2022::solver::{self, Answer, Solver};
+use advent_of_code_2022::timing::{self, Benchmark};
+use advent_of_code_2022::trace;
+use advent_of_code_2022::{BoxedResult, TimedResult};
+
+use crate::check::Row;
+use crate::cli::{Animate, Command, DiffCheck, Fuzz, Generate, Image, Options, FUZZ_WORKER, USAGE};
+
+// Only counts anything while `memory::measure` is running, which is only with --memory.
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+mod check;
+mod cli;
+mod player;
+mod repl;
+mod watch;
+
+fn main() -> ExitCode {
+    let command = match Command::parse(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(error) => {
+            eprintln!("{}\n\n{}", error, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    // Only running solutions has -v, so the other commands go by the environment alone.
+    let tracing = match &command {
+        Command::Run(options) => trace::start(options.trace_level(), options.trace_file.as_deref()),
+        _ => trace::start(None, None),
+    };
+    if let Err(error) = tracing {
+        eprintln!("Couldn't start tracing: {}", error);
+        return ExitCode::from(2);
+    }
+
+    let options = match command {
+        Command::Run(options) => options,
+        Command::Generate(generate) => return generate_input(&generate),
+        Command::DiffCheck(diffcheck) => return check_references(&diffcheck),
+        Command::Fuzz(fuzz) => return fuzz_parsers(&fuzz),
+        Command::Image(image) => return draw_picture(&image),
+        Command::Animate(animate) => return animate_simulation(&animate),
+        Command::FuzzWorker(day, part) => return serve_fuzz_worker(day, part),
+    };
+
+    if options.help {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    if options.repl {
+        return repl::run(&options);
+    }
+
+    if let Some(address) = &options.serve {
+        return serve(address);
+    }
+
+    let solvers = solver::registry();
+
+    for selection in &options.selections {
+        if !solvers
+            .iter()
+            .any(|solver| selection.matches(solver.day(), solver.part()))
+        {
+            eprintln!("No solutions match '{}'.", selection);
+            return ExitCode::from(2);
+        }
+    }
+
+    // With --stream, the days that have a streaming solution use it, and the rest run as usual.
+    let streaming = if options.stream {
+        solver::streaming_registry()
+    } else {
+        Vec::new()
+    };
+    let streaming_for = |solver: &dyn Solver| {
+        streaming
+            .iter()
+            .find(|streaming| streaming.day == solver.day() && streaming.part == solver.part())
+            .copied()
+    };
+
+    let selected = solvers
+        .into_iter()
+        .filter(|solver| options.is_selected(solver.day(), solver.part()))
+        .map(|solver| streaming_for(solver).map_or(solver, |streaming| streaming as &dyn Solver))
+        .collect::<Vec<&dyn Solver>>();
+
+    if options.list {
+        for solver in &selected {
+            match streaming_for(*solver) {
+                Some(streaming) => println!(
+                    "{}.{}\t{}\t{}",
+                    solver.day(),
+                    solver.part(),
+                    solver.name(),
+                    streaming.memory
+                ),
+                None => println!("{}.{}\t{}", solver.day(), solver.part(), solver.name()),
+            }
+        }
+
+        return ExitCode::SUCCESS;
+    }
+
+    // Stdin only needs reading into memory when it's read more than once. Otherwise a streaming
+    // solution can read it as it arrives, however big it is.
+    let reused = selected.len() > 1 || options.bench || options.memory;
+    let shared_input = match options
+        .input
+        .clone()
+        .map(|input| {
+            if reused {
+                input.into_reusable()
+            } else {
+                Ok(input)
+            }
+        })
+        .transpose()
+    {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!("Couldn't read input: {}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // Checking needs the answers file to exist, but recording can start a new one.
+    let mut answers = if options.check || options.record {
+        match load_answers(&options.answers, options.check) {
+            Ok(answers) => Some(answers),
+            Err(error) => {
+                eprintln!(
+                    "Couldn't read answers from {}: {}",
+                    options.answers.display(),
+                    error
+                );
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        None
+    };
+
+    let jobs = selected
+        .into_iter()
+        .map(|solver| {
+            let input = match (&shared_input, &options.input_directory) {
+                (Some(input), _) => input.clone(),
+                (None, Some(directory)) => Input::in_directory(directory, solver.day()),
+                (None, None) => Input::for_day(solver.day()),
+            };
+            (solver, input)
+        })
+        .collect::<Vec<(&dyn Solver, Input)>>();
+
+    if options.watch {
+        watch::watch(&jobs, options.jobs, |(solver, input)| {
+            run_solver(*solver, input, &options)
+                .into_timed_result()
+                .map_err(|error| error.to_string())
+        });
+    }
+
+    // The solvers run on the pool, but everything is printed afterwards in day order so nothing
+    // interleaves.
+    let start_time = Instant::now();
+    let runs = parallel::map(&jobs, options.jobs, |(solver, input)| {
+        run_solver(*solver, input, &options)
+    });
+    let wall_time = start_time.elapsed();
+    let summed_time = runs.iter().map(|run| run.elapsed).sum::<Duration>();
+
+    let mut failed = false;
+    let mut records = Vec::<Record>::new();
+    let mut rows = Vec::<Row>::new();
+
+    for ((solver, input), run) in jobs.iter().zip(runs) {
+        let (day, part) = (solver.day(), solver.part());
+
+        if let (true, Some(answers)) = (options.check, &answers) {
+            let result = run.into_timed_result().map(|(answer, _)| answer);
+            let row = Row::new(day, part, input, answers, result);
+            failed |= row.status.is_failure(options.allow_missing);
+            rows.push(row);
+            continue;
+        }
+
+        if options.format.is_some() {
+            let record = Record::new(day, part, run.into_timed_result());
+            if let (Some(answers), Some(answer)) = (&mut answers, &record.answer) {
+                answers.insert(day, part, input, answer);
+            }
+            failed |= record.status() == Status::Error;
+            records.push(record);
+            continue;
+        }
+
+        let output = match run.outcome {
+            Outcome::Benchmarked(result) => {
+                let result = result.map(|benchmark| *benchmark).map_err(Into::into);
+                failed |= result.is_err();
+                if let (Some(answers), Ok(benchmark)) = (&mut answers, &result) {
+                    answers.insert(day, part, input, &benchmark.answer);
+                }
+                format_benchmark(result)
+            }
+            Outcome::Timed(result) => {
+                let result = result.map_err(Into::into);
+                failed |= result.is_err();
+                if let (Some(answers), Ok((answer, _))) = (&mut answers, &result) {
+                    answers.insert(day, part, input, answer);
+                }
+                format_timed_result(result)
+            }
+        };
+
+        println!("Day {}, part {}: {}", day, part, output);
+
+        if let Some(usage) = run.memory {
+            println!("  memory: {}", usage);
+        }
+    }
+
+    if options.format.is_none() && !options.check {
+        // The solver time is every run's own wall time added up, so with more than one job it can
+        // come to more than the wall time of the whole lot.
+        let threads = options.jobs.min(jobs.len());
+        println!(
+            "\nRan {} solutions on {} {}: {:?} wall time, {:?} summed solver time.",
+            jobs.len(),
+            threads,
+            if threads == 1 { "thread" } else { "threads" },
+            wall_time,
+            summed_time
+        );
+    }
+
+    if let Some(format) = options.format {
+        println!("{}", report::render(format, &records));
+    }
+
+    if options.check {
+        println!("{}", check::render_table(&rows));
+    } else if let (true, Some(answers)) = (options.record, &answers) {
+        if let Err(error) = save_answers(&options.answers, answers) {
+            eprintln!(
+                "Couldn't write answers to {}: {}",
+                options.answers.display(),
+                error
+            );
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+// What one solver produced on a worker thread. Boxed errors can't be sent between threads, so
+// they're flattened down to their messages.
+enum Outcome {
+    Timed(Result<(Answer, Duration), String>),
+    Benchmarked(Result<Box<Benchmark>, String>),
+}
+
+struct Run {
+    outcome: Outcome,
+    elapsed: Duration,
+    memory: Option<Usage>,
+}
+
+impl Run {
+    // Benchmarks are reported by their median time wherever a single time is expected.
+    fn into_timed_result(self) -> TimedResult<Answer> {
+        match self.outcome {
+            Outcome::Timed(result) => result.map_err(Into::into),
+            Outcome::Benchmarked(result) => result
+                .map(|benchmark| (benchmark.answer, benchmark.total.median))
+                .map_err(Into::into),
+        }
+    }
+}
+
+fn run_solver(solver: &dyn Solver, input: &Input, options: &Options) -> Run {
+    let start_time = Instant::now();
+
+    let outcome = if options.bench && !options.check {
+        Outcome::Benchmarked(
+            timing::benchmark_solver(solver, input, options.warmup, options.iterations)
+                .map(Box::new)
+                .map_err(|error| error.to_string()),
+        )
+    } else {
+        Outcome::Timed(timing::time_solver(solver, input).map_err(|error| error.to_string()))
+    };
+
+    let elapsed = start_time.elapsed();
+
+    // Counting gets its own run, so it can't slow down the timed ones. Opening the input isn't
+    // counted, since that's the same for every solver.
+    let memory = if options.memory {
+        input
+            .open()
+            .ok()
+            .map(|mut reader| memory::measure(|| solver.solve(&mut reader)).1)
+    } else {
+        None
+    };
+
+    Run {
+        outcome,
+        elapsed,
+        memory,
+    }
+}
+
+fn generate_input(options: &Generate) -> ExitCode {
+    if options.help {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    let generator = match generate::generator(options.day) {
+        Some(generator) => generator,
+        None => {
+            eprintln!(
+                "There's no generator for day {}. The size means:",
+                options.day
+            );
+            for (day, size, _) in generate::GENERATORS {
+                eprintln!("  day {:<2}  {}", day, size);
+            }
+            return ExitCode::from(2);
+        }
+    };
+
+    let seed = seed_or_new(options.seed);
+
+    let mut writer = BufWriter::new(io::stdout().lock());
+    let result =
+        generator(options.size, &mut Random::new(seed), &mut writer).and_then(|()| writer.flush());
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        // Piping into something like `head` closes stdout early, which isn't worth complaining about.
+        Err(error) if error.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("Couldn't write the input: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn draw_picture(options: &Image) -> ExitCode {
+    if options.help {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    let painter = match image::painter(options.day) {
+        Some(painter) => painter,
+        None => {
+            eprintln!(
+                "There's no picture for day {}. The days that have one show:",
+                options.day
+            );
+            for (day, picture, _) in image::PAINTERS {
+                eprintln!("  day {:<2}  {}", day, picture);
+            }
+            return ExitCode::from(2);
+        }
+    };
+
+    let input = options
+        .input
+        .clone()
+        .unwrap_or_else(|| Input::for_day(options.day));
+
+    let picture = match input.open().and_then(|mut reader| painter(&mut reader)) {
+        Ok(picture) => picture,
+        Err(error) => {
+            eprintln!("Couldn't draw day {}: {}", options.day, error);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let scale = image::fitting_scale(&picture, options.scale);
+    if scale < options.scale {
+        eprintln!(
+            "The picture is {} by {} squares, so it's drawn at scale {} instead.",
+            picture.width(),
+            picture.height(),
+            scale
+        );
+    }
+
+    let result = File::create(&options.output).and_then(|file| {
+        let mut writer = BufWriter::new(file);
+        image::write(&mut writer, options.format, &picture, scale)?;
+        writer.flush()
+    });
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("Couldn't write {}: {}", options.output.display(), error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn animate_simulation(options: &Animate) -> ExitCode {
+    if options.help {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    let animator = match animation::animator(options.day) {
+        Some(animator) => animator,
+        None => {
+            eprintln!(
+                "There's no animation for day {}. The days that have one step through:",
+                options.day
+            );
+            for (day, step, _) in animation::ANIMATORS {
+                eprintln!("  day {:<2}  {}", day, step);
+            }
+            return ExitCode::from(2);
+        }
+    };
+
+    let input = options
+        .input
+        .clone()
+        .unwrap_or_else(|| Input::for_day(options.day));
+
+    // It's kept around, since restarting reads it all over again.
+    let mut text = Vec::new();
+    if let Err(error) = input
+        .open()
+        .and_then(|mut reader| Ok(reader.read_to_end(&mut text)?))
+    {
+        eprintln!("Couldn't animate day {}: {}", options.day, error);
+        return ExitCode::FAILURE;
+    }
+
+    let start = || -> BoxedResult<_> { Ok(animator(&mut text.as_slice(), options.part)?) };
+
+    match player::play(start, options.speed) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("Couldn't animate day {}: {}", options.day, error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// Without a seed, the clock picks one, and it's shown so the same run can be made again.
+fn seed_or_new(seed: Option<u64>) -> u64 {
+    seed.unwrap_or_else(|| {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_nanos() as u64);
+        eprintln!("Seed: {}", seed);
+        seed
+    })
+}
+
+// Inputs longer than this are left for `generate` to make again, rather than filling the terminal.
+const MAX_SHOWN_INPUT_LINES: usize = 40;
+
+fn check_references(options: &DiffCheck) -> ExitCode {
+    if options.help {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    let checks = solver::registry()
+        .into_iter()
+        .filter(|solver| options.is_selected(solver.day(), solver.part()))
+        .filter_map(|solver| Some((solver, solver::reference(solver.day(), solver.part())?)))
+        .collect::<Vec<(&dyn Solver, solver::Reference)>>();
+
+    if checks.is_empty() {
+        eprintln!("No parts with references match the selection.");
+        return ExitCode::from(2);
+    }
+
+    let seed = seed_or_new(options.seed);
+    let disagreements = parallel::map(&checks, options.jobs, |(solver, reference)| {
+        diffcheck::find_disagreement(*solver, *reference, options.runs, options.size, seed)
+    });
+
+    let mut failed = false;
+
+    for ((solver, _), disagreement) in checks.iter().zip(disagreements) {
+        print!("Day {}, part {}: ", solver.day(), solver.part());
+        match disagreement {
+            None => println!("agreed on {} inputs", options.runs),
+            Some(disagreement) => {
+                failed = true;
+                println!("disagreed");
+                show_disagreement(&disagreement);
+            }
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn show_disagreement(disagreement: &Disagreement) {
+    println!("{}", disagreement);
+
+    let lines = disagreement.input.lines().count();
+    if lines <= MAX_SHOWN_INPUT_LINES {
+        println!("  input:\n{}", disagreement.input.trim_end());
+    } else {
+        println!("  input: {} lines, too long to show", lines);
+    }
+}
+
+// Generated inputs of these sizes join the examples as the inputs that get mutated.
+const FUZZ_SEED_SIZES: [usize; 3] = [1, 3, 10];
+
+fn fuzz_parsers(options: &Fuzz) -> ExitCode {
+    if options.help {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    let solvers = solver::registry()
+        .into_iter()
+        .filter(|solver| options.is_selected(solver.day(), solver.part()))
+        .collect::<Vec<&dyn Solver>>();
+
+    if solvers.is_empty() {
+        eprintln!("No solutions match the selection.");
+        return ExitCode::from(2);
+    }
+
+    let program = match env::current_exe() {
+        Ok(program) => program,
+        Err(error) => {
+            eprintln!(
+                "Couldn't find this program to run the parsers in: {}",
+                error
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let seed = seed_or_new(options.seed);
+    let results = parallel::map(&solvers, options.jobs, |solver| {
+        let mut worker = Worker::new(
+            program.clone(),
+            vec![
+                String::from(FUZZ_WORKER),
+                format!("{}.{}", solver.day(), solver.part()),
+            ],
+            options.timeout,
+        );
+        let seeds = fuzz_seeds(solver.day(), seed);
+        fuzz::fuzz(&mut worker, &seeds, options.runs, &mut Random::new(seed))
+    });
+
+    let mut failed = false;
+
+    for (solver, result) in solvers.iter().zip(results) {
+        print!("Day {}, part {}: ", solver.day(), solver.part());
+        let findings = match result {
+            Ok(findings) if findings.is_empty() => {
+                println!("nothing found in {} inputs", options.runs);
+                continue;
+            }
+            Ok(findings) => findings,
+            Err(error) => {
+                failed = true;
+                println!("couldn't run the parser: {}", error);
+                continue;
+            }
+        };
+
+        failed = true;
+        println!("found {}", findings.len());
+
+        for (index, finding) in findings.iter().enumerate() {
+            let path = options.output.join(format!(
+                "day{}-part{}-{}-{}.txt",
+                solver.day(),
+                solver.part(),
+                finding.failure.kind(),
+                index + 1
+            ));
+            let saved = fs::create_dir_all(&options.output)
+                .and_then(|()| fs::write(&path, &finding.input))
+                .map_or_else(
+                    |error| format!("couldn't save it: {}", error),
+                    |()| format!("saved to {}", path.display()),
+                );
+
+            println!(
+                "  {} (minimized from {} to {} bytes, {})",
+                finding.failure,
+                finding.original_length,
+                finding.input.len(),
+                saved
+            );
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+// The day's examples, if they're around, and a few small generated inputs.
+fn fuzz_seeds(day: usize, seed: u64) -> Vec<Vec<u8>> {
+    let mut seeds = fs::read_dir(format!("examples/day{}", day))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.file_name().is_some_and(|name| name != "answers.txt"))
+        .filter_map(|path| fs::read(path).ok())
+        .collect::<Vec<Vec<u8>>>();
+
+    if let Some(generator) = generate::generator(day) {
+        for size in FUZZ_SEED_SIZES {
+            let mut input = Vec::new();
+            if generator(size, &mut Random::new(seed), &mut input).is_ok() {
+                seeds.push(input);
+            }
+        }
+    }
+
+    seeds
+}
+
+fn serve_fuzz_worker(day: usize, part: usize) -> ExitCode {
+    let Some(solver) = solver::registry()
+        .into_iter()
+        .find(|solver| solver.day() == day && solver.part() == part)
+    else {
+        eprintln!("No solution for day {}, part {}.", day, part);
+        return ExitCode::from(2);
+    };
+
+    match fuzz::serve_worker(solver) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("The fuzz worker stopped: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn serve(address: &str) -> ExitCode {
+    let server = match Server::bind(address) {
+        Ok(server) => server,
+        Err(error) => {
+            eprintln!("Couldn't listen on {}: {}", address, error);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // Binding to port 0 picks a free port, so show the one that was actually used.
+    let bound = server
+        .local_addr()
+        .map_or(String::from(address), |bound| bound.to_string());
+    println!("Listening on http://{}", bound);
+
+    match server.serve() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("Stopped serving: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn load_answers(path: &Path, must_exist: bool) -> BoxedResult<Answers> {
+    match File::open(path) {
+        Ok(file) => Answers::from_reader(BufReader::new(file)),
+        Err(error) if error.kind() == io::ErrorKind::NotFound && !must_exist => {
+            Ok(Answers::default())
+        }
+        Err(error) => Err(error.into()),
+    }
+}
+
+fn save_answers(path: &Path, answers: &Answers) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    answers.write_to(&mut writer)?;
+    writer.flush()
+}
+
+fn format_timed_result(result: TimedResult<Answer>) -> String {
     match result {
-        Ok((value, duration)) => format!("{} (in {} ms)", value, duration.as_millis()),
+        Ok((answer, duration)) if answer.is_multiline() => {
+            format!("(in {:?})\n{}", duration, answer)
+        }
+        Ok((answer, duration)) => format!("{} (in {:?})", answer, duration),
+        Err(error) => format!("Errored: {}", error),
+    }
+}
+
+fn format_benchmark(result: BoxedResult<Benchmark>) -> String {
+    match result {
+        Ok(benchmark) => {
+            let summary = if benchmark.answer.is_multiline() {
+                format!(
+                    "({} iterations)\n{}",
+                    benchmark.iterations, benchmark.answer
+                )
+            } else {
+                format!("{} ({} iterations)", benchmark.answer, benchmark.iterations)
+            };
+
+            format!(
+                "{}\n  parse: {}\n  solve: {}",
+                summary, benchmark.parse, benchmark.solve
+            )
+        }
         Err(error) => format!("Errored: {}", error),
     }
 }
@@ -0,0 +1,153 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt;
+
+// Wraps the system allocator, counting what each thread allocates while it's inside `measure`. It
+// only does anything once it's installed with `#[global_allocator]`, and outside of `measure` it
+// costs one thread-local check per allocation.
+pub struct CountingAllocator;
+
+#[derive(Clone, Copy)]
+struct Counters {
+    measuring: bool,
+    allocations: usize,
+    bytes: usize,
+    // Memory freed on a different thread than it was allocated on can make these go negative.
+    live: isize,
+    peak: isize,
+}
+
+thread_local! {
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters {
+            measuring: false,
+            allocations: 0,
+            bytes: 0,
+            live: 0,
+            peak: 0,
+        })
+    };
+}
+
+// Called on every allocation, so it has to stay away from anything that allocates itself. The
+// counters can already be gone while a thread is shutting down, which is why it's `try_with`.
+fn record(allocated: usize, freed: usize) {
+    let _ = COUNTERS.try_with(|counters| {
+        let mut current = counters.get();
+        if !current.measuring {
+            return;
+        }
+
+        if allocated > 0 {
+            current.allocations += 1;
+            current.bytes += allocated;
+        }
+        current.live += allocated as isize - freed as isize;
+        current.peak = current.peak.max(current.live);
+        counters.set(current);
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let pointer = System.alloc(layout);
+        if !pointer.is_null() {
+            record(layout.size(), 0);
+        }
+        pointer
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let pointer = System.alloc_zeroed(layout);
+        if !pointer.is_null() {
+            record(layout.size(), 0);
+        }
+        pointer
+    }
+
+    unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+        System.dealloc(pointer, layout);
+        record(0, layout.size());
+    }
+
+    // Growing or shrinking counts as one allocation of the new size, since that's usually a copy.
+    unsafe fn realloc(&self, pointer: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_pointer = System.realloc(pointer, layout, new_size);
+        if !new_pointer.is_null() {
+            record(new_size, layout.size());
+        }
+        new_pointer
+    }
+}
+
+// What one call to `measure` allocated. The peak is the most that was live at once, on top of
+// whatever was already allocated before it started.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Usage {
+    pub allocations: usize,
+    pub bytes: usize,
+    pub peak_bytes: usize,
+}
+
+impl fmt::Display for Usage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {}, {} allocated, {} peak",
+            self.allocations,
+            if self.allocations == 1 {
+                "allocation"
+            } else {
+                "allocations"
+            },
+            format_bytes(self.bytes),
+            format_bytes(self.peak_bytes)
+        )
+    }
+}
+
+// Runs `function` and counts everything it allocates on this thread. Without `CountingAllocator`
+// installed, the usage always comes back empty.
+pub fn measure<T, F: FnOnce() -> T>(function: F) -> (T, Usage) {
+    let before = COUNTERS.with(|counters| {
+        let mut current = counters.get();
+        current.measuring = true;
+        current.peak = current.live;
+        counters.set(current);
+        current
+    });
+
+    let result = function();
+
+    let after = COUNTERS.with(|counters| {
+        let mut current = counters.get();
+        current.measuring = before.measuring;
+        counters.set(current);
+        current
+    });
+
+    let usage = Usage {
+        allocations: after.allocations - before.allocations,
+        bytes: after.bytes - before.bytes,
+        peak_bytes: (after.peak - before.live).max(0) as usize,
+    };
+
+    (result, usage)
+}
+
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
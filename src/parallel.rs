@@ -0,0 +1,40 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+// Calls `function` on every item using up to `jobs` worker threads, which each take the next
+// unclaimed item until there are none left. The results come back in the same order as the items,
+// no matter which finished first.
+pub fn map<T, U, F>(items: &[T], jobs: usize, function: F) -> Vec<U>
+where
+    T: Sync,
+    U: Send,
+    F: Fn(&T) -> U + Sync,
+{
+    let next_index = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel::<(usize, U)>();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            let sender = sender.clone();
+            let next_index = &next_index;
+            let function = &function;
+
+            scope.spawn(move || loop {
+                let index = next_index.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+
+                // The receiver outlives every worker, so this can't fail.
+                let _ = sender.send((index, function(item)));
+            });
+        }
+    });
+
+    drop(sender);
+
+    let mut results = receiver.into_iter().collect::<Vec<(usize, U)>>();
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
@@ -0,0 +1,290 @@
+use std::io::{self, BufRead};
+use std::iter::Enumerate;
+use std::str::{FromStr, SplitAsciiWhitespace};
+
+use crate::{Error, Line, Problem, Result};
+
+// Hands `function` each line of the input in turn, numbered from 1, stopping at the first error.
+pub fn for_each_line<R, F>(reader: R, day: usize, mut function: F) -> Result<()>
+where
+    R: BufRead,
+    F: FnMut(Line) -> Result<()>,
+{
+    for (index, text) in reader.lines().enumerate() {
+        function(Line::new(day, index + 1, &text?))?;
+    }
+
+    Ok(())
+}
+
+// One value per line, made by `parse`.
+pub fn lines<R, T, F>(reader: R, day: usize, mut parse: F) -> Result<Vec<T>>
+where
+    R: BufRead,
+    F: FnMut(Line) -> Result<T>,
+{
+    let mut values = Vec::<T>::new();
+    for_each_line(reader, day, |line| {
+        values.push(parse(line)?);
+        Ok(())
+    })?;
+
+    Ok(values)
+}
+
+// The input split into groups of lines wherever there's a blank one.
+pub fn records<R: BufRead>(reader: R, day: usize) -> Records<R> {
+    Records {
+        lines: reader.lines().enumerate(),
+        day,
+    }
+}
+
+// Yields every group of lines between blank ones, so two blank lines in a row make an empty
+// record. A blank line at the very end doesn't start another one, though.
+pub struct Records<R> {
+    lines: Enumerate<io::Lines<R>>,
+    day: usize,
+}
+
+impl<R: BufRead> Iterator for Records<R> {
+    type Item = Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut record = Record {
+            day: self.day,
+            lines: Vec::new(),
+        };
+
+        loop {
+            match self.lines.next() {
+                Some((index, Ok(text))) if !text.trim().is_empty() => {
+                    record.lines.push((index + 1, text));
+                }
+                Some((_, Ok(_))) => return Some(Ok(record)),
+                Some((_, Err(error))) => return Some(Err(error.into())),
+                None if record.lines.is_empty() => return None,
+                None => return Some(Ok(record)),
+            }
+        }
+    }
+}
+
+// A group of lines, each kept with its line number in the whole input.
+#[derive(Debug, Default)]
+pub struct Record {
+    day: usize,
+    lines: Vec<(usize, String)>,
+}
+
+impl Record {
+    pub fn lines(&self) -> RecordLines<'_> {
+        RecordLines {
+            record: self,
+            index: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+}
+
+pub struct RecordLines<'a> {
+    record: &'a Record,
+    index: usize,
+}
+
+impl<'a> RecordLines<'a> {
+    // For records with a fixed layout, where running out of lines means `what` is missing. It
+    // would have come after the record's last line, so that's where the error points.
+    pub fn next_or_missing(&mut self, what: &'static str) -> Result<Line<'a>> {
+        self.next().ok_or_else(|| match self.record.lines.last() {
+            Some((number, text)) => {
+                Line::new(self.record.day, *number, text).error_at_end(Problem::Missing(what))
+            }
+            None => Error::puzzle(self.record.day, Problem::Missing(what)),
+        })
+    }
+}
+
+impl<'a> Iterator for RecordLines<'a> {
+    type Item = Line<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (number, text) = self.record.lines.get(self.index)?;
+        self.index += 1;
+
+        Some(Line::new(self.record.day, *number, text))
+    }
+}
+
+// Whatever follows `label` on the line, once the indentation is out of the way. This is for lines
+// like "  Starting items: 79, 98", where the label is everything up to the value.
+pub fn labelled<'a>(line: &Line<'a>, label: &'static str) -> Result<&'a str> {
+    line.text
+        .trim_start()
+        .strip_prefix(label)
+        .map(str::trim)
+        .ok_or_else(|| line.error(Problem::Label(label)))
+}
+
+// Splits a "key: value" line at its first colon, trimming both sides.
+pub fn key_value<'a>(line: &Line<'a>) -> Result<(&'a str, &'a str)> {
+    line.text
+        .split_once(':')
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .ok_or_else(|| line.error_at_end(Problem::Missing("':'")))
+}
+
+// Splits `part` of the line into exactly two around `separator`, like "2-4" or "2-4,6-8". Any
+// other number of pieces is blamed on `part`, as not being what was `expected`.
+pub fn split_pair<'a>(
+    line: &Line<'a>,
+    part: &'a str,
+    separator: char,
+    expected: &'static str,
+) -> Result<(&'a str, &'a str)> {
+    match part.split_once(separator) {
+        Some((first, second)) if !second.contains(separator) => Ok((first, second)),
+        _ => Err(line.error_at(part, Problem::Expected(expected))),
+    }
+}
+
+// Takes a line's whitespace-separated fields one at a time. Every field is a slice of the line, so
+// errors about it point at the right column.
+pub struct Fields<'a, 'b> {
+    line: &'b Line<'a>,
+    fields: SplitAsciiWhitespace<'a>,
+}
+
+impl<'a, 'b> Fields<'a, 'b> {
+    pub fn new(line: &'b Line<'a>) -> Self {
+        Self::of(line, line.text)
+    }
+
+    // Just the fields in `part` of the line, like what's left after a label.
+    pub fn of(line: &'b Line<'a>, part: &'a str) -> Self {
+        Self {
+            line,
+            fields: part.split_ascii_whitespace(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.clone().next().is_none()
+    }
+
+    pub fn next(&mut self, what: &'static str) -> Result<&'a str> {
+        self.fields
+            .next()
+            .ok_or_else(|| self.line.error_at_end(Problem::Missing(what)))
+    }
+
+    pub fn number<T: FromStr>(&mut self, what: &'static str) -> Result<T> {
+        self.line.number(self.next(what)?)
+    }
+
+    // The next field has to be exactly `literal`.
+    pub fn literal(&mut self, literal: &'static str) -> Result<()> {
+        match self.fields.next() {
+            Some(field) if field == literal => Ok(()),
+            Some(field) => Err(self.line.error_at(field, Problem::Word(literal))),
+            None => Err(self.line.error_at_end(Problem::Word(literal))),
+        }
+    }
+
+    // The value paired with whichever of `choices` the next field is.
+    pub fn one_of<T: Clone>(&mut self, what: &'static str, choices: &[(&str, T)]) -> Result<T> {
+        let field = self.next(what)?;
+
+        choices
+            .iter()
+            .find(|(name, _)| *name == field)
+            .map(|(_, value)| value.clone())
+            .ok_or_else(|| self.line.error_at(field, Problem::Unrecognized(what)))
+    }
+
+    // There mustn't be any fields left.
+    pub fn end(&mut self) -> Result<()> {
+        match self.fields.next() {
+            Some(extra) => Err(self
+                .line
+                .error_at(extra, Problem::Expected("the end of the line"))),
+            None => Ok(()),
+        }
+    }
+}
+
+// Walks a line byte by byte, for inputs with no whitespace between their parts, keeping track of
+// where it is so errors can point at the bad byte.
+pub struct Cursor<'a, 'b> {
+    line: &'b Line<'a>,
+    position: usize,
+}
+
+impl<'a, 'b> Cursor<'a, 'b> {
+    pub fn new(line: &'b Line<'a>) -> Self {
+        Self { line, position: 0 }
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn peek(&self) -> Option<u8> {
+        self.line.text.as_bytes().get(self.position).copied()
+    }
+
+    pub fn advance(&mut self) -> Option<u8> {
+        let byte = self.peek();
+        if byte.is_some() {
+            self.position += 1;
+        }
+
+        byte
+    }
+
+    // Moves past every byte `accept` is happy with, and returns them. Only ASCII bytes are taken,
+    // so the slice can't end partway through a character.
+    pub fn take_while<F: Fn(u8) -> bool>(&mut self, accept: F) -> &'a str {
+        let start = self.position;
+        while self
+            .peek()
+            .is_some_and(|byte| byte.is_ascii() && accept(byte))
+        {
+            self.position += 1;
+        }
+
+        &self.line.text[start..self.position]
+    }
+
+    // Takes a run of digits as a number, blaming the current byte as not being `what` if there
+    // aren't any, or all of them if there are too many to fit.
+    pub fn number<T: FromStr>(&mut self, what: &'static str) -> Result<T> {
+        let digits = self.take_while(|byte| byte.is_ascii_digit());
+        if digits.is_empty() {
+            return Err(self.expected(what));
+        }
+
+        digits.parse().map_err(|_| {
+            self.line
+                .error_at(digits, Problem::Expected("a smaller number"))
+        })
+    }
+
+    // Blames the byte at the current position, or the end of the line if there's nothing left.
+    pub fn expected(&self, what: &'static str) -> Error {
+        let length = usize::from(self.peek().is_some());
+        self.line
+            .error_at_offset(self.position, length, Problem::Expected(what))
+    }
+
+    pub fn error_at_position(&self, length: usize, problem: Problem) -> Error {
+        self.line.error_at_offset(self.position, length, problem)
+    }
+}
@@ -0,0 +1,275 @@
+use std::io::{self, IsTerminal, Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use advent_of_code_2022::animation::Simulation;
+use advent_of_code_2022::BoxedResult;
+
+pub const CONTROLS: &str = "space: play/pause  n: step  +/-: faster/slower  r: restart  q: quit";
+
+// Speeds are in steps a second. Past the frame rate, several steps are taken between frames.
+pub const DEFAULT_SPEED: usize = 8;
+pub const MAX_SPEED: usize = 1 << 16;
+const FRAMES_PER_SECOND: usize = 30;
+
+const ENTER_SCREEN: &str = "\x1b[?1049h\x1b[?25l";
+const LEAVE_SCREEN: &str = "\x1b[?25h\x1b[?1049l";
+const HOME: &str = "\x1b[H";
+const CLEAR_LINE: &str = "\x1b[K";
+const CLEAR_BELOW: &str = "\x1b[J";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Key {
+    PlayPause,
+    Step,
+    Faster,
+    Slower,
+    Restart,
+    Quit,
+}
+
+// Plays the simulation `start` makes, redrawing it after every step. Without a terminal to draw
+// on, every frame is printed one after the other instead, as fast as it can go.
+pub fn play<F>(start: F, speed: usize) -> BoxedResult<()>
+where
+    F: Fn() -> BoxedResult<Box<dyn Simulation>>,
+{
+    let mut simulation = start()?;
+    let mut stdout = io::stdout().lock();
+
+    if !io::stdout().is_terminal() {
+        loop {
+            write_frame(&mut stdout, simulation.as_ref())?;
+            if !simulation.step() {
+                return Ok(());
+            }
+        }
+    }
+
+    // Keys only work when they can be read one at a time. Otherwise it just plays to the end.
+    let raw_mode = RawMode::enable();
+    let keys = match &raw_mode {
+        Some(_) => read_keys(),
+        None => mpsc::channel().1,
+    };
+    let (rows, columns) = terminal_size().unwrap_or((24, 80));
+
+    write!(stdout, "{}", ENTER_SCREEN)?;
+    let result = run(
+        &mut stdout,
+        &start,
+        &mut simulation,
+        &keys,
+        speed,
+        rows,
+        columns,
+    );
+    write!(stdout, "{}", LEAVE_SCREEN)?;
+    drop(raw_mode);
+
+    // The screen it was drawn on is gone, so this leaves a record of how far it got.
+    writeln!(stdout, "{}", simulation.status())?;
+    result
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run<F>(
+    stdout: &mut dyn Write,
+    start: &F,
+    simulation: &mut Box<dyn Simulation>,
+    keys: &Receiver<Key>,
+    mut speed: usize,
+    rows: usize,
+    columns: usize,
+) -> BoxedResult<()>
+where
+    F: Fn() -> BoxedResult<Box<dyn Simulation>>,
+{
+    let mut playing = true;
+    let mut finished = false;
+
+    loop {
+        let state = match (finished, playing) {
+            (true, _) => "Finished",
+            (false, true) => "Playing",
+            (false, false) => "Paused",
+        };
+        let footer = [
+            simulation.status(),
+            format!("{} at {} steps a second.  {}", state, speed, CONTROLS),
+        ];
+        draw(stdout, &simulation.draw(), &footer, rows, columns)?;
+
+        let key = if playing {
+            match keys.recv_timeout(frame_interval(speed)) {
+                Ok(key) => Some(key),
+                Err(RecvTimeoutError::Timeout) => None,
+                // There's no keyboard, so the waiting has to happen here.
+                Err(RecvTimeoutError::Disconnected) => {
+                    thread::sleep(frame_interval(speed));
+                    None
+                }
+            }
+        } else {
+            match keys.recv() {
+                Ok(key) => Some(key),
+                Err(_) => return Ok(()),
+            }
+        };
+
+        match key {
+            None => {
+                for _ in 0..steps_per_frame(speed) {
+                    if !simulation.step() {
+                        finished = true;
+                        playing = false;
+                        break;
+                    }
+                }
+            }
+            Some(Key::PlayPause) => playing = !playing && !finished,
+            Some(Key::Step) => {
+                playing = false;
+                finished = !simulation.step();
+            }
+            Some(Key::Faster) => speed = (speed * 2).min(MAX_SPEED),
+            Some(Key::Slower) => speed = (speed / 2).max(1),
+            Some(Key::Restart) => {
+                *simulation = start()?;
+                finished = false;
+            }
+            Some(Key::Quit) => return Ok(()),
+        }
+    }
+}
+
+fn frame_interval(speed: usize) -> Duration {
+    Duration::from_secs(1) / speed.clamp(1, FRAMES_PER_SECOND) as u32
+}
+
+fn steps_per_frame(speed: usize) -> usize {
+    (speed / FRAMES_PER_SECOND).max(1)
+}
+
+// Draws over the last frame rather than clearing the screen first, which would flicker. Anything
+// that won't fit on the screen is cut off, keeping room for the footer.
+fn draw(
+    stdout: &mut dyn Write,
+    lines: &[String],
+    footer: &[String],
+    rows: usize,
+    columns: usize,
+) -> io::Result<()> {
+    let mut frame = String::from(HOME);
+    let room = rows.saturating_sub(footer.len() + 1);
+
+    for line in lines
+        .iter()
+        .take(room)
+        .chain([&String::new()])
+        .chain(footer)
+    {
+        frame.extend(line.chars().take(columns));
+        frame.push_str(CLEAR_LINE);
+        frame.push_str("\r\n");
+    }
+    frame.push_str(CLEAR_BELOW);
+
+    stdout.write_all(frame.as_bytes())?;
+    stdout.flush()
+}
+
+fn write_frame(stdout: &mut dyn Write, simulation: &dyn Simulation) -> io::Result<()> {
+    for line in simulation.draw() {
+        writeln!(stdout, "{}", line)?;
+    }
+
+    writeln!(stdout, "{}\n", simulation.status())
+}
+
+// Reads keys on their own thread, so waiting for the next frame can be cut short by one.
+fn read_keys() -> Receiver<Key> {
+    let (sender, keys) = mpsc::channel();
+
+    thread::spawn(move || {
+        let mut bytes = io::stdin().lock().bytes().map_while(Result::ok);
+
+        while let Some(byte) = bytes.next() {
+            let key = match byte {
+                b' ' | b'p' => Key::PlayPause,
+                b'n' | b'.' => Key::Step,
+                b'+' | b'=' => Key::Faster,
+                b'-' | b'_' => Key::Slower,
+                b'r' => Key::Restart,
+                // Ctrl-C arrives as a byte too, since raw mode stops it being a signal.
+                b'q' | 3 => Key::Quit,
+                // The arrow keys come as escape sequences: right steps, up and down change speed.
+                0x1b if bytes.next() == Some(b'[') => match bytes.next() {
+                    Some(b'C') => Key::Step,
+                    Some(b'A') => Key::Faster,
+                    Some(b'B') => Key::Slower,
+                    _ => continue,
+                },
+                _ => continue,
+            };
+
+            if sender.send(key).is_err() {
+                break;
+            }
+        }
+    });
+
+    keys
+}
+
+// Std can't switch the terminal into raw mode itself, so this asks `stty` to, and puts the old
+// settings back when it's dropped.
+struct RawMode {
+    saved: String,
+}
+
+impl RawMode {
+    fn enable() -> Option<Self> {
+        if !io::stdin().is_terminal() {
+            return None;
+        }
+
+        let saved = stty(&["-g"])?;
+        stty(&["-icanon", "-echo", "-isig", "min", "1"])?;
+
+        Some(Self {
+            saved: saved.trim().to_string(),
+        })
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        stty(&[&self.saved]);
+    }
+}
+
+fn stty(args: &[&str]) -> Option<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+
+    if output.status.success() {
+        String::from_utf8(output.stdout).ok()
+    } else {
+        None
+    }
+}
+
+// As (rows, columns).
+fn terminal_size() -> Option<(usize, usize)> {
+    let size = stty(&["size"])?;
+    let (rows, columns) = size.trim().split_once(' ')?;
+
+    Some((rows.parse().ok()?, columns.parse().ok()?))
+}
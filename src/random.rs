@@ -0,0 +1,56 @@
+use std::ops::RangeInclusive;
+
+// A small seeded generator (SplitMix64), so anything built from it can be made again from the same
+// seed. It's nowhere near good enough for anything secret, but every seed works, including 0.
+#[derive(Clone, Debug)]
+pub struct Random {
+    state: u64,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut value = self.state;
+        value = (value ^ (value >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        value ^ (value >> 31)
+    }
+
+    // A number from 0 up to but not including `bound`, which can't be 0. Scaling by the bound
+    // instead of taking a remainder keeps small bounds from favouring the low numbers.
+    pub fn below(&mut self, bound: usize) -> usize {
+        assert!(bound > 0, "Random::below needs a bound above 0");
+
+        ((self.next_u64() as u128 * bound as u128) >> 64) as usize
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<usize>) -> usize {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "Random::range needs a range that isn't empty");
+
+        match (end - start).checked_add(1) {
+            Some(width) => start + self.below(width),
+            None => self.next_u64() as usize,
+        }
+    }
+
+    // True about once in every `times` calls.
+    pub fn one_in(&mut self, times: usize) -> bool {
+        self.below(times) == 0
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for index in (1..items.len()).rev() {
+            items.swap(index, self.below(index + 1));
+        }
+    }
+}
@@ -0,0 +1,362 @@
+use std::cmp::Reverse;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::process::ExitCode;
+
+use advent_of_code_2022::input::Input;
+use advent_of_code_2022::solver::{self, Solver};
+use advent_of_code_2022::{day1, day11, day13, day5, day7, day8, timing, BoxedResult};
+
+use crate::cli::Options;
+
+pub const HELP: &str = "\
+Commands:
+  load <DAY> [PATH]  Read DAY's puzzle input from PATH (default: inputs/dayN.txt)
+  run [PART]         Solve the loaded input, timing a single run
+  time [PART]        Benchmark the loaded input, with separate parse and solve times
+  show [VIEW]        Show part of the loaded input after parsing, or list the views for its day
+  help               Show this message
+  quit               Leave";
+
+// Ways of looking at a day's parsed input, as (day, name, description).
+const VIEWS: &[(usize, &str, &str)] = &[
+    (1, "elves", "Every elf's snacks and their total"),
+    (5, "stacks", "The stacks of crates before any moves"),
+    (7, "tree", "The whole filesystem, with sizes"),
+    (7, "sizes", "Every directory's total size, biggest first"),
+    (8, "map", "The tree heights"),
+    (11, "monkeys", "Every monkey's items, operation and test"),
+    (
+        13,
+        "pairs",
+        "Each pair of packets and whether it's in order",
+    ),
+    (
+        13,
+        "packets",
+        "Every packet in order, along with the dividers",
+    ),
+];
+
+#[derive(Debug)]
+pub enum CommandError {
+    NothingLoaded,
+    NoSuchDay(usize),
+    NoSuchPart(usize, usize),
+    NoSuchView(usize, String),
+    InvalidNumber(String),
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NothingLoaded => write!(f, "Nothing's loaded yet. Try 'load 1'."),
+            Self::NoSuchDay(day) => write!(f, "There's no solution for day {}.", day),
+            Self::NoSuchPart(day, part) => write!(f, "Day {} has no part {}.", day, part),
+            Self::NoSuchView(day, view) => {
+                write!(f, "Day {} has no view '{}'. Try 'show'.", day, view)
+            }
+            Self::InvalidNumber(value) => write!(f, "'{}' isn't a number.", value),
+        }
+    }
+}
+
+impl Error for CommandError {}
+
+struct Loaded {
+    day: usize,
+    text: String,
+}
+
+struct Repl<'a> {
+    options: &'a Options,
+    solvers: Vec<&'static dyn Solver>,
+    loaded: Option<Loaded>,
+}
+
+// Reads commands from stdin until it runs out or is told to quit. The prompt is only shown when
+// someone's typing, so a script of commands can be piped in too.
+pub fn run(options: &Options) -> ExitCode {
+    let mut repl = Repl {
+        options,
+        solvers: solver::registry(),
+        loaded: None,
+    };
+    let interactive = io::stdin().is_terminal();
+
+    if interactive {
+        println!("Type 'help' for a list of commands.");
+    }
+
+    loop {
+        if interactive {
+            print!("> ");
+            let _ = io::stdout().flush();
+        }
+
+        let mut line = String::new();
+        match io::stdin().lock().read_line(&mut line) {
+            Ok(0) => return ExitCode::SUCCESS,
+            Ok(_) => {}
+            Err(error) => {
+                eprintln!("Couldn't read command: {}", error);
+                return ExitCode::FAILURE;
+            }
+        }
+
+        let words = line.split_whitespace().collect::<Vec<&str>>();
+        let result = match words.as_slice() {
+            [] => Ok(()),
+            ["quit" | "exit"] => return ExitCode::SUCCESS,
+            ["help"] => {
+                println!("{}", HELP);
+                Ok(())
+            }
+            ["load", day] => repl.load(day, None),
+            ["load", day, path] => repl.load(day, Some(path)),
+            ["run"] => repl.run(None),
+            ["run", part] => repl.run(Some(part)),
+            ["time"] => repl.time(None),
+            ["time", part] => repl.time(Some(part)),
+            ["show"] => repl.list_views(),
+            ["show", view] => repl.show(view),
+            _ => {
+                println!("Unknown command '{}'. Type 'help' for a list.", line.trim());
+                Ok(())
+            }
+        };
+
+        if let Err(error) = result {
+            println!("{}", error);
+        }
+    }
+}
+
+impl Repl<'_> {
+    fn load(&mut self, day: &str, path: Option<&str>) -> BoxedResult<()> {
+        let day = parse_number(day)?;
+        if !self.solvers.iter().any(|solver| solver.day() == day) {
+            return Err(CommandError::NoSuchDay(day).into());
+        }
+
+        let input = match (path, &self.options.input_directory) {
+            (Some(path), _) => Input::Path(path.into()),
+            (None, Some(directory)) => Input::in_directory(directory, day),
+            (None, None) => Input::for_day(day),
+        };
+
+        let text = io::read_to_string(input.open()?)
+            .map_err(|error| format!("Couldn't read {}: {}", input, error))?;
+
+        println!(
+            "Loaded {} for day {} ({} lines).",
+            input,
+            day,
+            text.lines().count()
+        );
+        self.loaded = Some(Loaded { day, text });
+
+        Ok(())
+    }
+
+    fn run(&self, part: Option<&str>) -> BoxedResult<()> {
+        let loaded = self.loaded()?;
+
+        for solver in self.selected(part)? {
+            let result = timing::time_solver(solver, &Input::Text(loaded.text.clone()));
+            println!(
+                "Day {}, part {}: {}",
+                solver.day(),
+                solver.part(),
+                crate::format_timed_result(result)
+            );
+        }
+
+        Ok(())
+    }
+
+    fn time(&self, part: Option<&str>) -> BoxedResult<()> {
+        let loaded = self.loaded()?;
+
+        for solver in self.selected(part)? {
+            let result = timing::benchmark_solver(
+                solver,
+                &Input::Text(loaded.text.clone()),
+                self.options.warmup,
+                self.options.iterations,
+            );
+            println!(
+                "Day {}, part {}: {}",
+                solver.day(),
+                solver.part(),
+                crate::format_benchmark(result)
+            );
+        }
+
+        Ok(())
+    }
+
+    fn list_views(&self) -> BoxedResult<()> {
+        let loaded = self.loaded()?;
+        let views = VIEWS
+            .iter()
+            .filter(|(day, _, _)| *day == loaded.day)
+            .collect::<Vec<&(usize, &str, &str)>>();
+
+        if views.is_empty() {
+            println!("Day {} doesn't have any views yet.", loaded.day);
+        }
+
+        for (_, name, description) in views {
+            println!("  {:<10} {}", name, description);
+        }
+
+        Ok(())
+    }
+
+    fn show(&self, view: &str) -> BoxedResult<()> {
+        let loaded = self.loaded()?;
+        let text = loaded.text.as_bytes();
+
+        let shown = match (loaded.day, view) {
+            (1, "elves") => show_elves(&day1::parse_elves(text)?),
+            (5, "stacks") => day5::parse_procedure(text)?.unload_space.to_string(),
+            (7, "tree") => day7::TreeBuilder::build_from_reader(text)?
+                .borrow()
+                .to_string(),
+            (7, "sizes") => show_sizes(&day7::TreeBuilder::build_from_reader(text)?),
+            (8, "map") => day8::Map::from_reader(text)?.to_string(),
+            (11, "monkeys") => format!("{:#?}", day11::parse_monkeys(text)?),
+            (13, "pairs") => show_pairs(&day13::part1::ListSet::from_reader(text)?),
+            (13, "packets") => show_packets(day13::part2::parse_packets(text)?),
+            _ => return Err(CommandError::NoSuchView(loaded.day, String::from(view)).into()),
+        };
+
+        println!("{}", shown);
+
+        Ok(())
+    }
+
+    fn loaded(&self) -> Result<&Loaded, CommandError> {
+        self.loaded.as_ref().ok_or(CommandError::NothingLoaded)
+    }
+
+    // Every part of the loaded day, or just the one asked for.
+    fn selected(&self, part: Option<&str>) -> BoxedResult<Vec<&'static dyn Solver>> {
+        let day = self.loaded()?.day;
+        let part = part.map(parse_number).transpose()?;
+
+        let selected = self
+            .solvers
+            .iter()
+            .copied()
+            .filter(|solver| solver.day() == day && part.is_none_or(|part| solver.part() == part))
+            .collect::<Vec<&'static dyn Solver>>();
+
+        match (part, selected.is_empty()) {
+            (Some(part), true) => Err(CommandError::NoSuchPart(day, part).into()),
+            _ => Ok(selected),
+        }
+    }
+}
+
+fn parse_number(value: &str) -> Result<usize, CommandError> {
+    value
+        .parse()
+        .map_err(|_| CommandError::InvalidNumber(String::from(value)))
+}
+
+fn show_elves(elves: &[day1::Elf]) -> String {
+    elves
+        .iter()
+        .enumerate()
+        .map(|(index, elf)| {
+            format!(
+                "Elf {}: {:?}, {} in total",
+                index + 1,
+                elf,
+                elf.iter().sum::<usize>()
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn show_sizes(root: &day7::MagicNode) -> String {
+    let mut sizes = Vec::<(String, usize)>::new();
+    collect_sizes(root, String::new(), &mut sizes);
+    sizes.sort_by_key(|(_, size)| Reverse(*size));
+
+    sizes
+        .into_iter()
+        .map(|(path, size)| format!("{:>10} {}", size, path))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn collect_sizes(node: &day7::MagicNode, parent_path: String, sizes: &mut Vec<(String, usize)>) {
+    let node = node.borrow();
+    let path = if node.name == "/" {
+        String::from("/")
+    } else {
+        format!("{}{}/", parent_path, node.name)
+    };
+
+    for child in &node.contents {
+        if child.borrow().is_directory {
+            collect_sizes(child, path.clone(), sizes);
+        }
+    }
+
+    sizes.push((path, node.size));
+}
+
+fn show_pairs(list_set: &day13::part1::ListSet) -> String {
+    list_set
+        .pairs
+        .iter()
+        .enumerate()
+        .map(|(index, pair)| {
+            format!(
+                "Pair {}: {}\n  {}\n  {}",
+                index + 1,
+                if pair.is_ordered() {
+                    "in order"
+                } else {
+                    "out of order"
+                },
+                pair.left,
+                pair.right
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn show_packets(packets: Vec<day13::List>) -> String {
+    use day13::part2::{divider, FIRST_DIVIDER, SECOND_DIVIDER};
+
+    // Packets like [2] are equal to a divider by the puzzle's rules, so the dividers are kept track
+    // of by which entries they are. The sort is stable, so they still end up after any packets
+    // equal to them, where part 2 counts them.
+    let mut packets = packets
+        .into_iter()
+        .map(|packet| (packet, false))
+        .chain([
+            (divider(FIRST_DIVIDER), true),
+            (divider(SECOND_DIVIDER), true),
+        ])
+        .collect::<Vec<(day13::List, bool)>>();
+    packets.sort_by(|a, b| a.0.cmp(&b.0));
+
+    packets
+        .iter()
+        .enumerate()
+        .map(|(index, (packet, is_divider))| {
+            let marker = if *is_divider { "  <- divider" } else { "" };
+            format!("{:>4} {}{}", index + 1, packet, marker)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
@@ -0,0 +1,222 @@
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::solver::Answer;
+use crate::TimedResult;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Json,
+    Csv,
+    Markdown,
+}
+
+#[derive(Debug)]
+pub struct UnknownFormat(pub String);
+
+impl fmt::Display for UnknownFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unknown format '{}'.", self.0)
+    }
+}
+
+impl error::Error for UnknownFormat {}
+
+impl FromStr for Format {
+    type Err = UnknownFormat;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            "markdown" => Ok(Self::Markdown),
+            _ => Err(UnknownFormat(String::from(value))),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Status {
+    Ok,
+    Error,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ok => write!(f, "ok"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+// One solver's outcome, flattened down to plain values so it can be written out in any format.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    pub day: usize,
+    pub part: usize,
+    pub answer: Option<Answer>,
+    pub error: Option<String>,
+    pub time: Option<Duration>,
+}
+
+impl Record {
+    pub fn new(day: usize, part: usize, result: TimedResult<Answer>) -> Self {
+        match result {
+            Ok((answer, time)) => Self {
+                day,
+                part,
+                answer: Some(answer),
+                error: None,
+                time: Some(time),
+            },
+            Err(error) => Self {
+                day,
+                part,
+                answer: None,
+                error: Some(error.to_string()),
+                time: None,
+            },
+        }
+    }
+
+    pub fn status(&self) -> Status {
+        if self.error.is_some() {
+            Status::Error
+        } else {
+            Status::Ok
+        }
+    }
+}
+
+pub fn render(format: Format, records: &[Record]) -> String {
+    match format {
+        Format::Json => render_json(records),
+        Format::Csv => render_csv(records),
+        Format::Markdown => render_markdown(records),
+    }
+}
+
+fn render_json(records: &[Record]) -> String {
+    let objects = records
+        .iter()
+        .map(|record| format!("  {}", render_json_record(record)))
+        .collect::<Vec<String>>();
+
+    if objects.is_empty() {
+        String::from("[]")
+    } else {
+        format!("[\n{}\n]", objects.join(",\n"))
+    }
+}
+
+// A single record as a one-line JSON object, the same as each entry in the JSON array.
+pub fn render_json_record(record: &Record) -> String {
+    format!(
+        "{{\"day\": {}, \"part\": {}, \"answer\": {}, \"status\": \"{}\", \"error\": {}, \"time_ns\": {}}}",
+        record.day,
+        record.part,
+        json_string_or_null(record.answer.as_ref().map(Answer::to_string)),
+        record.status(),
+        json_string_or_null(record.error.clone()),
+        record
+            .time
+            .map_or(String::from("null"), |time| time.as_nanos().to_string()),
+    )
+}
+
+pub fn json_string_or_null(value: Option<String>) -> String {
+    match value {
+        Some(value) => format!("\"{}\"", escape_json(&value)),
+        None => String::from("null"),
+    }
+}
+
+fn escape_json(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for character in value.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            _ if character.is_control() => {
+                escaped.push_str(&format!("\\u{:04x}", character as u32))
+            }
+            _ => escaped.push(character),
+        }
+    }
+
+    escaped
+}
+
+fn render_csv(records: &[Record]) -> String {
+    let mut lines = vec![String::from("day,part,answer,status,error,time_ns")];
+
+    for record in records {
+        lines.push(format!(
+            "{},{},{},{},{},{}",
+            record.day,
+            record.part,
+            escape_csv(
+                &record
+                    .answer
+                    .as_ref()
+                    .map_or(String::new(), Answer::to_string)
+            ),
+            record.status(),
+            escape_csv(record.error.as_deref().unwrap_or("")),
+            record
+                .time
+                .map_or(String::new(), |time| time.as_nanos().to_string()),
+        ));
+    }
+
+    lines.join("\n")
+}
+
+// Fields are only quoted when they have to be, which is the common reading of RFC 4180.
+fn escape_csv(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        String::from(value)
+    }
+}
+
+fn render_markdown(records: &[Record]) -> String {
+    let mut lines = vec![
+        String::from("| Day | Part | Answer | Status | Time | Error |"),
+        String::from("| --: | ---: | ------ | ------ | ---: | ----- |"),
+    ];
+
+    for record in records {
+        lines.push(format!(
+            "| {} | {} | {} | {} | {} | {} |",
+            record.day,
+            record.part,
+            escape_markdown(
+                &record
+                    .answer
+                    .as_ref()
+                    .map_or(String::new(), Answer::to_string)
+            ),
+            record.status(),
+            record
+                .time
+                .map_or(String::new(), |time| format!("{:?}", time)),
+            escape_markdown(record.error.as_deref().unwrap_or("")),
+        ));
+    }
+
+    lines.join("\n")
+}
+
+// Table cells have to stay on one line, so pictures like day 10's CRT get line breaks instead.
+fn escape_markdown(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', "<br>")
+}
@@ -0,0 +1,288 @@
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{mpsc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::input::Input;
+use crate::report::{self, Record, Status};
+use crate::solver::{self, Solver};
+use crate::timing;
+
+// Anything bigger than this is turned away before it's read. Real puzzle inputs are a few tens of
+// kilobytes.
+const MAX_BODY_SIZE: usize = 16 * 1024 * 1024;
+const MAX_HEADERS: usize = 100;
+// Connections are handled by this many threads at most. Any more wait to be accepted until one of
+// them is free, so slow clients and big inputs can only ever tie up this many at once.
+pub const WORKERS: usize = 8;
+// A client that goes quiet partway through a request, or stops reading the response, only ties up
+// its thread for this long.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+const WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+
+// A tiny HTTP/1.1 server for running the solutions. Connections are handed out to a fixed pool of
+// threads, and each is closed after one response.
+//
+//   GET  /days               Every available part, as a JSON array
+//   POST /day/{n}/part/{m}   Solves the request body as that part's input, answering with the same
+//                            JSON object as `--format json` uses for one result
+pub struct Server {
+    listener: TcpListener,
+}
+
+impl Server {
+    pub fn bind<A: ToSocketAddrs>(address: A) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(address)?,
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    // Only returns if accepting a connection fails, once the connections already accepted are done.
+    pub fn serve(&self) -> io::Result<()> {
+        // With no room in the channel, the next connection isn't accepted until a worker takes it.
+        let (sender, receiver) = mpsc::sync_channel::<TcpStream>(0);
+        let receiver = Mutex::new(receiver);
+
+        thread::scope(|scope| {
+            for _ in 0..WORKERS {
+                let receiver = &receiver;
+
+                scope.spawn(move || loop {
+                    let stream = match receiver.lock() {
+                        Ok(receiver) => receiver.recv(),
+                        Err(_) => break,
+                    };
+                    let Ok(stream) = stream else {
+                        break;
+                    };
+
+                    // There's nobody to tell if the client hung up on us. Anything that panics
+                    // outside of solving only loses its own connection, not the worker.
+                    let _ = panic::catch_unwind(AssertUnwindSafe(|| handle_connection(stream)));
+                });
+            }
+
+            let result = self
+                .listener
+                .incoming()
+                .try_for_each(|stream| sender.send(stream?).map_err(io::Error::other));
+            // Lets the workers finish once they've dealt with what they've got.
+            drop(sender);
+
+            result
+        })
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+struct Response {
+    status: u16,
+    reason: &'static str,
+    body: String,
+}
+
+impl Response {
+    fn json(status: u16, reason: &'static str, body: String) -> Self {
+        Self {
+            status,
+            reason,
+            body,
+        }
+    }
+
+    // Errors that aren't about a particular part just carry a message.
+    fn error(status: u16, reason: &'static str, message: &str) -> Self {
+        Self::json(
+            status,
+            reason,
+            format!(
+                "{{\"error\": {}}}",
+                report::json_string_or_null(Some(String::from(message)))
+            ),
+        )
+    }
+
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        write!(
+            writer,
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status,
+            self.reason,
+            self.body.len(),
+            self.body
+        )?;
+        writer.flush()
+    }
+}
+
+fn handle_connection(stream: TcpStream) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    // A solver that panics on some input is a bug, but the client still gets an answer.
+    let response = match read_request(&mut reader) {
+        Ok(request) => panic::catch_unwind(AssertUnwindSafe(|| respond(&request)))
+            .unwrap_or_else(|_| Response::error(500, "Internal Server Error", "Solving panicked.")),
+        Err(response) => response,
+    };
+
+    response.write_to(&mut &stream)
+}
+
+fn read_request<R: BufRead>(reader: &mut R) -> Result<Request, Response> {
+    let bad_request = |message| Response::error(400, "Bad Request", message);
+
+    let request_line =
+        read_line(reader).ok_or_else(|| bad_request("Couldn't read the request."))?;
+    let mut parts = request_line.split_whitespace();
+    let (method, target, version) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(method), Some(target), Some(version)) => (method, target, version),
+        _ => return Err(bad_request("Malformed request line.")),
+    };
+
+    if !version.starts_with("HTTP/1.") {
+        return Err(Response::error(
+            505,
+            "HTTP Version Not Supported",
+            "Only HTTP/1.1 is supported.",
+        ));
+    }
+
+    let mut content_length: usize = 0;
+
+    for header_count in 0.. {
+        let line = read_line(reader).ok_or_else(|| bad_request("Couldn't read the headers."))?;
+        if line.is_empty() {
+            break;
+        }
+
+        if header_count == MAX_HEADERS {
+            return Err(Response::error(
+                431,
+                "Request Header Fields Too Large",
+                "Too many headers.",
+            ));
+        }
+
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| bad_request("Malformed header."))?;
+
+        if name.eq_ignore_ascii_case("content-length") {
+            content_length = value
+                .trim()
+                .parse()
+                .map_err(|_| bad_request("Invalid Content-Length."))?;
+        } else if name.eq_ignore_ascii_case("transfer-encoding") {
+            return Err(Response::error(
+                411,
+                "Length Required",
+                "Send the body with a Content-Length instead.",
+            ));
+        }
+    }
+
+    if content_length > MAX_BODY_SIZE {
+        return Err(Response::error(
+            413,
+            "Content Too Large",
+            "The input is too big.",
+        ));
+    }
+
+    let mut body = vec![0; content_length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|_| bad_request("The body was shorter than its Content-Length."))?;
+
+    // Query strings aren't used for anything, so they're dropped.
+    let path = target.split('?').next().unwrap_or(target);
+
+    Ok(Request {
+        method: String::from(method),
+        path: String::from(path),
+        body,
+    })
+}
+
+// Lines end in CRLF, but a bare LF is accepted too. `None` means the connection ended or failed.
+fn read_line<R: BufRead>(reader: &mut R) -> Option<String> {
+    let mut line = String::new();
+    match reader.read_line(&mut line) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(String::from(line.trim_end_matches(['\r', '\n']))),
+    }
+}
+
+fn respond(request: &Request) -> Response {
+    let segments = request.path.split('/').skip(1).collect::<Vec<&str>>();
+
+    match (request.method.as_str(), segments.as_slice()) {
+        ("GET", ["days"]) => list_days(),
+        ("POST", ["day", day, "part", part]) => match (day.parse(), part.parse()) {
+            (Ok(day), Ok(part)) => solve(day, part, &request.body),
+            _ => Response::error(404, "Not Found", "Days and parts are numbers."),
+        },
+        (_, ["days"]) | (_, ["day", _, "part", _]) => {
+            Response::error(405, "Method Not Allowed", "Wrong method for this path.")
+        }
+        _ => Response::error(404, "Not Found", "No such path."),
+    }
+}
+
+fn list_days() -> Response {
+    let entries = solver::registry()
+        .iter()
+        .map(|solver| {
+            format!(
+                "{{\"day\": {}, \"part\": {}, \"name\": {}}}",
+                solver.day(),
+                solver.part(),
+                report::json_string_or_null(Some(String::from(solver.name())))
+            )
+        })
+        .collect::<Vec<String>>();
+
+    Response::json(200, "OK", format!("[{}]", entries.join(", ")))
+}
+
+// An input the solver rejects still gets the full record, just with a different status code.
+fn solve(day: usize, part: usize, body: &[u8]) -> Response {
+    let solvers = solver::registry();
+    let solver = match find_solver(&solvers, day, part) {
+        Some(solver) => solver,
+        None => return Response::error(404, "Not Found", "No solution for that day and part."),
+    };
+
+    let text = match String::from_utf8(body.to_vec()) {
+        Ok(text) => text,
+        Err(_) => return Response::error(400, "Bad Request", "The input isn't valid UTF-8."),
+    };
+
+    let record = Record::new(day, part, timing::time_solver(solver, &Input::Text(text)));
+    let (status, reason) = match record.status() {
+        Status::Ok => (200, "OK"),
+        Status::Error => (422, "Unprocessable Content"),
+    };
+
+    Response::json(status, reason, report::render_json_record(&record))
+}
+
+fn find_solver<'a>(solvers: &[&'a dyn Solver], day: usize, part: usize) -> Option<&'a dyn Solver> {
+    solvers
+        .iter()
+        .copied()
+        .find(|solver| solver.day() == day && solver.part() == part)
+}
@@ -0,0 +1,163 @@
+use std::any::Any;
+use std::fmt;
+use std::io::BufRead;
+
+use crate::{day1, day10, day11, day12, day13, day2, day3, day4, day5, day6, day7, day8, day9};
+use crate::{Error, Result};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Answer {
+    Unsigned(usize),
+    Signed(isize),
+    Text(String),
+    Picture(Vec<String>),
+}
+
+impl Answer {
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Self::Picture(_))
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Self::Unsigned(value)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(value: isize) -> Self {
+        Self::Signed(value)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<Vec<String>> for Answer {
+    fn from(value: Vec<String>) -> Self {
+        Self::Picture(value)
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsigned(value) => write!(f, "{}", value),
+            Self::Signed(value) => write!(f, "{}", value),
+            Self::Text(value) => write!(f, "{}", value),
+            Self::Picture(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+// Whatever a solver's parse step produced, handed back to the same solver's solve step.
+pub type Parsed = Box<dyn Any>;
+
+pub fn downcast<T: 'static>(parsed: Parsed) -> Result<T> {
+    match parsed.downcast::<T>() {
+        Ok(value) => Ok(*value),
+        Err(_) => Err(Error::WrongParsedType(std::any::type_name::<T>())),
+    }
+}
+
+// Solving is split in two so the parsing and the actual puzzle logic can be timed separately.
+pub trait Solver: Sync {
+    fn day(&self) -> usize;
+    fn part(&self) -> usize;
+    fn name(&self) -> &'static str;
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Parsed>;
+    fn solve_parsed(&self, parsed: Parsed) -> Result<Answer>;
+
+    fn solve(&self, input: &mut dyn BufRead) -> Result<Answer> {
+        self.solve_parsed(self.parse(input)?)
+    }
+}
+
+// A solution that reads its input once from start to end, keeping no more of it than it has to,
+// for inputs too big to load whole. It solves as it reads, so there's nothing to split: the whole
+// run counts as parsing, and solving just hands the answer on.
+pub struct Streaming {
+    pub day: usize,
+    pub part: usize,
+    pub name: &'static str,
+    // The most it keeps in memory at once, however long the input is.
+    pub memory: &'static str,
+    pub stream: fn(&mut dyn BufRead) -> Result<Answer>,
+}
+
+impl Solver for Streaming {
+    fn day(&self) -> usize {
+        self.day
+    }
+
+    fn part(&self) -> usize {
+        self.part
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Parsed> {
+        Ok(Box::new((self.stream)(input)?))
+    }
+
+    fn solve_parsed(&self, parsed: Parsed) -> Result<Answer> {
+        downcast::<Answer>(parsed)
+    }
+}
+
+// A deliberately naive solution to one part, written straight from the puzzle text to check the
+// real one against. It gives up with `None` wherever the real one would report an error.
+pub type Reference = fn(&str) -> Option<Answer>;
+
+// Every solution, in day and part order.
+pub fn registry() -> Vec<&'static dyn Solver> {
+    [
+        day1::SOLVERS,
+        day2::SOLVERS,
+        day3::SOLVERS,
+        day4::SOLVERS,
+        day5::SOLVERS,
+        day6::SOLVERS,
+        day7::SOLVERS,
+        day8::SOLVERS,
+        day9::SOLVERS,
+        day10::SOLVERS,
+        day11::SOLVERS,
+        day12::SOLVERS,
+        day13::SOLVERS,
+    ]
+    .concat()
+}
+
+// Every streaming solution, in day and part order. Only some days have one, since most puzzles
+// need all of their input at once anyway.
+pub fn streaming_registry() -> Vec<&'static Streaming> {
+    [day6::STREAMING, day8::STREAMING, day13::STREAMING].concat()
+}
+
+pub fn reference(day: usize, part: usize) -> Option<Reference> {
+    let references = match day {
+        1 => day1::REFERENCES,
+        2 => day2::REFERENCES,
+        3 => day3::REFERENCES,
+        4 => day4::REFERENCES,
+        5 => day5::REFERENCES,
+        6 => day6::REFERENCES,
+        7 => day7::REFERENCES,
+        8 => day8::REFERENCES,
+        9 => day9::REFERENCES,
+        10 => day10::REFERENCES,
+        11 => day11::REFERENCES,
+        12 => day12::REFERENCES,
+        13 => day13::REFERENCES,
+        _ => return None,
+    };
+
+    references.get(part.checked_sub(1)?).copied()
+}
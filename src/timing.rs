@@ -1,13 +1,144 @@
-use std::fmt::Display;
-use std::time::Instant;
+use std::error;
+use std::fmt;
+use std::io::Read;
+use std::time::{Duration, Instant};
 
-use crate::BoxedResult;
+use crate::input::Input;
+use crate::solver::{Answer, Solver};
+use crate::{BoxedResult, TimedResult};
+
+pub fn time_solver(solver: &dyn Solver, input: &Input) -> TimedResult<Answer> {
+    let mut reader = input.open()?;
 
-pub fn time_function<T: Display>(func: fn() -> BoxedResult<T>) -> String {
     let start_time = Instant::now();
+    let answer = solver.solve(&mut reader)?;
+
+    Ok((answer, start_time.elapsed()))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Statistics {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub std_dev: Duration,
+}
+
+impl Statistics {
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let count = sorted.len();
+        let middle = count / 2;
+        let median = if count.is_multiple_of(2) {
+            (sorted[middle - 1] + sorted[middle]) / 2
+        } else {
+            sorted[middle]
+        };
+
+        // Nearest-rank percentile, so the p95 is always one of the actual samples.
+        let p95 = sorted[(count * 95).div_ceil(100) - 1];
 
-    match func() {
-        Ok(result) => format!("{} (in {} ms)", result, start_time.elapsed().as_millis()),
-        Err(error) => format!("Errored: {}", error),
+        let total: u128 = sorted.iter().map(Duration::as_nanos).sum();
+        let mean_nanos = total as f64 / count as f64;
+        let variance = sorted
+            .iter()
+            .map(|sample| (sample.as_nanos() as f64 - mean_nanos).powi(2))
+            .sum::<f64>()
+            / count as f64;
+
+        Some(Self {
+            min: sorted[0],
+            median,
+            mean: Duration::from_nanos(mean_nanos.round() as u64),
+            p95,
+            std_dev: Duration::from_nanos(variance.sqrt().round() as u64),
+        })
+    }
+}
+
+impl fmt::Display for Statistics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {:?}, median {:?}, mean {:?}, p95 {:?}, std dev {:?}",
+            self.min, self.median, self.mean, self.p95, self.std_dev
+        )
     }
 }
+
+#[derive(Debug)]
+pub struct Benchmark {
+    pub answer: Answer,
+    pub iterations: usize,
+    pub parse: Statistics,
+    pub solve: Statistics,
+    pub total: Statistics,
+}
+
+#[derive(Debug)]
+pub struct NoIterations;
+
+impl fmt::Display for NoIterations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "A benchmark needs at least one iteration.")
+    }
+}
+
+impl error::Error for NoIterations {}
+
+// Runs the solver `warmup` times without recording anything, then `iterations` more times timing
+// the parse and solve steps separately. The input is read into memory up front so that disk and
+// stdin speed don't end up in the parse times.
+pub fn benchmark_solver(
+    solver: &dyn Solver,
+    input: &Input,
+    warmup: usize,
+    iterations: usize,
+) -> BoxedResult<Benchmark> {
+    if iterations == 0 {
+        return Err(NoIterations.into());
+    }
+
+    let mut bytes = Vec::<u8>::new();
+    input.open()?.read_to_end(&mut bytes)?;
+
+    for _ in 0..warmup {
+        solver.solve(&mut bytes.as_slice())?;
+    }
+
+    let mut parse_samples = Vec::<Duration>::with_capacity(iterations);
+    let mut solve_samples = Vec::<Duration>::with_capacity(iterations);
+    let mut total_samples = Vec::<Duration>::with_capacity(iterations);
+    let mut answer = None;
+
+    for _ in 0..iterations {
+        let start_time = Instant::now();
+        let parsed = solver.parse(&mut bytes.as_slice())?;
+        let parse_time = start_time.elapsed();
+
+        let start_time = Instant::now();
+        let result = solver.solve_parsed(parsed)?;
+        let solve_time = start_time.elapsed();
+
+        parse_samples.push(parse_time);
+        solve_samples.push(solve_time);
+        total_samples.push(parse_time + solve_time);
+
+        answer = Some(result);
+    }
+
+    Ok(Benchmark {
+        answer: answer.ok_or(NoIterations)?,
+        iterations,
+        parse: Statistics::from_samples(&parse_samples).ok_or(NoIterations)?,
+        solve: Statistics::from_samples(&solve_samples).ok_or(NoIterations)?,
+        total: Statistics::from_samples(&total_samples).ok_or(NoIterations)?,
+    })
+}
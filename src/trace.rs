@@ -0,0 +1,180 @@
+use std::env;
+use std::error;
+use std::fmt::{self, Display};
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Mutex;
+
+use crate::BoxedResult;
+
+// Tracing is off unless `-v`/`-vv` or these variables turn it on. The command line wins over them.
+pub const LEVEL_VARIABLE: &str = "AOC_TRACE";
+pub const FILE_VARIABLE: &str = "AOC_TRACE_FILE";
+
+// How much the solvers say about what they're doing. Debug is the steps of each puzzle, like moves
+// and throws, and trace adds how every line of the input got parsed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Off,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    // How many `-v`s it takes.
+    pub fn from_verbosity(count: usize) -> Self {
+        match count {
+            0 => Self::Off,
+            1 => Self::Debug,
+            _ => Self::Trace,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Debug => "debug",
+            Self::Trace => "trace",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct UnknownLevel(pub String);
+
+impl fmt::Display for UnknownLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Unknown trace level '{}', expected off, debug or trace.",
+            self.0
+        )
+    }
+}
+
+impl error::Error for UnknownLevel {}
+
+impl FromStr for Level {
+    type Err = UnknownLevel;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "" | "0" | "off" => Ok(Self::Off),
+            "1" | "debug" => Ok(Self::Debug),
+            "2" | "trace" => Ok(Self::Trace),
+            _ => Err(UnknownLevel(String::from(value))),
+        }
+    }
+}
+
+// Checked before every event, so an atomic keeps it cheap when tracing is off.
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Off as u8);
+
+// Where events go, or stderr when there's no file. Answers always go to stdout, so either way
+// tracing can't get mixed into them. Nothing's buffered, so a run that's stopped with Ctrl-C (like
+// --watch or --serve) still leaves every event in the file.
+static FILE: Mutex<Option<File>> = Mutex::new(None);
+
+pub fn level() -> Level {
+    match LEVEL.load(Ordering::Relaxed) {
+        0 => Level::Off,
+        1 => Level::Debug,
+        _ => Level::Trace,
+    }
+}
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn enabled(level: Level) -> bool {
+    level != Level::Off && level <= self::level()
+}
+
+// Sends every event from now on to `path`, replacing whatever's there.
+pub fn to_file(path: &Path) -> io::Result<()> {
+    let file = File::create(path)?;
+    *FILE.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(file);
+
+    Ok(())
+}
+
+// Turns tracing on from the command line, falling back to the environment variables for whatever
+// the command line didn't say. Asking for a file without a level means debug.
+pub fn start(level: Option<Level>, file: Option<&Path>) -> BoxedResult<Level> {
+    let level = match level {
+        Some(level) => level,
+        None => match env::var(LEVEL_VARIABLE) {
+            Ok(value) => value.parse()?,
+            Err(_) if file.is_some() => Level::Debug,
+            Err(_) => Level::Off,
+        },
+    };
+
+    let file = file
+        .map(PathBuf::from)
+        .or_else(|| env::var_os(FILE_VARIABLE).map(PathBuf::from));
+
+    if level != Level::Off {
+        if let Some(path) = file {
+            to_file(&path)?;
+        }
+    }
+
+    set_level(level);
+    Ok(level)
+}
+
+// One event per line, as `level day=N event=NAME key=value...`, so they're easy to grep and split.
+// Values with spaces or quotes in them get quoted. Failing to write is ignored, since tracing
+// mustn't change how solving goes.
+pub fn write_event(level: Level, day: usize, event: &str, fields: &[(&str, &dyn Display)]) {
+    let mut line = format!("{} day={} event={}", level.name(), day, event);
+    for (key, value) in fields {
+        let value = value.to_string();
+        if value.is_empty() || value.contains(|c: char| c.is_whitespace() || c == '"') {
+            line.push_str(&format!(" {}={:?}", key, value));
+        } else {
+            line.push_str(&format!(" {}={}", key, value));
+        }
+    }
+    line.push('\n');
+
+    let mut file = FILE.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let _ = match file.as_mut() {
+        Some(file) => file.write_all(line.as_bytes()),
+        None => io::stderr().lock().write_all(line.as_bytes()),
+    };
+}
+
+// `trace::debug!(5, "move", quantity = 3, from = 1)` records a debug event for day 5, but only
+// works out the values when that level is on.
+macro_rules! event {
+    ($level:expr, $day:expr, $event:expr $(, $key:ident = $value:expr)* $(,)?) => {
+        if $crate::trace::enabled($level) {
+            $crate::trace::write_event(
+                $level,
+                $day,
+                $event,
+                &[$((stringify!($key), &$value as &dyn ::std::fmt::Display)),*],
+            );
+        }
+    };
+}
+
+macro_rules! debug {
+    ($($arguments:tt)*) => {
+        $crate::trace::event!($crate::trace::Level::Debug, $($arguments)*)
+    };
+}
+
+macro_rules! trace {
+    ($($arguments:tt)*) => {
+        $crate::trace::event!($crate::trace::Level::Trace, $($arguments)*)
+    };
+}
+
+pub(crate) use {debug, event, trace};
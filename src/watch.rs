@@ -0,0 +1,128 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use advent_of_code_2022::input::Input;
+use advent_of_code_2022::parallel;
+use advent_of_code_2022::solver::{Answer, Solver};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// A single timed run, with the error flattened to its message so it can come back from a worker.
+pub type Outcome = Result<(Answer, Duration), String>;
+
+// Runs every job once, then keeps polling their inputs' modification times. Whenever one changes,
+// the jobs reading it are run again and compared against what they printed last time. This only
+// stops when the process is killed.
+pub fn watch<F>(jobs: &[(&dyn Solver, Input)], threads: usize, run: F) -> !
+where
+    F: Fn(&(&dyn Solver, Input)) -> Outcome + Sync,
+{
+    let mut times = jobs
+        .iter()
+        .map(|(_, input)| modification_time(input))
+        .collect::<Vec<Option<SystemTime>>>();
+    let mut outcomes = parallel::map(jobs, threads, &run);
+
+    for ((solver, _), outcome) in jobs.iter().zip(&outcomes) {
+        println!("{}", describe(*solver, None, outcome));
+    }
+
+    let watched = jobs
+        .iter()
+        .map(|(_, input)| input.to_string())
+        .collect::<BTreeSet<String>>();
+    println!(
+        "\nWatching {} for changes. Press Ctrl-C to stop.",
+        watched.into_iter().collect::<Vec<String>>().join(", ")
+    );
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        let changed = (0..jobs.len())
+            .filter(|index| modification_time(&jobs[*index].1) != times[*index])
+            .collect::<Vec<usize>>();
+
+        if changed.is_empty() {
+            continue;
+        }
+
+        let changed_inputs = changed
+            .iter()
+            .map(|index| jobs[*index].1.to_string())
+            .collect::<BTreeSet<String>>();
+        println!(
+            "\n{} changed.",
+            changed_inputs
+                .into_iter()
+                .collect::<Vec<String>>()
+                .join(", ")
+        );
+
+        // Taking the times before running means an edit made partway through still gets noticed.
+        for index in &changed {
+            times[*index] = modification_time(&jobs[*index].1);
+        }
+
+        let rerun = parallel::map(&changed, threads, |index| run(&jobs[*index]));
+
+        for (index, outcome) in changed.into_iter().zip(rerun) {
+            println!(
+                "{}",
+                describe(jobs[index].0, Some(&outcomes[index]), &outcome)
+            );
+            outcomes[index] = outcome;
+        }
+    }
+}
+
+// A missing or unreadable file has no time, so it counts as changed when it appears again.
+fn modification_time(input: &Input) -> Option<SystemTime> {
+    match input {
+        Input::Path(path) => fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok(),
+        _ => None,
+    }
+}
+
+fn describe(solver: &dyn Solver, previous: Option<&Outcome>, current: &Outcome) -> String {
+    let heading = format!("Day {}, part {}:", solver.day(), solver.part());
+
+    // Pictures and error snippets take up several lines, so they aren't repeated when unchanged.
+    let answer = match (previous.map(answer_text), answer_text(current)) {
+        (Some(before), now) if before == now && now.contains('\n') => String::from("(unchanged)"),
+        (Some(before), now) if before == now => format!("{} (unchanged)", now),
+        (Some(before), now) if before.contains('\n') || now.contains('\n') => {
+            format!("changed from\n{}\nto\n{}", before, now)
+        }
+        (Some(before), now) => format!("{} -> {}", before, now),
+        (None, now) => now,
+    };
+
+    let time = match (previous.and_then(duration), duration(current)) {
+        (Some(before), Some(now)) => format!(" (in {:?}, was {:?})", now, before),
+        (None, Some(now)) => format!(" (in {:?})", now),
+        (_, None) => String::new(),
+    };
+
+    // Anything spread over several lines goes underneath, with the times above it.
+    if answer.contains('\n') {
+        format!("{}{}\n{}", heading, time, answer)
+    } else {
+        format!("{} {}{}", heading, answer, time)
+    }
+}
+
+fn answer_text(outcome: &Outcome) -> String {
+    match outcome {
+        Ok((answer, _)) => answer.to_string(),
+        Err(error) => format!("Errored: {}", error),
+    }
+}
+
+fn duration(outcome: &Outcome) -> Option<Duration> {
+    outcome.as_ref().ok().map(|(_, duration)| *duration)
+}
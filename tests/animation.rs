@@ -0,0 +1,95 @@
+use std::fs;
+use std::process::Command;
+
+use advent_of_code_2022::animation::{self, Simulation};
+
+fn animate_example(day: usize, part: usize) -> Box<dyn Simulation> {
+    let input = fs::read(format!("examples/day{}/input.txt", day)).unwrap();
+    animation::animator(day).unwrap()(&mut input.as_slice(), part).unwrap()
+}
+
+// The last frame, and the status that goes with it.
+fn play_to_the_end(day: usize, part: usize) -> (Vec<String>, String) {
+    let mut simulation = animate_example(day, part);
+    let last = animation::frames(simulation.as_mut()).pop().unwrap();
+
+    (last, simulation.status())
+}
+
+#[test]
+fn crates_are_drawn_the_way_the_puzzle_draws_them() {
+    let simulation = animate_example(5, 1);
+    assert_eq!(
+        simulation.draw(),
+        ["    [D]", "[N] [C]", "[Z] [M] [P]", " 1   2   3 "]
+    );
+
+    // One frame before any moves, then one after each of the four.
+    let frames = animation::frames(animate_example(5, 1).as_mut());
+    assert_eq!(frames.len(), 5);
+
+    let (last, _) = play_to_the_end(5, 2);
+    assert_eq!(last.first().unwrap().trim(), "[D]");
+    assert_eq!(last.last().unwrap(), " 1   2   3 ");
+}
+
+#[test]
+fn knots_are_labelled_from_head_to_tail() {
+    let simulation = animate_example(9, 1);
+    let start = simulation.draw().concat();
+    assert!(start.contains('H'));
+    assert!(!start.contains('1'));
+
+    let (last, status) = play_to_the_end(9, 1);
+    assert!(last.concat().contains('T'));
+    assert!(status.contains("visited 13 squares"));
+
+    let (last, status) = play_to_the_end(9, 2);
+    assert!(last.concat().contains(".1H3"));
+    assert!(status.contains("visited 1 squares"));
+}
+
+#[test]
+fn the_crt_lights_up_one_pixel_a_cycle() {
+    let (last, status) = play_to_the_end(10, 1);
+    let crt = &last[last.len() - 6..];
+    assert_eq!(crt[0], "##..##..##..##..##..##..##..##..##..##..");
+    assert!(crt
+        .iter()
+        .all(|row| row.len() == 40 && row.chars().all(|pixel| pixel == '#' || pixel == '.')));
+    assert!(status.contains("add up to 13140"));
+}
+
+#[test]
+fn monkeys_inspect_as_many_items_as_solving_says() {
+    let (last, _) = play_to_the_end(11, 1);
+    let counts = last
+        .iter()
+        .filter_map(|line| line.split(" inspected items ").nth(1))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        counts,
+        ["101 times.", "95 times.", "7 times.", "105 times."]
+    );
+}
+
+#[test]
+fn frames_are_printed_when_there_is_no_terminal() {
+    let output = Command::new(env!("CARGO_BIN_EXE_advent-of-code-2022"))
+        .args(["animate", "5.2", "--input", "examples/day5/input.txt"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!stdout.contains('\x1b'));
+    assert_eq!(stdout.matches(" 1   2   3 ").count(), 5);
+    assert!(stdout.contains("Move 4 of 4: move 1 from 1 to 2"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_advent-of-code-2022"))
+        .args(["animate", "3"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+}
@@ -0,0 +1,157 @@
+use std::path::PathBuf;
+
+use advent_of_code_2022::answers::{Answers, Verdict};
+use advent_of_code_2022::input::Input;
+use advent_of_code_2022::solver::Answer;
+
+fn path(path: &str) -> Input {
+    Input::Path(PathBuf::from(path))
+}
+
+fn parse(text: &str) -> Answers {
+    Answers::from_reader(text.as_bytes()).unwrap()
+}
+
+fn parse_error(text: &str) -> String {
+    Answers::from_reader(text.as_bytes())
+        .unwrap_err()
+        .to_string()
+}
+
+fn write(answers: &Answers) -> String {
+    let mut output = Vec::<u8>::new();
+    answers.write_to(&mut output).unwrap();
+
+    String::from_utf8(output).unwrap()
+}
+
+fn crt() -> Answer {
+    Answer::from(vec![
+        String::from("##..##..##"),
+        String::from("###...###."),
+        String::from("####....##"),
+    ])
+}
+
+#[test]
+fn answers_are_read_by_day_part_and_input() {
+    let answers = parse(
+        "# A comment.\n\n1.1 inputs/day1.txt = 24000\n 1.2   inputs/day1.txt   =   45000  \n",
+    );
+
+    assert_eq!(
+        answers.expected(1, 1, &path("inputs/day1.txt")),
+        Some("24000")
+    );
+    assert_eq!(
+        answers.expected(1, 2, &path("inputs/day1.txt")),
+        Some("45000")
+    );
+    assert_eq!(answers.expected(1, 1, &path("inputs/day2.txt")), None);
+    assert_eq!(answers.expected(2, 1, &path("inputs/day1.txt")), None);
+}
+
+#[test]
+fn paths_match_however_they_start() {
+    let answers = parse("1.1 ./inputs/day1.txt = 24000\n");
+
+    assert_eq!(
+        answers.expected(1, 1, &path("inputs/day1.txt")),
+        Some("24000")
+    );
+    assert_eq!(
+        answers.expected(1, 1, &path("./inputs/day1.txt")),
+        Some("24000")
+    );
+}
+
+#[test]
+fn pictures_are_read_from_the_indented_lines() {
+    let answers =
+        parse("10.2 inputs/day10.txt =\n  ##..##..##\n  ###...###.\n  ####....##\n1.1 x = 1\n");
+
+    assert_eq!(
+        answers.expected(10, 2, &path("inputs/day10.txt")),
+        Some("##..##..##\n###...###.\n####....##")
+    );
+    assert_eq!(answers.expected(1, 1, &path("x")), Some("1"));
+}
+
+#[test]
+fn what_is_written_reads_back_the_same() {
+    let mut answers = Answers::default();
+    answers.insert(1, 1, &path("inputs/day1.txt"), &Answer::from(24000usize));
+    answers.insert(10, 1, &path("inputs/day10.txt"), &Answer::from(-13140isize));
+    answers.insert(
+        5,
+        1,
+        &path("my inputs/day 5.txt"),
+        &Answer::from(String::from("CMZ")),
+    );
+    answers.insert(10, 2, &path("inputs/day10.txt"), &crt());
+
+    let written = write(&answers);
+    assert!(written.contains("1.1 inputs/day1.txt = 24000\n"));
+    assert!(written.contains("5.1 my inputs/day 5.txt = CMZ\n"));
+    assert!(written.contains("10.2 inputs/day10.txt =\n  ##..##..##\n  ###...###.\n  ####....##\n"));
+
+    let read = parse(&written);
+    assert_eq!(read, answers);
+    assert_eq!(write(&read), written);
+    assert_eq!(
+        read.check(
+            5,
+            1,
+            &path("my inputs/day 5.txt"),
+            &Answer::from(String::from("CMZ"))
+        ),
+        Verdict::Pass
+    );
+    assert_eq!(
+        read.check(10, 2, &path("inputs/day10.txt"), &crt()),
+        Verdict::Pass
+    );
+}
+
+#[test]
+fn answers_are_checked_against_what_was_recorded() {
+    let answers = parse("1.1 inputs/day1.txt = 24000\n");
+    let input = path("inputs/day1.txt");
+
+    assert_eq!(
+        answers.check(1, 1, &input, &Answer::from(24000usize)),
+        Verdict::Pass
+    );
+    assert_eq!(
+        answers.check(1, 1, &input, &Answer::from(1usize)),
+        Verdict::Mismatch(String::from("24000"))
+    );
+    assert_eq!(
+        answers.check(1, 2, &input, &Answer::from(1usize)),
+        Verdict::Missing
+    );
+}
+
+#[test]
+fn malformed_lines_say_which_line_they_are() {
+    assert_eq!(
+        parse_error("1.1 a = 1\n1.2 a 2\n"),
+        "Line 2 is missing its '='."
+    );
+    assert_eq!(
+        parse_error("one.1 a = 1\n"),
+        "Line 1 doesn't start with '<day>.<part> <input>'."
+    );
+    assert_eq!(
+        parse_error("1.1 = 1\n"),
+        "Line 1 doesn't start with '<day>.<part> <input>'."
+    );
+    assert_eq!(
+        parse_error("1.1 a = 1\n  ##..\n"),
+        "Line 2 is indented but doesn't continue an answer."
+    );
+    assert_eq!(
+        parse_error("  ##..\n"),
+        "Line 1 is indented but doesn't continue an answer."
+    );
+}
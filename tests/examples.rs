@@ -0,0 +1,122 @@
+use std::collections::BTreeSet;
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use advent_of_code_2022::answers::Answers;
+use advent_of_code_2022::input::Input;
+use advent_of_code_2022::solver;
+
+const EXAMPLES_DIRECTORY: &str = "examples";
+const ANSWERS_FILE: &str = "answers.txt";
+
+// Every `examples/dayN/` directory holds one or more sample inputs next to an `answers.txt` keyed by
+// those inputs' paths. Each part is run against every input that has an answer recorded for it.
+#[test]
+fn every_part_matches_its_examples() {
+    let solvers = solver::registry();
+    let mut covered = BTreeSet::<(usize, usize)>::new();
+    let mut failures = Vec::<String>::new();
+
+    for (day, directory) in example_directories() {
+        let answers = Answers::from_reader(BufReader::new(
+            File::open(directory.join(ANSWERS_FILE)).unwrap(),
+        ))
+        .unwrap();
+
+        for path in example_inputs(&directory) {
+            let input = Input::Path(path);
+
+            for solver in solvers.iter().filter(|solver| solver.day() == day) {
+                let expected = match answers.expected(day, solver.part(), &input) {
+                    Some(expected) => expected,
+                    None => continue,
+                };
+
+                covered.insert((day, solver.part()));
+
+                match solver.solve(&mut input.open().unwrap()) {
+                    Ok(answer) if answer.to_string() == expected => {}
+                    Ok(answer) => failures.push(format!(
+                        "{}.{} on {}: expected\n{}\nbut got\n{}",
+                        day,
+                        solver.part(),
+                        input,
+                        expected,
+                        answer
+                    )),
+                    Err(error) => failures.push(format!(
+                        "{}.{} on {}: errored with {}",
+                        day,
+                        solver.part(),
+                        input,
+                        error
+                    )),
+                }
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+
+    let uncovered = solvers
+        .iter()
+        .map(|solver| (solver.day(), solver.part()))
+        .filter(|key| !covered.contains(key))
+        .map(|(day, part)| format!("{}.{}", day, part))
+        .collect::<Vec<String>>();
+
+    assert!(
+        uncovered.is_empty(),
+        "No examples for {}.",
+        uncovered.join(", ")
+    );
+}
+
+// Errors opening an input have to say which file it was, since the OS's message doesn't.
+#[test]
+fn inputs_that_cant_be_opened_are_named() {
+    let path = Path::new(EXAMPLES_DIRECTORY)
+        .join("day0")
+        .join("missing.txt");
+    let error = Input::Path(path.clone()).open().err().unwrap().to_string();
+
+    assert!(
+        error.starts_with(&format!("Couldn't open {}: ", path.display())),
+        "{}",
+        error
+    );
+}
+
+fn example_directories() -> Vec<(usize, PathBuf)> {
+    let mut directories = fs::read_dir(EXAMPLES_DIRECTORY)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter_map(|path| {
+            let day = path
+                .file_name()?
+                .to_str()?
+                .strip_prefix("day")?
+                .parse::<usize>()
+                .ok()?;
+            Some((day, path))
+        })
+        .collect::<Vec<(usize, PathBuf)>>();
+
+    directories.sort();
+    directories
+}
+
+fn example_inputs(directory: &Path) -> Vec<PathBuf> {
+    let mut inputs = fs::read_dir(directory)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            path.extension().is_some_and(|extension| extension == "txt")
+                && path.file_name().is_some_and(|name| name != ANSWERS_FILE)
+        })
+        .collect::<Vec<PathBuf>>();
+
+    inputs.sort();
+    inputs
+}
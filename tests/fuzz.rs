@@ -0,0 +1,92 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use advent_of_code_2022::fuzz::{self, Failure, Worker};
+use advent_of_code_2022::generate;
+use advent_of_code_2022::random::Random;
+use advent_of_code_2022::solver;
+
+const RUNS: usize = 200;
+const SEED: u64 = 2022;
+
+fn worker(day: usize, part: usize) -> Worker {
+    Worker::new(
+        PathBuf::from(env!("CARGO_BIN_EXE_advent-of-code-2022")),
+        vec![String::from("fuzz-worker"), format!("{}.{}", day, part)],
+        Duration::from_secs(5),
+    )
+}
+
+// A short run for every part, from generated inputs, which should find nothing now that the
+// parsers have been through the full fuzzer.
+#[test]
+fn no_parser_fails_a_short_fuzz() {
+    let mut failures = Vec::<String>::new();
+
+    for solver in solver::registry() {
+        let mut input = Vec::new();
+        generate::generator(solver.day()).unwrap()(5, &mut Random::new(SEED), &mut input).unwrap();
+
+        let findings = fuzz::fuzz(
+            &mut worker(solver.day(), solver.part()),
+            &[input],
+            RUNS,
+            &mut Random::new(SEED),
+        )
+        .unwrap();
+
+        for finding in findings {
+            failures.push(format!(
+                "{}.{} {} on {:?}",
+                solver.day(),
+                solver.part(),
+                finding.failure,
+                String::from_utf8_lossy(&finding.input)
+            ));
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+// The worker survives inputs that fail to parse, and keeps answering after them.
+#[test]
+fn the_worker_reports_nothing_for_errors() {
+    let mut worker = worker(13, 1);
+
+    assert_eq!(worker.run(b"[1,[2\n").unwrap(), None);
+    assert_eq!(worker.run("[".repeat(10_000).as_bytes()).unwrap(), None);
+    assert_eq!(worker.run(b"[1]\n[2]\n").unwrap(), None);
+}
+
+#[test]
+fn minimizing_keeps_only_what_still_fails() {
+    let input = b"lots of harmless text around the bad bytes, and then some more";
+    let minimized = fuzz::minimize(input, |candidate| {
+        candidate.windows(3).any(|window| window == b"bad")
+    });
+
+    assert_eq!(minimized, b"bad");
+}
+
+#[test]
+fn the_same_seed_mutates_the_same_way() {
+    let input = b"move 1 from 2 to 1\nmove 3 from 1 to 3\n";
+
+    for seed in 0..20 {
+        assert_eq!(
+            fuzz::mutate(input, &mut Random::new(seed)),
+            fuzz::mutate(input, &mut Random::new(seed))
+        );
+    }
+}
+
+#[test]
+fn panics_are_the_same_failure_only_in_the_same_place() {
+    let panic = |location: &str| Failure::Panic(String::from(location), String::from("oops"));
+
+    assert!(panic("src/a.rs:1:1").is_same_as(&panic("src/a.rs:1:1")));
+    assert!(!panic("src/a.rs:1:1").is_same_as(&panic("src/a.rs:2:1")));
+    assert!(Failure::Hang.is_same_as(&Failure::Hang));
+    assert!(!Failure::Hang.is_same_as(&Failure::StackOverflow));
+}
@@ -0,0 +1,117 @@
+use advent_of_code_2022::generate::{self, GENERATORS};
+use advent_of_code_2022::input::Input;
+use advent_of_code_2022::random::Random;
+use advent_of_code_2022::solver::{self, Answer};
+
+const SEEDS: [u64; 4] = [0, 1, 2022, u64::MAX];
+const SIZES: [usize; 4] = [1, 2, 15, 100];
+
+fn generate(day: usize, size: usize, seed: u64) -> String {
+    let mut output = Vec::<u8>::new();
+    generate::generator(day).unwrap()(size, &mut Random::new(seed), &mut output).unwrap();
+
+    String::from_utf8(output).unwrap()
+}
+
+// Whatever the seed and size, every part of the day has to find an answer in what's generated.
+#[test]
+fn every_part_solves_generated_inputs() {
+    let solvers = solver::registry();
+    let mut failures = Vec::<String>::new();
+
+    for (day, _, _) in GENERATORS {
+        for seed in SEEDS {
+            for size in SIZES {
+                let input = Input::Text(generate(*day, size, seed));
+
+                for solver in solvers.iter().filter(|solver| solver.day() == *day) {
+                    let mut reader = input.open().unwrap();
+                    if let Err(error) = solver.solve(&mut reader) {
+                        failures.push(format!(
+                            "Day {}, part {} with size {} and seed {}: {}",
+                            day,
+                            solver.part(),
+                            size,
+                            seed,
+                            error
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn every_day_has_a_generator() {
+    for solver in solver::registry() {
+        assert!(
+            generate::generator(solver.day()).is_some(),
+            "No generator for day {}",
+            solver.day()
+        );
+    }
+}
+
+#[test]
+fn the_same_seed_makes_the_same_input() {
+    for (day, _, _) in GENERATORS {
+        assert_eq!(generate(*day, 50, 7), generate(*day, 50, 7));
+        assert_ne!(generate(*day, 50, 7), generate(*day, 50, 8));
+    }
+}
+
+// Day 13's deepest pair is as deep as the parser allows, so this catches either side changing.
+#[test]
+fn the_deepest_packets_can_be_solved() {
+    let input = Input::Text(generate(13, 500, 1));
+
+    for solver in solver::registry()
+        .iter()
+        .filter(|solver| solver.day() == 13)
+    {
+        solver.solve(&mut input.open().unwrap()).unwrap();
+    }
+}
+
+// Day 9's longest moves go billions of squares, which only works if the rope jumps straight to the
+// end of them. The answers have to agree with the reference's, and with one worked out by hand.
+#[test]
+fn the_longest_rope_moves_are_solved() {
+    let input = generate(9, 200, 9);
+    let longest = format!(" {}", generate::LONGEST_MOVE);
+    assert!(input.lines().next().unwrap().ends_with(&longest));
+    assert!(
+        input
+            .lines()
+            .filter(|line| line[2..].parse::<usize>().unwrap() > 1 << 20)
+            .count()
+            > 5
+    );
+
+    // Out along a row, then turning at the end.
+    let turning = "R 4000000000\nU 5\nL 100\n";
+
+    for solver in solver::registry().iter().filter(|solver| solver.day() == 9) {
+        let reference = solver::reference(9, solver.part()).unwrap();
+        assert_eq!(
+            solver.solve(&mut input.as_bytes()).ok(),
+            reference(&input),
+            "part {}",
+            solver.part()
+        );
+
+        let expected: usize = if solver.part() == 1 {
+            4_000_000_103
+        } else {
+            4_000_000_079
+        };
+        assert_eq!(
+            solver.solve(&mut turning.as_bytes()).unwrap(),
+            Answer::from(expected)
+        );
+        assert_eq!(reference(turning), Some(Answer::from(expected)));
+    }
+}
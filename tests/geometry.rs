@@ -0,0 +1,60 @@
+use advent_of_code_2022::geometry::{BoundingBox, Direction, Vector2};
+
+#[test]
+fn distances() {
+    let vector = Vector2::new(3, -4);
+
+    assert_eq!(vector.manhattan(), 7);
+    assert_eq!(vector.chebyshev(), 4);
+    assert_eq!(vector.euclidean(), 5.0);
+    assert_eq!((vector - Vector2::new(3, -4)).manhattan(), 0);
+}
+
+#[test]
+fn operators() {
+    let mut vector = Vector2::new(1, 2) + Vector2::new(3, 4) * 2;
+    assert_eq!(vector, Vector2::new(7, 10));
+
+    vector -= Vector2::new(7, 0);
+    vector *= -1;
+    assert_eq!(vector, -Vector2::new(0, 10));
+    assert_eq!(Vector2::new(-5, 0).signum(), Vector2::new(-1, 0));
+}
+
+#[test]
+fn rotations_match_the_directions() {
+    for direction in Direction::ALL {
+        assert_eq!(
+            direction.vector().rotated_left(),
+            direction.turned_left().vector()
+        );
+        assert_eq!(
+            direction.vector().rotated_right(),
+            direction.turned_right().vector()
+        );
+        assert_eq!(-direction.vector(), direction.opposite().vector());
+    }
+
+    assert_eq!(Direction::Up.turned_right(), Direction::Right);
+}
+
+#[test]
+fn bounding_boxes_cover_every_point() {
+    let points = [Vector2::new(2, -1), Vector2::new(-3, 4), Vector2::new(0, 0)];
+    let bounds = BoundingBox::around(points).unwrap();
+
+    assert_eq!(bounds.min, Vector2::new(-3, -1));
+    assert_eq!(bounds.max, Vector2::new(2, 4));
+    assert_eq!((bounds.width(), bounds.height()), (6, 6));
+    assert!(points.iter().all(|point| bounds.contains(*point)));
+    assert_eq!(bounds.to_position(Vector2::new(-3, -1)), Some((0, 0)));
+    assert_eq!(bounds.to_position(Vector2::new(3, 0)), None);
+    assert_eq!(BoundingBox::around([]), None);
+}
+
+#[test]
+fn grid_positions() {
+    assert_eq!(Vector2::new(2, 3).to_position(), Some((2, 3)));
+    assert_eq!(Vector2::new(-1, 3).to_position(), None);
+    assert_eq!(Vector2::from((4, 5)), Vector2::new(4, 5));
+}
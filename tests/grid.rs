@@ -0,0 +1,81 @@
+use advent_of_code_2022::geometry::{Direction, Vector2};
+use advent_of_code_2022::grid::Grid;
+
+const DIGITS: &str = "123\n456\n";
+
+fn digits() -> Grid<u8> {
+    Grid::from_reader(DIGITS.as_bytes(), 0, "a digit", |_, byte| {
+        byte.is_ascii_digit().then(|| byte - b'0')
+    })
+    .unwrap()
+}
+
+#[test]
+fn parsing_keeps_the_layout() {
+    let grid = digits();
+
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid[(0, 0)], 1);
+    assert_eq!(grid[(2, 1)], 6);
+    assert_eq!(grid.get((3, 0)), None);
+    assert_eq!(grid.to_string(), DIGITS.trim_end());
+}
+
+#[test]
+fn parsing_rejects_bad_cells_and_ragged_rows() {
+    let parse = |text: &str| {
+        Grid::from_reader(text.as_bytes(), 0, "a digit", |_, byte| {
+            byte.is_ascii_digit().then_some(byte)
+        })
+    };
+
+    assert!(parse("12\n3x\n").is_err());
+    assert!(parse("12\n345\n").is_err());
+    assert!(parse("").unwrap().is_empty());
+}
+
+#[test]
+fn rows_and_columns() {
+    let grid = digits();
+
+    assert_eq!(grid.row(1), [4, 5, 6]);
+    assert_eq!(grid.column(1).copied().collect::<Vec<u8>>(), [2, 5]);
+    assert_eq!(grid.rows().count(), 2);
+    assert_eq!(grid.columns().count(), 3);
+}
+
+#[test]
+fn neighbours_stay_on_the_grid() {
+    let grid = digits();
+
+    let mut corner = grid.neighbours((0, 0)).collect::<Vec<_>>();
+    corner.sort();
+    assert_eq!(corner, [(0, 1), (1, 0)]);
+    assert_eq!(grid.neighbours((1, 1)).count(), 3);
+    assert_eq!(grid.neighbours_with_diagonals((1, 0)).count(), 5);
+}
+
+#[test]
+fn rays_run_to_the_edge() {
+    let grid = digits();
+
+    assert_eq!(
+        grid.ray((0, 0), Direction::Right.vector())
+            .collect::<Vec<_>>(),
+        [(1, 0), (2, 0)]
+    );
+    assert_eq!(grid.ray((0, 0), Direction::Up.vector()).count(), 0);
+    assert_eq!(
+        grid.ray((0, 0), Vector2::new(1, 1)).collect::<Vec<_>>(),
+        [(1, 1)]
+    );
+}
+
+#[test]
+fn transposing_swaps_rows_and_columns() {
+    let transposed = digits().transposed();
+
+    assert_eq!((transposed.width(), transposed.height()), (2, 3));
+    assert_eq!(transposed.to_string(), "14\n25\n36");
+    assert_eq!(transposed.transposed(), digits());
+}
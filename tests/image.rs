@@ -0,0 +1,114 @@
+use std::fs;
+use std::path::Path;
+
+use advent_of_code_2022::day12::Map;
+use advent_of_code_2022::grid::Grid;
+use advent_of_code_2022::image::{self, Color, Format, Picture};
+
+fn checkerboard() -> Picture {
+    Grid::new(2, 1, vec![Color::new(255, 0, 0), Color::new(0, 0, 255)])
+}
+
+fn paint_example(day: usize) -> Picture {
+    let input = fs::read(format!("examples/day{}/input.txt", day)).unwrap();
+    image::painter(day).unwrap()(&mut input.as_slice()).unwrap()
+}
+
+fn written(format: Format, picture: &Picture, scale: usize) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    image::write(&mut bytes, format, picture, scale).unwrap();
+    bytes
+}
+
+#[test]
+fn formats_come_from_extensions() {
+    assert_eq!(Format::from_path(Path::new("a.ppm")), Some(Format::Ppm));
+    assert_eq!(Format::from_path(Path::new("dir/b.PGM")), Some(Format::Pgm));
+    assert_eq!(Format::from_path(Path::new("c.svg")), Some(Format::Svg));
+    assert_eq!(Format::from_path(Path::new("d.png")), None);
+    assert_eq!(Format::from_path(Path::new("ppm")), None);
+}
+
+#[test]
+fn netpbm_pictures_are_scaled_up() {
+    let ppm = written(Format::Ppm, &checkerboard(), 2);
+    let header = b"P6\n4 2\n255\n";
+    assert_eq!(&ppm[..header.len()], header);
+
+    let row = [255, 0, 0, 255, 0, 0, 0, 0, 255, 0, 0, 255];
+    assert_eq!(&ppm[header.len()..], [row, row].concat());
+
+    let pgm = written(Format::Pgm, &checkerboard(), 1);
+    assert_eq!(pgm, [&b"P5\n2 1\n255\n"[..], &[76, 29]].concat());
+}
+
+#[test]
+fn svg_pictures_merge_runs_of_one_colour() {
+    let picture = Grid::filled(3, 2, Color::WHITE);
+    let svg = String::from_utf8(written(Format::Svg, &picture, 5)).unwrap();
+
+    assert!(svg.starts_with("<svg "));
+    assert!(svg.contains("width=\"15\" height=\"10\""));
+    assert_eq!(svg.matches("<rect ").count(), 2);
+    assert!(svg.contains("<rect x=\"0\" y=\"5\" width=\"15\" height=\"5\" fill=\"#ffffff\"/>"));
+    assert!(svg.trim_end().ends_with("</svg>"));
+}
+
+#[test]
+fn colours_mix_and_scales_fit() {
+    let mixed = Color::BLACK.towards(Color::WHITE, 1, 2);
+    assert_eq!(mixed, Color::gray(127));
+    assert_eq!(Color::BLACK.towards(Color::WHITE, 5, 2), Color::WHITE);
+
+    assert!(image::canvas(0, 1 << 12, 1 << 12).is_err());
+    let picture = image::canvas(0, 1 << 10, 1 << 10).unwrap();
+    assert_eq!(image::fitting_scale(&picture, 64), 8);
+    assert_eq!(image::fitting_scale(&checkerboard(), 64), 64);
+}
+
+#[test]
+fn examples_are_drawn() {
+    // The CRT draws six rows of forty pixels, and every lit one is white.
+    let crt = paint_example(10);
+    assert_eq!((crt.width(), crt.height()), (40, 6));
+    assert!(crt
+        .iter()
+        .all(|pixel| *pixel == Color::WHITE || *pixel == Color::BLACK));
+
+    let forest = paint_example(8);
+    assert_eq!((forest.width(), forest.height()), (5, 5));
+
+    // The path is 31 steps, so it covers 32 squares.
+    let heightmap = paint_example(12);
+    assert_eq!(
+        heightmap
+            .iter()
+            .filter(|color| **color == Color::RED)
+            .count(),
+        32
+    );
+
+    let tails = paint_example(9);
+    assert!(tails.iter().any(|color| *color == Color::RED));
+}
+
+#[test]
+fn routes_follow_the_rules() {
+    let map = Map::from_reader(fs::read("examples/day12/input.txt").unwrap().as_slice()).unwrap();
+    let route = map.find_route_from(map.start).unwrap();
+
+    assert_eq!(route.first(), Some(&map.start));
+    assert_eq!(route.last(), Some(&map.end));
+    assert_eq!(
+        route.len() - 1,
+        map.find_shortest_path_from(map.start).unwrap()
+    );
+
+    for step in route.windows(2) {
+        assert_eq!(
+            step[0].0.abs_diff(step[1].0) + step[0].1.abs_diff(step[1].1),
+            1
+        );
+        assert!(map.heightmap[step[1]] <= map.heightmap[step[0]] + 1);
+    }
+}
@@ -0,0 +1,289 @@
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+
+use advent_of_code_2022::solver::{self, Solver};
+use advent_of_code_2022::Error;
+
+const EXAMPLES_DIRECTORY: &str = "examples";
+const MUTATIONS_PER_EXAMPLE: usize = 40;
+const RANDOM_INPUTS: usize = 200;
+
+// Bytes that show up in real inputs, plus a few that never should.
+const ALPHABET: &[u8] =
+    b"0123456789 \n\n,-[]$/.:abcdeszABCXYZSERLUD move from to cd ls dir addx noop old * + \xff";
+
+// Inputs that are broken in the ways the solvers used to panic on. Each of these must come back as
+// an error for the given day.
+const KNOWN_MALFORMED: &[(usize, &str)] = &[
+    (1, "1000\nlots\n"),
+    (2, "A Q\n"),
+    (2, "A\n"),
+    (3, "\u{e9}a\n"),
+    (5, "    [D]\n[N] [C]\n 1   2\n\nmove 1 from 0 to 1\n"),
+    (7, "$ cd /\n$ ls\n18446744073709551615 a\n1 b\n"),
+    (8, ""),
+    (8, "30373\n255\n"),
+    (9, "R 4\nU 18446744073709551615\n"),
+    (10, "addx 99999999999\n"),
+    (
+        11,
+        "Monkey 0:\n  Starting items: 79\n  Operation: new = old * 19\n  Test: by 23\n",
+    ),
+    (12, "Sbc\nxyz\n"),
+    (13, "[1,[2,3]\n[1]\n"),
+];
+
+// Every solver gets fed nonsense, truncated examples and examples with random bytes changed. It's
+// fine for any of them to error, or even to find an answer, but none of them may panic.
+#[test]
+fn no_part_panics_on_malformed_input() {
+    let solvers = solver::registry();
+    let mut random = Random(0x2022_1201);
+    let mut failures = Vec::<String>::new();
+
+    let mut inputs = vec![
+        Vec::new(),
+        b"\n".to_vec(),
+        b"\n\n\n".to_vec(),
+        b"garbage".to_vec(),
+        "\u{1f384}\u{1f384}\u{1f384}".as_bytes().to_vec(),
+        vec![0xff, 0xfe, 0x00],
+        b"-1\n".to_vec(),
+        b"18446744073709551615\n18446744073709551615\n".to_vec(),
+        "[".repeat(10_000).into_bytes(),
+    ];
+
+    for _ in 0..RANDOM_INPUTS {
+        let length = random.below(64);
+        inputs.push(
+            (0..length)
+                .map(|_| ALPHABET[random.below(ALPHABET.len())])
+                .collect(),
+        );
+    }
+
+    for solver in &solvers {
+        for input in &inputs {
+            check_no_panic(*solver, input, &mut failures);
+        }
+
+        for example in example_inputs(solver.day()) {
+            for input in truncations(&example) {
+                check_no_panic(*solver, &input, &mut failures);
+            }
+
+            for _ in 0..MUTATIONS_PER_EXAMPLE {
+                let mut input = example.clone();
+                let index = random.below(input.len());
+                input[index] = ALPHABET[random.below(ALPHABET.len())];
+                check_no_panic(*solver, &input, &mut failures);
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+}
+
+#[test]
+fn known_malformed_inputs_are_errors() {
+    let solvers = solver::registry();
+    let mut failures = Vec::<String>::new();
+
+    for (day, input) in KNOWN_MALFORMED {
+        for solver in solvers.iter().filter(|solver| solver.day() == *day) {
+            if let Ok(answer) = solver.solve(&mut input.as_bytes()) {
+                failures.push(format!(
+                    "{}.{} answered {} for {:?}",
+                    day,
+                    solver.part(),
+                    answer,
+                    input
+                ));
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+// Directories nested this deep used to take minutes to build, and overflowed the stack solving.
+#[test]
+fn deeply_nested_directories_are_errors() {
+    let input = format!(
+        "$ cd /\n{}$ ls\n5 f\n",
+        "$ ls\ndir a\n$ cd a\n".repeat(100_000)
+    );
+
+    for solver in solver::registry().iter().filter(|solver| solver.day() == 7) {
+        assert!(solver.solve(&mut input.as_bytes()).is_err());
+    }
+}
+
+// Some mistakes only show up once the whole input's been read, or partway through solving, but
+// they still have to point at the line that caused them.
+#[test]
+fn late_errors_point_at_their_line() {
+    const BOTH: &[usize] = &[1, 2];
+
+    let stacks = "    [D]\n[N] [C]\n 1   2\n\n";
+    let monkey = |number: usize, modulus: usize, target: usize| {
+        format!(
+            "Monkey {}:\n  Starting items: 79\n  Operation: new = old * 19\n  \
+             Test: divisible by {}\n    If true: throw to monkey {}\n    \
+             If false: throw to monkey 0\n",
+            number, modulus, target
+        )
+    };
+    let huge = usize::MAX / 2;
+
+    let cases = [
+        (
+            5,
+            BOTH,
+            format!("{}move 1 from 1 to 2\nmove 1 from 2 to 3\n", stacks),
+            (6, 18, "There's no stack 3."),
+        ),
+        (
+            5,
+            BOTH,
+            format!("{}move 1 from 3 to 1\n", stacks),
+            (5, 13, "There's no stack 3."),
+        ),
+        (
+            5,
+            BOTH,
+            format!("{}move 3 from 1 to 2\n", stacks),
+            (5, 6, "Not enough crates in stack 1."),
+        ),
+        (
+            3,
+            &[2][..],
+            String::from("vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n"),
+            (2, 33, "Number of rucksacks was not a multiple of three."),
+        ),
+        (
+            11,
+            BOTH,
+            format!("{}\n{}", monkey(0, 23, 1), monkey(1, 19, 2)),
+            (12, 30, "There's no monkey 2."),
+        ),
+        (
+            11,
+            BOTH,
+            format!("{}\n{}", monkey(0, huge, 1), monkey(1, huge, 0)),
+            (11, 22, "The numbers got too big to multiply."),
+        ),
+        (
+            11,
+            BOTH,
+            monkey(0, 23, 0),
+            (6, 32, "Missing a second monkey."),
+        ),
+        (
+            12,
+            BOTH,
+            String::from("abc\nSbc\nxyz\n"),
+            (3, 4, "Missing the end, 'E'."),
+        ),
+    ];
+
+    for (day, parts, input, (line, column, problem)) in cases {
+        for solver in solver::registry()
+            .iter()
+            .filter(|solver| solver.day() == day && parts.contains(&solver.part()))
+        {
+            match solver.solve(&mut input.as_bytes()) {
+                Err(Error::Input {
+                    location,
+                    problem: found,
+                    ..
+                }) => assert_eq!(
+                    (location.line, location.column, found.to_string()),
+                    (line, column, String::from(problem)),
+                    "{}.{} on {:?}",
+                    day,
+                    solver.part(),
+                    input
+                ),
+                other => panic!(
+                    "{}.{} on {:?} gave {:?} rather than an input error",
+                    day,
+                    solver.part(),
+                    input,
+                    other.map(|answer| answer.to_string())
+                ),
+            }
+        }
+    }
+}
+
+// Day 6's parts look for different markers, so the error has to say which one wasn't there.
+#[test]
+fn missing_markers_say_how_long_they_are() {
+    for solver in solver::registry().iter().filter(|solver| solver.day() == 6) {
+        let size = if solver.part() == 1 { 4 } else { 14 };
+
+        assert_eq!(
+            solver
+                .solve(&mut "abcabc\n".as_bytes())
+                .unwrap_err()
+                .to_string(),
+            format!(
+                "Day 6: No marker found: no {} characters in a row were all different.",
+                size
+            )
+        );
+    }
+}
+
+fn check_no_panic(solver: &dyn Solver, input: &[u8], failures: &mut Vec<String>) {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| solver.solve(&mut &input[..])));
+
+    if result.is_err() {
+        failures.push(format!(
+            "{}.{} panicked on {:?}",
+            solver.day(),
+            solver.part(),
+            String::from_utf8_lossy(input)
+        ));
+    }
+}
+
+fn example_inputs(day: usize) -> Vec<Vec<u8>> {
+    let directory = Path::new(EXAMPLES_DIRECTORY).join(format!("day{}", day));
+
+    match fs::read(directory.join("input.txt")) {
+        Ok(bytes) => vec![bytes],
+        Err(_) => Vec::new(),
+    }
+}
+
+// The example cut off at the end of every line, and again halfway through it.
+fn truncations(example: &[u8]) -> Vec<Vec<u8>> {
+    let mut truncations = Vec::<Vec<u8>>::new();
+    let mut line_start = 0;
+
+    for (index, byte) in example.iter().enumerate() {
+        if *byte == b'\n' {
+            truncations.push(example[..line_start + (index - line_start) / 2].to_vec());
+            truncations.push(example[..index].to_vec());
+            line_start = index + 1;
+        }
+    }
+
+    truncations
+}
+
+// A tiny xorshift generator, so every run tries the same inputs.
+struct Random(u64);
+
+impl Random {
+    fn below(&mut self, bound: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+
+        (self.0 % bound.max(1) as u64) as usize
+    }
+}
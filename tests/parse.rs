@@ -0,0 +1,150 @@
+use advent_of_code_2022::parse::{self, Cursor, Fields};
+use advent_of_code_2022::{Error, Line, Result};
+
+// Where an error points, as (line, column, problem), so tests can check the blame lands right.
+fn blame<T>(result: Result<T>) -> (usize, usize, String) {
+    match result {
+        Err(Error::Input {
+            location, problem, ..
+        }) => (location.line, location.column, problem.to_string()),
+        Err(error) => panic!("expected an input error, got {}", error),
+        Ok(_) => panic!("expected an input error"),
+    }
+}
+
+fn record_texts(input: &str) -> Vec<Vec<(usize, String)>> {
+    parse::records(input.as_bytes(), 0)
+        .map(|record| {
+            record
+                .unwrap()
+                .lines()
+                .map(|line| (line.number, line.text.to_string()))
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn lines_are_numbered_from_one() {
+    let numbered = parse::lines("a\nb\n\nc".as_bytes(), 0, |line| {
+        Ok((line.number, line.text.to_string()))
+    })
+    .unwrap();
+
+    assert_eq!(
+        numbered,
+        [(1, "a"), (2, "b"), (3, ""), (4, "c")].map(|(number, text)| (number, text.to_string()))
+    );
+}
+
+#[test]
+fn records_are_split_at_blank_lines() {
+    let records = record_texts("1\n2\n\n3\n  \n\n4\n\n");
+
+    assert_eq!(records.len(), 4);
+    assert_eq!(records[0], [(1, "1".to_string()), (2, "2".to_string())]);
+    assert_eq!(records[1], [(4, "3".to_string())]);
+    assert!(records[2].is_empty());
+    assert_eq!(records[3], [(7, "4".to_string())]);
+
+    assert!(record_texts("").is_empty());
+    assert_eq!(record_texts("\n").len(), 1);
+}
+
+#[test]
+fn fixed_records_report_missing_lines() {
+    let record = parse::records("first\n".as_bytes(), 4)
+        .next()
+        .unwrap()
+        .unwrap();
+    let mut lines = record.lines();
+
+    assert_eq!(lines.next_or_missing("first line").unwrap().text, "first");
+    assert_eq!(
+        blame(lines.next_or_missing("second line")),
+        (1, 6, String::from("Missing second line."))
+    );
+}
+
+#[test]
+fn fields_point_at_what_went_wrong() {
+    let line = Line::new(0, 3, "move 12 from x");
+    let mut fields = Fields::new(&line);
+
+    fields.literal("move").unwrap();
+    assert_eq!(fields.number::<usize>("a quantity").unwrap(), 12);
+    assert_eq!(
+        blame(fields.literal("to")),
+        (3, 9, "Expected 'to'.".to_string())
+    );
+    assert_eq!(
+        blame(fields.number::<usize>("a stack")),
+        (3, 14, "Expected a number.".to_string())
+    );
+    assert!(fields.is_empty());
+    assert!(fields.end().is_ok());
+}
+
+#[test]
+fn fields_choose_from_a_table() {
+    let line = Line::new(0, 1, "A Q extra");
+    let mut fields = Fields::new(&line);
+    let choices = [("A", 1), ("B", 2)];
+
+    assert_eq!(fields.one_of("choice", &choices).unwrap(), 1);
+    assert_eq!(blame(fields.one_of("choice", &choices)).1, 3);
+    assert_eq!(blame(fields.end()).1, 5);
+}
+
+#[test]
+fn pairs_must_have_exactly_one_separator() {
+    let line = Line::new(0, 1, "2-4,6-8");
+    let (first, second) = parse::split_pair(&line, line.text, ',', "two ranges").unwrap();
+
+    assert_eq!((first, second), ("2-4", "6-8"));
+    assert_eq!(
+        parse::split_pair(&line, second, '-', "a range").unwrap(),
+        ("6", "8")
+    );
+
+    let line = Line::new(0, 1, "2-4-6");
+    assert_eq!(
+        blame(parse::split_pair(&line, line.text, '-', "a range")),
+        (1, 1, "Expected a range.".to_string())
+    );
+}
+
+#[test]
+fn labels_and_keys() {
+    let line = Line::new(0, 1, "  Starting items: 79, 98");
+    assert_eq!(parse::labelled(&line, "Starting items:").unwrap(), "79, 98");
+    assert!(parse::labelled(&line, "Operation:").is_err());
+
+    assert_eq!(
+        parse::key_value(&line).unwrap(),
+        ("Starting items", "79, 98")
+    );
+    assert!(parse::key_value(&Line::new(0, 1, "no colon")).is_err());
+}
+
+#[test]
+fn cursors_walk_bytes() {
+    let line = Line::new(0, 1, "[10,x]");
+    let mut cursor = Cursor::new(&line);
+
+    assert_eq!(cursor.advance(), Some(b'['));
+    assert_eq!(cursor.number::<u8>("a digit").unwrap(), 10);
+    assert_eq!(cursor.peek(), Some(b','));
+    assert_eq!(cursor.take_while(|byte| byte == b','), ",");
+    assert_eq!(cursor.position(), 4);
+    assert_eq!(
+        blame(cursor.number::<u8>("a digit")),
+        (1, 5, "Expected a digit.".to_string())
+    );
+
+    let line = Line::new(0, 1, "999");
+    assert_eq!(
+        blame(Cursor::new(&line).number::<u8>("a digit")).2,
+        "Expected a smaller number."
+    );
+}
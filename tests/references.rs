@@ -0,0 +1,60 @@
+use std::fs;
+
+use advent_of_code_2022::diffcheck;
+use advent_of_code_2022::solver;
+
+const EXAMPLES_DIRECTORY: &str = "examples";
+const ANSWERS_FILE: &str = "answers.txt";
+
+// The references are only worth checking against if they get the puzzle's own examples right.
+#[test]
+fn every_reference_agrees_on_the_examples() {
+    let mut failures = Vec::<String>::new();
+
+    for solver in solver::registry() {
+        let reference = solver::reference(solver.day(), solver.part())
+            .unwrap_or_else(|| panic!("No reference for {}.{}", solver.day(), solver.part()));
+        let directory = format!("{}/day{}", EXAMPLES_DIRECTORY, solver.day());
+
+        for entry in fs::read_dir(directory).unwrap() {
+            let path = entry.unwrap().path();
+            if path.file_name().unwrap() == ANSWERS_FILE {
+                continue;
+            }
+
+            let input = fs::read_to_string(&path).unwrap();
+            let expected = solver.solve(&mut input.as_bytes()).ok();
+            let answer = reference(&input);
+
+            if answer != expected {
+                failures.push(format!(
+                    "{}.{} on {}: the solution got {:?} but the reference got {:?}",
+                    solver.day(),
+                    solver.part(),
+                    path.display(),
+                    expected,
+                    answer
+                ));
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn every_reference_agrees_on_small_generated_inputs() {
+    for solver in solver::registry() {
+        let reference = solver::reference(solver.day(), solver.part()).unwrap();
+
+        if let Some(disagreement) = diffcheck::find_disagreement(solver, reference, 20, 10, 2022) {
+            panic!(
+                "Day {}, part {} disagreed.\n{}\n{}",
+                solver.day(),
+                solver.part(),
+                disagreement,
+                disagreement.input
+            );
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use std::time::Duration;
+
+use advent_of_code_2022::report::{self, Format, Record};
+use advent_of_code_2022::solver::Answer;
+
+fn answered(day: usize, part: usize, answer: Answer) -> Record {
+    Record {
+        day,
+        part,
+        answer: Some(answer),
+        error: None,
+        time: Some(Duration::from_nanos(1500)),
+    }
+}
+
+fn errored(day: usize, part: usize, error: &str) -> Record {
+    Record {
+        day,
+        part,
+        answer: None,
+        error: Some(String::from(error)),
+        time: None,
+    }
+}
+
+fn crt() -> Answer {
+    Answer::from(vec![String::from("##..#"), String::from("#..##")])
+}
+
+// Every awkward character at once: quotes, commas, pipes, backslashes and a line break.
+const TRICKY: &str = "Day 5, line 2: \"a|b\" \\ here\nand here";
+
+#[test]
+fn json_is_an_array_of_one_line_objects() {
+    let records = [
+        answered(1, 1, Answer::from(24000usize)),
+        errored(5, 2, TRICKY),
+    ];
+
+    assert_eq!(
+        report::render(Format::Json, &records),
+        "[\n  \
+         {\"day\": 1, \"part\": 1, \"answer\": \"24000\", \"status\": \"ok\", \"error\": null, \"time_ns\": 1500},\n  \
+         {\"day\": 5, \"part\": 2, \"answer\": null, \"status\": \"error\", \"error\": \"Day 5, line 2: \\\"a|b\\\" \\\\ here\\nand here\", \"time_ns\": null}\n\
+         ]"
+    );
+    assert_eq!(report::render(Format::Json, &[]), "[]");
+}
+
+#[test]
+fn json_escapes_pictures_and_control_characters() {
+    assert_eq!(
+        report::render_json_record(&answered(10, 2, crt())),
+        "{\"day\": 10, \"part\": 2, \"answer\": \"##..#\\n#..##\", \"status\": \"ok\", \"error\": null, \"time_ns\": 1500}"
+    );
+    assert_eq!(
+        report::json_string_or_null(Some(String::from("tab\there\r\u{1}"))),
+        "\"tab\\there\\r\\u0001\""
+    );
+    assert_eq!(report::json_string_or_null(None), "null");
+}
+
+#[test]
+fn csv_quotes_only_the_fields_that_need_it() {
+    let records = [
+        answered(1, 1, Answer::from(24000usize)),
+        answered(5, 1, Answer::from(String::from("CMZ"))),
+        answered(10, 2, crt()),
+        errored(5, 2, TRICKY),
+    ];
+
+    assert_eq!(
+        report::render(Format::Csv, &records),
+        "day,part,answer,status,error,time_ns\n\
+         1,1,24000,ok,,1500\n\
+         5,1,CMZ,ok,,1500\n\
+         10,2,\"##..#\n#..##\",ok,,1500\n\
+         5,2,,error,\"Day 5, line 2: \"\"a|b\"\" \\ here\nand here\","
+    );
+    assert_eq!(
+        report::render(Format::Csv, &[]),
+        "day,part,answer,status,error,time_ns"
+    );
+}
+
+#[test]
+fn markdown_keeps_every_record_on_one_row() {
+    let records = [
+        answered(1, 1, Answer::from(-3isize)),
+        answered(10, 2, crt()),
+        errored(5, 2, TRICKY),
+    ];
+
+    assert_eq!(
+        report::render(Format::Markdown, &records),
+        "| Day | Part | Answer | Status | Time | Error |\n\
+         | --: | ---: | ------ | ------ | ---: | ----- |\n\
+         | 1 | 1 | -3 | ok | 1.5µs |  |\n\
+         | 10 | 2 | ##..#<br>#..## | ok | 1.5µs |  |\n\
+         | 5 | 2 |  | error |  | Day 5, line 2: \"a\\|b\" \\ here<br>and here |"
+    );
+}
+
+#[test]
+fn formats_are_named_in_lower_case() {
+    assert_eq!("json".parse::<Format>().unwrap(), Format::Json);
+    assert_eq!("csv".parse::<Format>().unwrap(), Format::Csv);
+    assert_eq!("markdown".parse::<Format>().unwrap(), Format::Markdown);
+    assert_eq!(
+        "JSON".parse::<Format>().unwrap_err().to_string(),
+        "Unknown format 'JSON'."
+    );
+}
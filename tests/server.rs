@@ -0,0 +1,152 @@
+use std::fs;
+use std::io::{Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpStream};
+use std::thread;
+use std::time::Duration;
+
+use advent_of_code_2022::server::{self, Server};
+use advent_of_code_2022::solver;
+
+// Every test gets its own server on a free loopback port, left running in the background until the
+// test process exits.
+fn start_server() -> SocketAddr {
+    let server = Server::bind("127.0.0.1:0").unwrap();
+    let address = server.local_addr().unwrap();
+    thread::spawn(move || server.serve());
+
+    address
+}
+
+// Sends the raw request and returns the status code and body of the response.
+fn send(address: SocketAddr, request: &[u8]) -> (u16, String) {
+    let mut stream = TcpStream::connect(address).unwrap();
+    stream.write_all(request).unwrap();
+    stream.shutdown(Shutdown::Write).unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+
+    (status, String::from(body))
+}
+
+fn post(address: SocketAddr, path: &str, body: &str) -> (u16, String) {
+    let request = format!(
+        "POST {} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}",
+        path,
+        body.len(),
+        body
+    );
+
+    send(address, request.as_bytes())
+}
+
+#[test]
+fn solves_a_posted_input() {
+    let address = start_server();
+    let input = fs::read_to_string("examples/day1/input.txt").unwrap();
+
+    let (status, body) = post(address, "/day/1/part/2", &input);
+
+    assert_eq!(status, 200);
+    assert!(body.starts_with("{\"day\": 1, \"part\": 2, \"answer\": \"45000\", \"status\": \"ok\", \"error\": null, \"time_ns\": "));
+}
+
+#[test]
+fn reports_malformed_input_as_an_error() {
+    let address = start_server();
+
+    let (status, body) = post(address, "/day/4/part/1", "2-x,6-8\n");
+
+    assert_eq!(status, 422);
+    assert!(body.contains("\"answer\": null, \"status\": \"error\""));
+    assert!(body.contains("line 1, column 3: Expected a number."));
+    assert!(body.contains("\"time_ns\": null"));
+}
+
+#[test]
+fn lists_every_solver() {
+    let address = start_server();
+
+    let (status, body) = send(address, b"GET /days HTTP/1.1\r\nHost: localhost\r\n\r\n");
+
+    assert_eq!(status, 200);
+    for solver in solver::registry() {
+        assert!(body.contains(&format!(
+            "{{\"day\": {}, \"part\": {}, \"name\": \"{}\"}}",
+            solver.day(),
+            solver.part(),
+            solver.name()
+        )));
+    }
+}
+
+#[test]
+fn rejects_unknown_paths_and_methods() {
+    let address = start_server();
+
+    assert_eq!(post(address, "/day/99/part/1", "").0, 404);
+    assert_eq!(post(address, "/day/one/part/1", "").0, 404);
+    assert_eq!(post(address, "/nowhere", "").0, 404);
+    assert_eq!(post(address, "/days", "").0, 405);
+    assert_eq!(send(address, b"GET /day/1/part/1 HTTP/1.1\r\n\r\n").0, 405);
+}
+
+#[test]
+fn rejects_malformed_requests() {
+    let address = start_server();
+
+    assert_eq!(send(address, b"nonsense\r\n\r\n").0, 400);
+    assert_eq!(send(address, b"GET /days SPDY/3\r\n\r\n").0, 505);
+    assert_eq!(
+        send(
+            address,
+            b"POST /day/1/part/1 HTTP/1.1\r\nContent-Length: lots\r\n\r\n"
+        )
+        .0,
+        400
+    );
+    assert_eq!(
+        send(
+            address,
+            b"POST /day/1/part/1 HTTP/1.1\r\nContent-Length: 10\r\n\r\n1000"
+        )
+        .0,
+        400
+    );
+    assert_eq!(
+        send(
+            address,
+            b"POST /day/6/part/1 HTTP/1.1\r\nContent-Length: 2\r\n\r\n\xff\xfe"
+        )
+        .0,
+        400
+    );
+}
+
+// Clients that connect and then say nothing hold on to a worker each. Once every worker is busy,
+// anyone else waits until one is free, rather than getting a thread of their own.
+#[test]
+fn connections_beyond_the_pool_wait_their_turn() {
+    let address = start_server();
+    let idle = (0..server::WORKERS)
+        .map(|_| TcpStream::connect(address).unwrap())
+        .collect::<Vec<TcpStream>>();
+
+    let mut waiting = TcpStream::connect(address).unwrap();
+    waiting
+        .write_all(b"GET /days HTTP/1.1\r\nHost: localhost\r\n\r\n")
+        .unwrap();
+    waiting
+        .set_read_timeout(Some(Duration::from_millis(300)))
+        .unwrap();
+    assert!(waiting.read(&mut [0; 1]).is_err());
+
+    drop(idle);
+    waiting.set_read_timeout(None).unwrap();
+    let mut response = String::new();
+    waiting.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+}
@@ -0,0 +1,258 @@
+use std::io::{self, BufRead, Read};
+
+use advent_of_code_2022::day8;
+use advent_of_code_2022::memory::{self, CountingAllocator, Usage};
+use advent_of_code_2022::random::Random;
+use advent_of_code_2022::solver::{self, Answer, Solver, Streaming};
+
+// Counting is per thread, so the tests running alongside each other don't get in the way.
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+// An input made up a line at a time as it's read, so a big one never has to be in memory, and
+// the test's own allocations don't count against the solution. The line's room is set aside up
+// front, so reading doesn't allocate either.
+struct Synthesized<F> {
+    line: Vec<u8>,
+    read: usize,
+    next_line: F,
+}
+
+impl<F: FnMut(&mut Vec<u8>)> Synthesized<F> {
+    fn new(longest_line: usize, next_line: F) -> Self {
+        Self {
+            line: Vec::with_capacity(longest_line + 1),
+            read: 0,
+            next_line,
+        }
+    }
+}
+
+impl<F: FnMut(&mut Vec<u8>)> Read for Synthesized<F> {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let length = available.len().min(buffer.len());
+        buffer[..length].copy_from_slice(&available[..length]);
+        self.consume(length);
+
+        Ok(length)
+    }
+}
+
+impl<F: FnMut(&mut Vec<u8>)> BufRead for Synthesized<F> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.read == self.line.len() {
+            let capacity = self.line.capacity();
+            self.line.clear();
+            self.read = 0;
+            (self.next_line)(&mut self.line);
+            assert_eq!(
+                self.line.capacity(),
+                capacity,
+                "a line was longer than promised"
+            );
+        }
+
+        Ok(&self.line[self.read..])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.read += amount;
+    }
+}
+
+fn streaming(day: usize, part: usize) -> &'static Streaming {
+    solver::streaming_registry()
+        .into_iter()
+        .find(|streaming| streaming.day == day && streaming.part == part)
+        .unwrap()
+}
+
+fn regular(day: usize, part: usize) -> &'static dyn Solver {
+    solver::registry()
+        .into_iter()
+        .find(|solver| solver.day() == day && solver.part() == part)
+        .unwrap()
+}
+
+fn stream_and_measure(day: usize, part: usize, input: &mut dyn BufRead) -> (Answer, Usage) {
+    let stream = streaming(day, part).stream;
+    let (answer, usage) = memory::measure(|| stream(input));
+
+    (answer.unwrap(), usage)
+}
+
+// The datastream is the three letters that can never make a marker, until the very end.
+fn datastream(length: usize, seed: u64) -> impl BufRead {
+    const MARKER: &[u8] = b"defghijklmnopq\n";
+    const CHUNK: usize = 4096;
+
+    let mut random = Random::new(seed);
+    let mut remaining = length;
+    let mut finished = false;
+
+    Synthesized::new(CHUNK, move |line: &mut Vec<u8>| {
+        if remaining > 0 {
+            let chunk = remaining.min(CHUNK);
+            line.extend((0..chunk).map(|_| *random.choose(b"abc")));
+            remaining -= chunk;
+        } else if !finished {
+            line.extend_from_slice(MARKER);
+            finished = true;
+        }
+    })
+}
+
+fn forest(width: usize, height: usize, seed: u64) -> impl BufRead {
+    let mut random = Random::new(seed);
+    let mut rows = 0;
+
+    Synthesized::new(width, move |line: &mut Vec<u8>| {
+        if rows < height {
+            line.extend((0..width).map(|_| b'0' + random.below(10) as u8));
+            line.push(b'\n');
+            rows += 1;
+        }
+    })
+}
+
+// Pairs of packets written out to `depth`, each line at most `longest_line` long.
+fn packets(pairs: usize, depth: usize, longest_line: usize, seed: u64) -> impl BufRead {
+    let mut random = Random::new(seed);
+    let mut lines = 0;
+
+    Synthesized::new(longest_line, move |line: &mut Vec<u8>| {
+        if lines < pairs * 3 {
+            if lines % 3 != 2 {
+                write_packet(line, &mut random, depth, longest_line - 1);
+            }
+            line.push(b'\n');
+            lines += 1;
+        }
+    })
+}
+
+fn write_packet(line: &mut Vec<u8>, random: &mut Random, depth: usize, room: usize) {
+    line.push(b'[');
+    let mut first = true;
+
+    while line.len() + 8 < room && !random.one_in(4) {
+        if !first {
+            line.push(b',');
+        }
+        first = false;
+
+        if depth > 0 && random.one_in(3) {
+            write_packet(line, random, depth - 1, room - 1);
+        } else {
+            line.extend(random.below(11).to_string().bytes());
+        }
+    }
+
+    line.push(b']');
+}
+
+fn read_all(mut input: impl BufRead) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    input.read_to_end(&mut bytes).unwrap();
+    bytes
+}
+
+#[test]
+fn streaming_answers_match_the_regular_solutions() {
+    let mut inputs = Vec::<(usize, Vec<u8>)>::new();
+    for seed in 0..20 {
+        inputs.push((6, read_all(datastream(seed as usize * 37, seed))));
+        inputs.push((
+            8,
+            read_all(forest(1 + seed as usize % 7, 1 + seed as usize / 3, seed)),
+        ));
+        inputs.push((13, read_all(packets(seed as usize, 4, 80, seed))));
+    }
+    // The dividers themselves, and packets that only equal them by the puzzle's rules.
+    inputs.push((
+        13,
+        b"[[2]]\n[[6]]\n\n[2]\n[6]\n\n[[[2]]]\n[[[6]]]\n".to_vec(),
+    ));
+
+    for (day, input) in inputs {
+        for part in 1..=2 {
+            let expected = regular(day, part).solve(&mut input.as_slice());
+            let answer = streaming(day, part).solve(&mut input.as_slice());
+
+            assert_eq!(
+                answer.map_err(|error| error.to_string()),
+                expected.map_err(|error| error.to_string()),
+                "day {} part {} on {:?}",
+                day,
+                part,
+                String::from_utf8_lossy(&input)
+            );
+        }
+    }
+}
+
+// 16 MiB of datastream without a single allocation.
+#[test]
+fn datastreams_are_searched_without_allocating() {
+    for part in 1..=2 {
+        let (answer, usage) = stream_and_measure(6, part, &mut datastream(1 << 24, 6));
+
+        assert!(matches!(answer, Answer::Unsigned(end) if end > 1 << 24));
+        assert_eq!(usage.allocations, 0, "day 6 part {}", part);
+    }
+}
+
+// The memory's all per column, so a forest a hundred times taller needs no more room. What's used
+// has to be what the listing says, give or take the few bytes that don't depend on the width.
+#[test]
+fn forests_only_need_room_for_a_row() {
+    const WIDTH: usize = 1000;
+
+    for (part, per_column) in [
+        (1, day8::streaming::PART1_BYTES_PER_COLUMN),
+        (2, day8::streaming::PART2_BYTES_PER_COLUMN),
+    ] {
+        let memory = streaming(8, part).memory;
+        assert!(
+            memory.starts_with(&format!("{} bytes for each column", per_column)),
+            "day 8 part {}: {}",
+            part,
+            memory
+        );
+
+        let (_, short) = stream_and_measure(8, part, &mut forest(WIDTH, 40, 8));
+        let (_, tall) = stream_and_measure(8, part, &mut forest(WIDTH, 4000, 8));
+
+        let least = WIDTH * per_column;
+        assert!(
+            (least..least + 64).contains(&short.peak_bytes),
+            "day 8 part {}: {}",
+            part,
+            short
+        );
+        assert_eq!(short.peak_bytes, tall.peak_bytes, "day 8 part {}", part);
+    }
+}
+
+#[test]
+fn packets_only_need_room_for_a_pair() {
+    const LONGEST_LINE: usize = 200;
+    // Two packets, each up to 48 bytes a character, and the line they're read from.
+    let limit = 2 * 48 * LONGEST_LINE + 2 * LONGEST_LINE + 1024;
+
+    for part in 1..=2 {
+        let (_, few) = stream_and_measure(13, part, &mut packets(10, 6, LONGEST_LINE, 13));
+        let (_, many) = stream_and_measure(13, part, &mut packets(20_000, 6, LONGEST_LINE, 13));
+
+        assert!(few.peak_bytes <= limit, "day 13 part {}: {}", part, few);
+        assert!(many.peak_bytes <= limit, "day 13 part {}: {}", part, many);
+    }
+
+    // The worst case for the limit is nesting as deep as the line allows.
+    let depth = LONGEST_LINE / 2 - 1;
+    let nested = format!("{}{}\n", "[".repeat(depth), "]".repeat(depth));
+    let input = format!("{}{}\n", nested, nested);
+    let (_, usage) = stream_and_measure(13, 1, &mut input.as_bytes());
+    assert!(usage.peak_bytes <= limit, "{}", usage);
+}
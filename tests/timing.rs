@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+use advent_of_code_2022::timing::Statistics;
+
+fn millis(samples: &[u64]) -> Vec<Duration> {
+    samples.iter().copied().map(Duration::from_millis).collect()
+}
+
+fn statistics(samples: &[u64]) -> Statistics {
+    Statistics::from_samples(&millis(samples)).unwrap()
+}
+
+#[test]
+fn no_samples_have_no_statistics() {
+    assert_eq!(Statistics::from_samples(&[]), None);
+}
+
+#[test]
+fn one_sample_is_every_statistic() {
+    let sample = Duration::from_millis(5);
+
+    assert_eq!(
+        statistics(&[5]),
+        Statistics {
+            min: sample,
+            median: sample,
+            mean: sample,
+            p95: sample,
+            std_dev: Duration::ZERO,
+        }
+    );
+}
+
+#[test]
+fn an_odd_number_of_samples_has_a_middle_one() {
+    assert_eq!(
+        statistics(&[3, 1, 2]),
+        Statistics {
+            min: Duration::from_millis(1),
+            median: Duration::from_millis(2),
+            mean: Duration::from_millis(2),
+            p95: Duration::from_millis(3),
+            // The square root of 2/3 of a square millisecond.
+            std_dev: Duration::from_nanos(816_497),
+        }
+    );
+}
+
+#[test]
+fn an_even_number_of_samples_splits_the_middle_two() {
+    assert_eq!(
+        statistics(&[4, 1, 3, 2]),
+        Statistics {
+            min: Duration::from_millis(1),
+            median: Duration::from_micros(2500),
+            mean: Duration::from_micros(2500),
+            p95: Duration::from_millis(4),
+            // The square root of 1.25 square milliseconds.
+            std_dev: Duration::from_nanos(1_118_034),
+        }
+    );
+}
+
+// The p95 is the sample 95% of the way up, rounding up, so it's always one that was actually seen.
+#[test]
+fn the_p95_is_a_sample() {
+    let hundred = (1..=100).rev().collect::<Vec<u64>>();
+    assert_eq!(statistics(&hundred).p95, Duration::from_millis(95));
+    assert_eq!(statistics(&hundred).median, Duration::from_micros(50_500));
+
+    let twenty = (1..=20).collect::<Vec<u64>>();
+    assert_eq!(statistics(&twenty).p95, Duration::from_millis(19));
+
+    let nineteen = (1..=19).collect::<Vec<u64>>();
+    assert_eq!(statistics(&nineteen).p95, Duration::from_millis(19));
+}
+
+#[test]
+fn the_same_samples_have_no_spread() {
+    let same = statistics(&[7; 10]);
+
+    assert_eq!(same.std_dev, Duration::ZERO);
+    assert_eq!(same.mean, Duration::from_millis(7));
+    assert_eq!(same.p95, Duration::from_millis(7));
+}
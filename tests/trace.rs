@@ -0,0 +1,87 @@
+use std::env;
+use std::fs;
+use std::process::{Command, Output};
+
+use advent_of_code_2022::trace::{self, Level};
+
+fn run(args: &[&str], variables: &[(&str, &str)]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_advent-of-code-2022"))
+        .args(args)
+        .env_remove(trace::LEVEL_VARIABLE)
+        .env_remove(trace::FILE_VARIABLE)
+        .envs(variables.iter().copied())
+        .output()
+        .unwrap()
+}
+
+// The answer lines, without the timings that change from run to run.
+fn answers(output: &Output) -> Vec<String> {
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            line.split_once(" (in ")
+                .map(|(answer, _)| answer.to_string())
+        })
+        .collect()
+}
+
+#[test]
+fn levels_parse_from_names_and_numbers() {
+    assert_eq!("debug".parse::<Level>().unwrap(), Level::Debug);
+    assert_eq!("TRACE".parse::<Level>().unwrap(), Level::Trace);
+    assert_eq!("0".parse::<Level>().unwrap(), Level::Off);
+    assert!("loud".parse::<Level>().is_err());
+
+    assert_eq!(Level::from_verbosity(1), Level::Debug);
+    assert_eq!(Level::from_verbosity(3), Level::Trace);
+}
+
+#[test]
+fn events_go_to_stderr_without_changing_answers() {
+    let input = ["--input", "examples/day5/input.txt", "5"];
+    let quiet = run(&input, &[]);
+    let verbose = run(&[&input[..], &["-v"]].concat(), &[]);
+
+    assert!(quiet.stderr.is_empty());
+    assert_eq!(answers(&quiet), answers(&verbose));
+    assert_eq!(
+        answers(&verbose),
+        ["Day 5, part 1: CMZ", "Day 5, part 2: MCD"]
+    );
+
+    let events = String::from_utf8_lossy(&verbose.stderr);
+    assert_eq!(events.lines().count(), 8);
+    assert!(events
+        .lines()
+        .all(|line| line.starts_with("debug day=5 event=move ")));
+}
+
+#[test]
+fn trace_files_and_levels_come_from_the_environment() {
+    let path = env::temp_dir().join(format!("aoc-trace-{}.log", std::process::id()));
+    let output = run(
+        &["--input", "examples/day13/input.txt", "13.1"],
+        &[
+            (trace::LEVEL_VARIABLE, "trace"),
+            (trace::FILE_VARIABLE, path.to_str().unwrap()),
+        ],
+    );
+    let events = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert!(output.stderr.is_empty());
+    assert_eq!(answers(&output), ["Day 13, part 1: 13"]);
+    assert!(events.contains("trace day=13 event=packet line=1 list=[1,1,3,1,1]\n"));
+}
+
+#[test]
+fn the_command_line_overrides_the_environment() {
+    let output = run(
+        &["--input", "examples/day12/input.txt", "12.1", "-v"],
+        &[(trace::LEVEL_VARIABLE, "off")],
+    );
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("debug day=12 event=search "));
+
+    let output = run(&["1"], &[(trace::LEVEL_VARIABLE, "loud")]);
+    assert_eq!(output.status.code(), Some(2));
+}